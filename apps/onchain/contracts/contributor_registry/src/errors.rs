@@ -12,4 +12,10 @@ pub enum ContributorError {
     InvalidGitHubHandle = 6,
     ReputationOverflow = 7,
     GitHubHandleTaken = 8,
+    SameAdmin = 9,
+    InsufficientReputation = 10,
+    ContributorSuspended = 11,
+    ReferenceTooLong = 12,
+    AlreadyMigrated = 13,
+    NoEligibleContributors = 14,
 }
@@ -1,4 +1,28 @@
-use soroban_sdk::{contractevent, Address, BytesN};
+use soroban_sdk::{contractevent, Address, BytesN, Env, String, Symbol};
+
+/// Emitted by every privileged entry point. All such events share the
+/// `admin_action` topic so an indexer can follow admin activity with one
+/// filter; `action` names the entry point.
+#[contractevent(topics = ["admin_action"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminActionEvent {
+    #[topic]
+    pub action: Symbol,
+    #[topic]
+    pub admin: Address,
+}
+
+/// Publish an [`AdminActionEvent`] for a privileged call
+pub fn admin_action(env: &Env, action: &str, admin: &Address) {
+    AdminActionEvent {
+        action: Symbol::new(env, action),
+        admin: admin.clone(),
+    }
+    .publish(env);
+}
+
+/// Schema version carried by versioned events; bump when their fields change
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
 
 /// Emitted when the contract WASM is upgraded to a new hash.
 #[contractevent]
@@ -15,3 +39,44 @@ pub struct AdminChangedEvent {
     pub old_admin: Address,
     pub new_admin: Address,
 }
+
+/// Emitted when a contributor's reputation changes. `clamped` is set when a
+/// decrement would have taken the score below zero and it was floored at 0.
+/// `reference` links the change to off-chain evidence when provided.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationUpdatedEvent {
+    #[topic]
+    pub contributor: Address,
+    pub old_reputation: u64,
+    pub new_reputation: u64,
+    pub clamped: bool,
+    pub reference: Option<String>,
+    pub schema_version: u32,
+}
+
+/// Emitted when a contributor is registered, on their own or in a batch.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributorRegisteredEvent {
+    #[topic]
+    pub contributor: Address,
+    pub github_handle: String,
+}
+
+/// Emitted when the admin suspends or reinstates a contributor.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributorSuspendedEvent {
+    #[topic]
+    pub contributor: Address,
+    pub suspended: bool,
+}
+
+/// Emitted when `migrate` moves storage to a new layout version.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VersionBumpedEvent {
+    pub old_version: u32,
+    pub new_version: u32,
+}
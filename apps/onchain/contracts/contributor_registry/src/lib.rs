@@ -5,11 +5,32 @@ mod events;
 mod storage;
 
 use errors::ContributorError;
-use events::{AdminChangedEvent, UpgradedEvent};
+use events::{
+    AdminChangedEvent, ContributorRegisteredEvent, ContributorSuspendedEvent,
+    ReputationUpdatedEvent, UpgradedEvent, VersionBumpedEvent, EVENT_SCHEMA_VERSION,
+};
 use notification_interface::{Notification, NotificationReceiverTrait};
 use soroban_sdk::xdr::FromXdr;
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol};
-use storage::{ContributorData, DataKey};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Map, String, Symbol, Val, Vec};
+use storage::{
+    ContributorData, ContributorDataV1, ContributorProfile, DataKey, RegistryState,
+    ReputationEntry, CONTRIBUTOR_V1_FIELDS,
+};
+
+/// Maximum length of a reputation change reference (PR URL or commit hash)
+const MAX_REFERENCE_LEN: u32 = 256;
+
+/// Number of most recent reputation changes kept per contributor
+const MAX_HISTORY: u32 = 100;
+
+/// Storage layout version written by this build; bump alongside a `migrate` step
+const STORAGE_VERSION: u32 = 2;
+
+/// Positive scores below this value each get their own leaderboard shard
+const EXACT_SCORE_BUCKETS: u64 = 64;
+
+/// Number of leaderboard shards; covers every `u64` score
+const SCORE_BUCKETS: u32 = 1024;
 
 #[contract]
 pub struct ContributorRegistryContract;
@@ -40,6 +61,9 @@ impl ContributorRegistryContract {
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::Version, &STORAGE_VERSION);
         Ok(())
     }
 
@@ -64,21 +88,212 @@ impl ContributorRegistryContract {
             return Err(ContributorError::ContributorAlreadyExists);
         }
         Self::ensure_github_handle_available(&env, &github_handle, &address)?;
+        Self::store_contributor(&env, address, github_handle);
+
+        Ok(())
+    }
+
+    /// Register several contributors at once (admin only)
+    ///
+    /// The admin vouches for every entry, so the listed addresses do not sign.
+    /// Entries whose address is already registered or whose handle is taken
+    /// or empty are skipped. Returns how many contributors were registered.
+    pub fn batch_register(
+        env: Env,
+        admin: Address,
+        entries: Vec<(Address, String)>,
+    ) -> Result<u32, ContributorError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContributorError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(ContributorError::Unauthorized);
+        }
+        admin.require_auth();
+
+        let mut registered = 0;
+        for (address, github_handle) in entries.iter() {
+            if github_handle.is_empty()
+                || env
+                    .storage()
+                    .persistent()
+                    .has(&DataKey::Contributor(address.clone()))
+                || Self::ensure_github_handle_available(&env, &github_handle, &address).is_err()
+            {
+                continue;
+            }
+            Self::store_contributor(&env, address, github_handle);
+            registered += 1;
+        }
+
+        events::admin_action(&env, "batch_register", &admin);
+        Ok(registered)
+    }
+
+    /// Store a new contributor, index it by handle and registration order, and
+    /// emit [`ContributorRegisteredEvent`]
+    fn store_contributor(env: &Env, address: Address, github_handle: String) {
         let timestamp = env.ledger().timestamp();
         let contributor = ContributorData {
             address: address.clone(),
             github_handle: github_handle.clone(),
             reputation_score: 0,
             registered_timestamp: timestamp,
+            suspended: false,
         };
         env.storage()
             .persistent()
             .set(&DataKey::Contributor(address.clone()), &contributor);
         env.storage()
             .persistent()
-            .set(&DataKey::GitHubIndex(github_handle), &address);
+            .set(&DataKey::GitHubIndex(github_handle.clone()), &address);
 
-        Ok(())
+        Self::index_contributor(env, &address, 0);
+
+        ContributorRegisteredEvent {
+            contributor: address,
+            github_handle,
+        }
+        .publish(env);
+    }
+
+    /// Give a contributor the next registration index and enter it on the
+    /// leaderboard and in the pick weights with `reputation`
+    fn index_contributor(env: &Env, address: &Address, reputation: u64) {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ContributorCount)
+            .unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ContributorAt(count), address);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ContributorIndex(address.clone()), &count);
+        env.storage()
+            .instance()
+            .set(&DataKey::ContributorCount, &(count + 1));
+
+        Self::fenwick_append(env, DataKey::PickWeight, count, reputation as i128);
+        Self::fenwick_append(env, DataKey::PositiveCount, count, 0);
+        Self::enter_leaderboard(env, count, reputation);
+        Self::adjust_total_reputation(env, 0, reputation);
+    }
+
+    /// Leaderboard shard of a positive score: exact below
+    /// [`EXACT_SCORE_BUCKETS`], then sixteen shards per power of two, so a
+    /// higher shard always holds higher scores
+    fn score_bucket(score: u64) -> u32 {
+        if score < EXACT_SCORE_BUCKETS {
+            return score as u32;
+        }
+        let exponent = 63 - score.leading_zeros();
+        EXACT_SCORE_BUCKETS as u32 + (exponent - 6) * 16 + ((score >> (exponent - 4)) & 15) as u32
+    }
+
+    /// Entries `(u64::MAX - score, registration index)` of a leaderboard shard,
+    /// ascending, so the position of an entry is its rank within the shard
+    fn rank_shard(env: &Env, bucket: u32) -> Vec<(u64, u32)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RankShard(bucket))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Enter the contributor at registration `index` on the leaderboard
+    /// Zero scores are ranked by registration order alone and stay off the shards
+    fn enter_leaderboard(env: &Env, index: u32, score: u64) {
+        if score == 0 {
+            return;
+        }
+        let bucket = Self::score_bucket(score);
+        let mut shard = Self::rank_shard(env, bucket);
+        let entry = (u64::MAX - score, index);
+        let position = shard.binary_search(entry).unwrap_or_else(|at| at);
+        shard.insert(position, entry);
+        env.storage()
+            .persistent()
+            .set(&DataKey::RankShard(bucket), &shard);
+        Self::fenwick_add(env, DataKey::RankBucket, bucket, SCORE_BUCKETS, 1);
+        Self::fenwick_add(
+            env,
+            DataKey::PositiveCount,
+            index,
+            Self::contributor_count(env),
+            1,
+        );
+    }
+
+    /// Take the contributor at registration `index` off the leaderboard
+    fn leave_leaderboard(env: &Env, index: u32, score: u64) {
+        if score == 0 {
+            return;
+        }
+        let bucket = Self::score_bucket(score);
+        let mut shard = Self::rank_shard(env, bucket);
+        if let Ok(position) = shard.binary_search((u64::MAX - score, index)) {
+            shard.remove(position);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::RankShard(bucket), &shard);
+        Self::fenwick_add(env, DataKey::RankBucket, bucket, SCORE_BUCKETS, -1);
+        Self::fenwick_add(
+            env,
+            DataKey::PositiveCount,
+            index,
+            Self::contributor_count(env),
+            -1,
+        );
+    }
+
+    /// Number of registrations so far
+    fn contributor_count(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ContributorCount)
+            .unwrap_or(0)
+    }
+
+    /// Sum of the first `len` entries of the Fenwick tree stored under `node_key`
+    fn fenwick_prefix(env: &Env, node_key: fn(u32) -> DataKey, len: u32) -> i128 {
+        let mut node = len;
+        let mut sum: i128 = 0;
+        while node > 0 {
+            sum += env
+                .storage()
+                .persistent()
+                .get::<_, i128>(&node_key(node))
+                .unwrap_or(0);
+            node -= node & node.wrapping_neg();
+        }
+        sum
+    }
+
+    /// Move entry `index` of a Fenwick tree over `size` entries by `delta`
+    fn fenwick_add(env: &Env, node_key: fn(u32) -> DataKey, index: u32, size: u32, delta: i128) {
+        let mut node = index + 1;
+        while node <= size {
+            let key = node_key(node);
+            let sum: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            env.storage().persistent().set(&key, &(sum + delta));
+            node += node & node.wrapping_neg();
+        }
+    }
+
+    /// Append entry `index` with `value` to a Fenwick tree that grows with
+    /// registrations and so far holds `index` entries
+    fn fenwick_append(env: &Env, node_key: fn(u32) -> DataKey, index: u32, value: i128) {
+        // A new last node covers (node - lowbit(node), node]; nothing above it exists yet
+        let node = index + 1;
+        let covered = Self::fenwick_prefix(env, node_key, index)
+            - Self::fenwick_prefix(env, node_key, node - (node & node.wrapping_neg()));
+        env.storage()
+            .persistent()
+            .set(&node_key(node), &(covered + value));
     }
 
     /// Update an existing contributor's profile data.
@@ -100,6 +315,15 @@ impl ContributorRegistryContract {
             .get(&DataKey::Contributor(address.clone()))
             .ok_or(ContributorError::ContributorNotFound)?;
 
+        let min_reputation: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinRepForHandleUpdate)
+            .unwrap_or(0);
+        if contributor.reputation_score < min_reputation {
+            return Err(ContributorError::InsufficientReputation);
+        }
+
         Self::ensure_github_handle_available(&env, &github_handle, &address)?;
         if contributor.github_handle != github_handle {
             env.storage()
@@ -124,6 +348,7 @@ impl ContributorRegistryContract {
         admin: Address,
         contributor_address: Address,
         delta: i64,
+        reference: Option<String>,
     ) -> Result<(), ContributorError> {
         let stored_admin: Address = env
             .storage()
@@ -134,38 +359,478 @@ impl ContributorRegistryContract {
             return Err(ContributorError::Unauthorized);
         }
         admin.require_auth();
+        if let Some(reference) = &reference {
+            if reference.len() > MAX_REFERENCE_LEN {
+                return Err(ContributorError::ReferenceTooLong);
+            }
+        }
         let mut contributor: ContributorData = env
             .storage()
             .persistent()
             .get(&DataKey::Contributor(contributor_address.clone()))
             .ok_or(ContributorError::ContributorNotFound)?;
 
-        let new_score = if delta > 0 {
-            contributor
-                .reputation_score
+        if contributor.suspended {
+            return Err(ContributorError::ContributorSuspended);
+        }
+
+        // Increments fail on overflow; decrements floor at 0 and flag the clamp
+        let old_score = contributor.reputation_score;
+        let (new_score, clamped) = if delta > 0 {
+            let new_score = old_score
                 .checked_add(delta as u64)
-                .ok_or(ContributorError::ReputationOverflow)?
+                .ok_or(ContributorError::ReputationOverflow)?;
+            (new_score, false)
         } else {
-            let new_delta = match delta.checked_abs() {
-                Some(new_delta) => new_delta as u64,
-                None => 0,
-            };
-            contributor.reputation_score.saturating_sub(new_delta)
+            match old_score.checked_sub(delta.unsigned_abs()) {
+                Some(new_score) => (new_score, false),
+                None => (0, true),
+            }
         };
         contributor.reputation_score = new_score;
+        env.storage().persistent().set(
+            &DataKey::Contributor(contributor_address.clone()),
+            &contributor,
+        );
+
+        Self::record_reputation_change(
+            &env,
+            contributor_address,
+            old_score,
+            new_score,
+            clamped,
+            reference,
+            false,
+        );
+
+        events::admin_action(&env, "update_reputation", &admin);
+        Ok(())
+    }
+
+    /// Allow or disallow a contract to award reputation (admin only)
+    ///
+    /// Sources such as the vesting wallet reward contributors through
+    /// [`Self::award_reputation`] without holding the admin role.
+    pub fn set_reputation_source(
+        env: Env,
+        admin: Address,
+        source: Address,
+        allowed: bool,
+    ) -> Result<(), ContributorError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContributorError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(ContributorError::Unauthorized);
+        }
+        admin.require_auth();
+
+        let key = DataKey::ReputationSource(source);
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        events::admin_action(&env, "set_reputation_source", &admin);
+        Ok(())
+    }
+
+    /// Award reputation to a contributor from an allowed source
+    pub fn award_reputation(
+        env: Env,
+        source: Address,
+        contributor_address: Address,
+        amount: u64,
+    ) -> Result<(), ContributorError> {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::ReputationSource(source.clone()))
+        {
+            return Err(ContributorError::Unauthorized);
+        }
+        source.require_auth();
+
+        let mut contributor: ContributorData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributor(contributor_address.clone()))
+            .ok_or(ContributorError::ContributorNotFound)?;
+        if contributor.suspended {
+            return Err(ContributorError::ContributorSuspended);
+        }
+
+        let old_score = contributor.reputation_score;
+        let new_score = old_score
+            .checked_add(amount)
+            .ok_or(ContributorError::ReputationOverflow)?;
+        contributor.reputation_score = new_score;
+        env.storage().persistent().set(
+            &DataKey::Contributor(contributor_address.clone()),
+            &contributor,
+        );
+
+        Self::record_reputation_change(
+            &env,
+            contributor_address,
+            old_score,
+            new_score,
+            false,
+            None,
+            false,
+        );
+        Ok(())
+    }
+
+    /// Set a contributor's reputation to an exact value (admin only)
+    ///
+    /// Meant for correcting a corrupted score; recorded in the history as a
+    /// correction. Emits [`ReputationUpdatedEvent`].
+    pub fn set_reputation(
+        env: Env,
+        admin: Address,
+        contributor_address: Address,
+        value: u64,
+    ) -> Result<(), ContributorError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContributorError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(ContributorError::Unauthorized);
+        }
+        admin.require_auth();
+
+        let mut contributor: ContributorData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributor(contributor_address.clone()))
+            .ok_or(ContributorError::ContributorNotFound)?;
+
+        let old_score = contributor.reputation_score;
+        contributor.reputation_score = value;
+        env.storage().persistent().set(
+            &DataKey::Contributor(contributor_address.clone()),
+            &contributor,
+        );
+
+        Self::record_reputation_change(
+            &env,
+            contributor_address,
+            old_score,
+            value,
+            false,
+            None,
+            true,
+        );
+        events::admin_action(&env, "set_reputation", &admin);
+        Ok(())
+    }
+
+    /// Append a reputation change to the contributor's history and emit
+    /// [`ReputationUpdatedEvent`]
+    ///
+    /// The history is a ring of `MAX_HISTORY` slots, so each change writes a
+    /// single entry and the oldest is overwritten once the ring is full.
+    fn record_reputation_change(
+        env: &Env,
+        contributor: Address,
+        old_reputation: u64,
+        new_reputation: u64,
+        clamped: bool,
+        reference: Option<String>,
+        correction: bool,
+    ) {
+        let len_key = DataKey::HistoryLen(contributor.clone());
+        let len: u32 = env.storage().persistent().get(&len_key).unwrap_or(0);
+        env.storage().persistent().set(
+            &DataKey::HistoryEntry(contributor.clone(), len % MAX_HISTORY),
+            &ReputationEntry {
+                old_reputation,
+                new_reputation,
+                timestamp: env.ledger().timestamp(),
+                reference: reference.clone(),
+                correction,
+            },
+        );
+        env.storage().persistent().set(&len_key, &(len + 1));
+        Self::reindex_reputation(env, &contributor, old_reputation, new_reputation);
+
+        ReputationUpdatedEvent {
+            contributor,
+            old_reputation,
+            new_reputation,
+            clamped,
+            reference,
+            schema_version: EVENT_SCHEMA_VERSION,
+        }
+        .publish(env);
+    }
+
+    /// Move the reputation total, the leaderboard and the pick weights by a
+    /// stored contributor's score change
+    fn reindex_reputation(env: &Env, address: &Address, old_reputation: u64, new_reputation: u64) {
+        Self::adjust_total_reputation(env, old_reputation, new_reputation);
+        let Some(index) = env
+            .storage()
+            .persistent()
+            .get::<_, u32>(&DataKey::ContributorIndex(address.clone()))
+        else {
+            return;
+        };
+
+        Self::leave_leaderboard(env, index, old_reputation);
+        Self::enter_leaderboard(env, index, new_reputation);
+
+        let suspended = env
+            .storage()
+            .persistent()
+            .get::<_, ContributorData>(&DataKey::Contributor(address.clone()))
+            .is_some_and(|contributor| contributor.suspended);
+        if !suspended {
+            let delta = new_reputation as i128 - old_reputation as i128;
+            Self::fenwick_add(
+                env,
+                DataKey::PickWeight,
+                index,
+                Self::contributor_count(env),
+                delta,
+            );
+        }
+    }
+
+    /// Move the running reputation total by a contributor's score change
+    fn adjust_total_reputation(env: &Env, old_reputation: u64, new_reputation: u64) {
+        let total: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalReputation)
+            .unwrap_or(0);
+        let total = if new_reputation >= old_reputation {
+            total.saturating_add(new_reputation - old_reputation)
+        } else {
+            total.saturating_sub(old_reputation - new_reputation)
+        };
         env.storage()
+            .instance()
+            .set(&DataKey::TotalReputation, &total);
+    }
+
+    /// Get the last `MAX_HISTORY` reputation changes of a contributor, oldest first
+    pub fn get_reputation_history(env: Env, contributor: Address) -> Vec<ReputationEntry> {
+        let len: u32 = env
+            .storage()
             .persistent()
-            .set(&DataKey::Contributor(contributor_address), &contributor);
+            .get(&DataKey::HistoryLen(contributor.clone()))
+            .unwrap_or(0);
+
+        let mut history = Vec::new(&env);
+        for i in len.saturating_sub(MAX_HISTORY)..len {
+            if let Some(entry) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::HistoryEntry(contributor.clone(), i % MAX_HISTORY))
+            {
+                history.push_back(entry);
+            }
+        }
+        history
+    }
 
+    /// Suspend a contributor so they cannot gain reputation (admin only)
+    ///
+    /// The profile is kept and stays readable. Emits [`ContributorSuspendedEvent`].
+    pub fn suspend_contributor(
+        env: Env,
+        admin: Address,
+        contributor: Address,
+    ) -> Result<(), ContributorError> {
+        Self::set_suspended(&env, &admin, contributor, true)?;
+        events::admin_action(&env, "suspend_contributor", &admin);
         Ok(())
     }
 
+    /// Lift a contributor's suspension (admin only)
+    ///
+    /// Emits [`ContributorSuspendedEvent`].
+    pub fn reinstate_contributor(
+        env: Env,
+        admin: Address,
+        contributor: Address,
+    ) -> Result<(), ContributorError> {
+        Self::set_suspended(&env, &admin, contributor, false)?;
+        events::admin_action(&env, "reinstate_contributor", &admin);
+        Ok(())
+    }
+
+    fn set_suspended(
+        env: &Env,
+        admin: &Address,
+        contributor_address: Address,
+        suspended: bool,
+    ) -> Result<(), ContributorError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContributorError::NotInitialized)?;
+        if *admin != stored_admin {
+            return Err(ContributorError::Unauthorized);
+        }
+        admin.require_auth();
+
+        let key = DataKey::Contributor(contributor_address.clone());
+        let mut contributor: ContributorData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(ContributorError::ContributorNotFound)?;
+        if contributor.suspended != suspended {
+            // Suspended contributors carry no pick weight
+            if let Some(index) = env
+                .storage()
+                .persistent()
+                .get::<_, u32>(&DataKey::ContributorIndex(contributor_address.clone()))
+            {
+                let score = contributor.reputation_score as i128;
+                let delta = if suspended { -score } else { score };
+                Self::fenwick_add(
+                    env,
+                    DataKey::PickWeight,
+                    index,
+                    Self::contributor_count(env),
+                    delta,
+                );
+            }
+        }
+        contributor.suspended = suspended;
+        env.storage().persistent().set(&key, &contributor);
+
+        ContributorSuspendedEvent {
+            contributor: contributor_address,
+            suspended,
+        }
+        .publish(env);
+        Ok(())
+    }
+
+    /// Set the minimum reputation a contributor needs to change their GitHub
+    /// handle (admin only, 0 = no requirement)
+    pub fn set_min_rep_for_handle_update(
+        env: Env,
+        admin: Address,
+        min_reputation: u64,
+    ) -> Result<(), ContributorError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContributorError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(ContributorError::Unauthorized);
+        }
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::MinRepForHandleUpdate, &min_reputation);
+        events::admin_action(&env, "set_min_rep_for_handle_update", &admin);
+        Ok(())
+    }
+
+    /// Get the minimum reputation required to change a GitHub handle
+    pub fn get_min_rep_for_handle_update(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinRepForHandleUpdate)
+            .unwrap_or(0)
+    }
+
     /// Get contributor reputation
     pub fn get_reputation(env: Env, contributor: Address) -> Result<u64, ContributorError> {
         let contributor_data: ContributorData = Self::get_contributor(env, contributor)?;
         Ok(contributor_data.reputation_score)
     }
 
+    /// Get the sum of reputation across all contributors
+    pub fn total_reputation(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalReputation)
+            .unwrap_or(0)
+    }
+
+    /// Get the 1-based rank of a contributor by reputation
+    ///
+    /// Ties are broken by registration order, earlier registrations first.
+    pub fn get_rank(env: Env, contributor: Address) -> Result<u32, ContributorError> {
+        let target = Self::get_contributor(env.clone(), contributor.clone())?;
+        let index: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ContributorIndex(contributor))
+            .ok_or(ContributorError::ContributorNotFound)?;
+        let score = target.reputation_score;
+        let positive = Self::fenwick_prefix(&env, DataKey::RankBucket, SCORE_BUCKETS);
+        if score == 0 {
+            // Behind every positive score, then in registration order
+            let earlier_positive = Self::fenwick_prefix(&env, DataKey::PositiveCount, index);
+            return Ok((positive + index as i128 - earlier_positive) as u32 + 1);
+        }
+
+        let bucket = Self::score_bucket(score);
+        let above = positive - Self::fenwick_prefix(&env, DataKey::RankBucket, bucket + 1);
+        let position = Self::rank_shard(&env, bucket)
+            .binary_search((u64::MAX - score, index))
+            .map_err(|_| ContributorError::ContributorNotFound)?;
+        Ok(above as u32 + position + 1)
+    }
+
+    /// Pick a contributor with probability proportional to reputation
+    ///
+    /// The result depends only on `seed` and the registry state, so anyone can
+    /// re-run a draw to verify it. Suspended contributors are never picked.
+    pub fn weighted_pick(env: Env, seed: u64) -> Result<Address, ContributorError> {
+        let count = Self::contributor_count(&env);
+        let total_weight = Self::fenwick_prefix(&env, DataKey::PickWeight, count);
+        if total_weight <= 0 {
+            return Err(ContributorError::NoEligibleContributors);
+        }
+
+        // Spread consecutive seeds across the range (splitmix64 finalizer)
+        let mut mixed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        mixed ^= mixed >> 31;
+
+        // Descend the weight tree to the first registration whose running
+        // weight exceeds the target
+        let mut target = (mixed as u128 % total_weight as u128) as i128;
+        let mut position: u32 = 0;
+        let mut step: u32 = 1 << (31 - count.leading_zeros());
+        while step > 0 {
+            let node = position + step;
+            if node <= count {
+                let weight: i128 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::PickWeight(node))
+                    .unwrap_or(0);
+                if weight <= target {
+                    position = node;
+                    target -= weight;
+                }
+            }
+            step >>= 1;
+        }
+        env.storage()
+            .persistent()
+            .get(&DataKey::ContributorAt(position))
+            .ok_or(ContributorError::NoEligibleContributors)
+    }
+
     /// Get contributor profile data
     pub fn get_contributor(
         env: Env,
@@ -177,6 +842,38 @@ impl ContributorRegistryContract {
             .ok_or(ContributorError::ContributorNotFound)
     }
 
+    /// Get a contributor's data together with their rank in one call
+    ///
+    /// An unregistered address gets a profile with `is_registered` false,
+    /// rank 0 and empty data.
+    pub fn get_profile(env: Env, address: Address) -> ContributorProfile {
+        match Self::get_contributor(env.clone(), address.clone()) {
+            Ok(contributor) => ContributorProfile {
+                rank: Self::get_rank(env, address).unwrap_or(0),
+                contributor,
+                is_registered: true,
+            },
+            Err(_) => ContributorProfile {
+                contributor: ContributorData {
+                    address,
+                    github_handle: String::from_str(&env, ""),
+                    reputation_score: 0,
+                    registered_timestamp: 0,
+                    suspended: false,
+                },
+                rank: 0,
+                is_registered: false,
+            },
+        }
+    }
+
+    /// Check whether an address is a registered contributor
+    pub fn is_registered(env: Env, address: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Contributor(address))
+    }
+
     /// Get contributor profile data by GitHub handle.
     pub fn get_contributor_by_github(
         env: Env,
@@ -190,6 +887,24 @@ impl ContributorRegistryContract {
         Self::get_contributor(env, contributor_address)
     }
 
+    /// Get a compact snapshot of the registry's global state
+    pub fn get_state(env: Env) -> Result<RegistryState, ContributorError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContributorError::NotInitialized)?;
+
+        Ok(RegistryState {
+            admin,
+            contributor_count: env
+                .storage()
+                .instance()
+                .get(&DataKey::ContributorCount)
+                .unwrap_or(0),
+        })
+    }
+
     /// Get admin address
     pub fn get_admin(env: Env) -> Result<Address, ContributorError> {
         env.storage()
@@ -198,6 +913,14 @@ impl ContributorRegistryContract {
             .ok_or(ContributorError::NotInitialized)
     }
 
+    /// Check whether `address` is the stored admin (false before initialization)
+    pub fn is_admin(env: Env, address: Address) -> bool {
+        env.storage()
+            .instance()
+            .get::<_, Address>(&DataKey::Admin)
+            .is_some_and(|admin| admin == address)
+    }
+
     /// Upgrade the contract WASM to a new hash.
     ///
     /// Only the stored admin may call this. Emits [`UpgradedEvent`] on success.
@@ -217,6 +940,7 @@ impl ContributorRegistryContract {
         caller.require_auth();
         env.deployer()
             .update_current_contract_wasm(new_wasm_hash.clone());
+        events::admin_action(&env, "upgrade", &caller);
         UpgradedEvent {
             admin: caller,
             new_wasm_hash,
@@ -225,9 +949,112 @@ impl ContributorRegistryContract {
         Ok(())
     }
 
+    /// Migrate storage to the current layout after an upgrade (admin only)
+    ///
+    /// Call after `upgrade` installs WASM with a newer storage version.
+    /// Storage before version 2 kept no index of contributors, so the admin
+    /// declares `legacy_count`, the number of records in the old layout, and
+    /// lists their addresses in `contributors`, in one call or in batches;
+    /// only the first batch records `legacy_count`. Listed records are
+    /// rewritten in the current layout and indexed, adding their scores to the
+    /// reputation total. The version is bumped once every legacy record is
+    /// migrated.
+    /// Rejects with `AlreadyMigrated` once storage is at [`STORAGE_VERSION`].
+    pub fn migrate(
+        env: Env,
+        admin: Address,
+        legacy_count: u32,
+        contributors: Vec<Address>,
+    ) -> Result<(), ContributorError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContributorError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(ContributorError::Unauthorized);
+        }
+        admin.require_auth();
+
+        let version: u32 = env.storage().instance().get(&DataKey::Version).unwrap_or(0);
+        if version >= STORAGE_VERSION {
+            return Err(ContributorError::AlreadyMigrated);
+        }
+
+        // Version 2 extended `ContributorData` and added the registration
+        // index and the reputation total
+        if version < 2 {
+            let remaining: u32 = env
+                .storage()
+                .instance()
+                .get(&DataKey::MigrationRemaining)
+                .unwrap_or(legacy_count);
+            let migrated = Self::migrate_contributors(&env, &contributors)?;
+            let remaining = remaining.saturating_sub(migrated);
+            if remaining > 0 {
+                env.storage()
+                    .instance()
+                    .set(&DataKey::MigrationRemaining, &remaining);
+                events::admin_action(&env, "migrate", &admin);
+                return Ok(());
+            }
+            env.storage()
+                .instance()
+                .remove(&DataKey::MigrationRemaining);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Version, &STORAGE_VERSION);
+
+        VersionBumpedEvent {
+            old_version: version,
+            new_version: STORAGE_VERSION,
+        }
+        .publish(&env);
+        events::admin_action(&env, "migrate", &admin);
+        Ok(())
+    }
+
+    /// Rewrite the listed contributors still stored as `ContributorDataV1` in
+    /// the current layout and index them, adding their scores to the total
+    /// Returns how many records were rewritten
+    fn migrate_contributors(
+        env: &Env,
+        contributors: &Vec<Address>,
+    ) -> Result<u32, ContributorError> {
+        let mut migrated = 0;
+        for address in contributors.iter() {
+            let key = DataKey::Contributor(address.clone());
+            let Some(stored) = env.storage().persistent().get::<_, Map<Symbol, Val>>(&key) else {
+                return Err(ContributorError::ContributorNotFound);
+            };
+            if stored.len() != CONTRIBUTOR_V1_FIELDS {
+                continue;
+            }
+            let legacy: ContributorDataV1 = env.storage().persistent().get(&key).unwrap();
+            let contributor = ContributorData {
+                address: legacy.address,
+                github_handle: legacy.github_handle,
+                reputation_score: legacy.reputation_score,
+                registered_timestamp: legacy.registered_timestamp,
+                suspended: false,
+            };
+            env.storage().persistent().set(&key, &contributor);
+            Self::index_contributor(env, &address, contributor.reputation_score);
+            migrated += 1;
+        }
+        Ok(migrated)
+    }
+
+    /// Get the storage layout version (0 for storage that predates versioning)
+    pub fn get_version(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Version).unwrap_or(0)
+    }
+
     /// Transfer the admin role to `new_admin`.
     ///
-    /// Requires authorization from the current admin. Emits [`AdminChangedEvent`].
+    /// Requires authorization from the current admin. Rejects a no-op transfer
+    /// to the current admin. Emits [`AdminChangedEvent`].
     pub fn set_admin(
         env: Env,
         current_admin: Address,
@@ -241,8 +1068,12 @@ impl ContributorRegistryContract {
         if current_admin != stored_admin {
             return Err(ContributorError::Unauthorized);
         }
+        if new_admin == current_admin {
+            return Err(ContributorError::SameAdmin);
+        }
         current_admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &new_admin);
+        events::admin_action(&env, "set_admin", &current_admin);
         AdminChangedEvent {
             old_admin: current_admin,
             new_admin,
@@ -268,10 +1099,16 @@ impl NotificationReceiverTrait for ContributorRegistryContract {
                 .persistent()
                 .get::<_, ContributorData>(&DataKey::Contributor(user.clone()))
             {
-                contributor.reputation_score = contributor.reputation_score.saturating_add(1);
+                // Suspended contributors do not gain reputation
+                if contributor.suspended {
+                    return;
+                }
+                let old_score = contributor.reputation_score;
+                contributor.reputation_score = old_score.saturating_add(1);
                 env.storage()
                     .persistent()
-                    .set(&DataKey::Contributor(user), &contributor);
+                    .set(&DataKey::Contributor(user.clone()), &contributor);
+                Self::reindex_reputation(&env, &user, old_score, contributor.reputation_score);
             }
         }
     }
@@ -3,9 +3,23 @@ use soroban_sdk::{contracttype, Address, String};
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
-    Admin,                // -> Address
-    Contributor(Address), // -> ContributorData
-    GitHubIndex(String),  // -> Address
+    Admin,                      // -> Address
+    Contributor(Address),       // -> ContributorData
+    GitHubIndex(String),        // -> Address
+    ContributorCount,           // -> u32
+    MinRepForHandleUpdate,      // -> u64
+    HistoryLen(Address),        // -> u32 (reputation changes ever recorded)
+    HistoryEntry(Address, u32), // (contributor, slot) -> ReputationEntry
+    ReputationSource(Address),  // -> bool (allowed to award reputation)
+    ContributorAt(u32),         // registration index -> Address
+    Version,                    // -> u32 (storage layout version)
+    TotalReputation,            // -> u64 (sum of all reputation scores)
+    ContributorIndex(Address),  // -> u32 (registration index)
+    PickWeight(u32),            // Fenwick node (1-based) -> i128 (summed pick weights)
+    RankBucket(u32), // Fenwick node (1-based) over score buckets -> i128 (positive scores)
+    RankShard(u32),  // score bucket -> Vec<(u64, u32)> (u64::MAX - score, registration index)
+    PositiveCount(u32), // Fenwick node (1-based) over registration order -> i128 (positive scores)
+    MigrationRemaining, // -> u32 (legacy contributor records not yet migrated)
 }
 
 #[contracttype]
@@ -15,4 +29,43 @@ pub struct ContributorData {
     pub github_handle: String,
     pub reputation_score: u64,
     pub registered_timestamp: u64,
+    pub suspended: bool,
+}
+
+/// `ContributorData` as stored before storage version 2, migrated by `migrate`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributorDataV1 {
+    pub address: Address,
+    pub github_handle: String,
+    pub reputation_score: u64,
+    pub registered_timestamp: u64,
+}
+
+/// Number of fields in a stored `ContributorDataV1`
+pub const CONTRIBUTOR_V1_FIELDS: u32 = 4;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributorProfile {
+    pub contributor: ContributorData, // zeroed apart from the address when unregistered
+    pub rank: u32,                    // 0 when unregistered
+    pub is_registered: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistryState {
+    pub admin: Address,
+    pub contributor_count: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationEntry {
+    pub old_reputation: u64,
+    pub new_reputation: u64,
+    pub timestamp: u64,
+    pub reference: Option<String>, // off-chain evidence such as a PR URL
+    pub correction: bool,          // set directly by the admin rather than by a delta
 }
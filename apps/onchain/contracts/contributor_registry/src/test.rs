@@ -1,6 +1,10 @@
 use crate::errors::ContributorError;
+use crate::events::{AdminActionEvent, ContributorRegisteredEvent, ReputationUpdatedEvent};
 use crate::{ContributorRegistryContract, ContributorRegistryContractClient};
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    vec, Address, Env, Event, String, Symbol, Val, Vec,
+};
 
 fn setup_test<'a>(env: &Env) -> (ContributorRegistryContractClient<'a>, Address, Address) {
     let admin = Address::generate(env);
@@ -13,6 +17,24 @@ fn setup_test<'a>(env: &Env) -> (ContributorRegistryContractClient<'a>, Address,
     (client, admin, contributor)
 }
 
+/// The [`AdminActionEvent`] an admin call is expected to end with
+fn admin_action_event(
+    env: &Env,
+    client: &ContributorRegistryContractClient,
+    action: &str,
+    admin: &Address,
+) -> (Address, Vec<Val>, Val) {
+    let expected = AdminActionEvent {
+        action: Symbol::new(env, action),
+        admin: admin.clone(),
+    };
+    (
+        client.address.clone(),
+        expected.topics(env),
+        expected.data(env),
+    )
+}
+
 #[test]
 fn test_initialize() {
     let env = Env::default();
@@ -189,6 +211,71 @@ fn test_update_contributor_clears_stale_github_index_entry() {
     assert_eq!(contributor2_data.address, contributor2);
 }
 
+#[test]
+fn test_batch_register_skips_existing() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, existing) = setup_test(&env);
+    client.initialize(&admin);
+    client.register_contributor(&existing, &String::from_str(&env, "existing"));
+
+    let new1 = Address::generate(&env);
+    let new2 = Address::generate(&env);
+    let unnamed = Address::generate(&env);
+    let entries = vec![
+        &env,
+        (existing.clone(), String::from_str(&env, "existing")),
+        (new1.clone(), String::from_str(&env, "new1")),
+        (unnamed.clone(), String::from_str(&env, "")),
+        (new2.clone(), String::from_str(&env, "new2")),
+    ];
+    assert_eq!(client.batch_register(&admin, &entries), 2);
+
+    // One registration event per contributor actually registered
+    let registered = [
+        (new1.clone(), String::from_str(&env, "new1")),
+        (new2.clone(), String::from_str(&env, "new2")),
+    ];
+    let emitted = registered.map(|(contributor, github_handle)| {
+        let expected = ContributorRegisteredEvent {
+            contributor,
+            github_handle,
+        };
+        (
+            client.address.clone(),
+            expected.topics(&env),
+            expected.data(&env),
+        )
+    });
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            emitted[0].clone(),
+            emitted[1].clone(),
+            admin_action_event(&env, &client, "batch_register", &admin)
+        ]
+    );
+    assert!(!client.is_registered(&unnamed));
+
+    assert_eq!(client.get_state().contributor_count, 3);
+    assert_eq!(
+        client.get_contributor(&new1).github_handle,
+        String::from_str(&env, "new1")
+    );
+    assert_eq!(
+        client
+            .get_contributor_by_github(&String::from_str(&env, "new2"))
+            .address,
+        new2
+    );
+
+    let stranger = Address::generate(&env);
+    let result = client.try_batch_register(&stranger, &entries);
+    assert_eq!(result, Err(Ok(ContributorError::Unauthorized)));
+}
+
 #[test]
 fn test_update_reputation() {
     let env = Env::default();
@@ -205,7 +292,7 @@ fn test_update_reputation() {
 
     // Update reputation
     let new_score: i64 = 100;
-    client.update_reputation(&admin, &contributor, &new_score);
+    client.update_reputation(&admin, &contributor, &new_score, &None);
 
     // Verify reputation updated
     let data = client.get_contributor(&contributor);
@@ -228,7 +315,7 @@ fn test_update_reputation_unauthorized() {
 
     // Non-admin tries to update reputation - should fail
     let non_admin = Address::generate(&env);
-    let result = client.try_update_reputation(&non_admin, &contributor, &100);
+    let result = client.try_update_reputation(&non_admin, &contributor, &100, &None);
     assert_eq!(result, Err(Ok(ContributorError::Unauthorized)));
 }
 
@@ -244,7 +331,7 @@ fn test_update_reputation_contributor_not_found() {
 
     // Try to update reputation for non-existent contributor - should fail
     let non_existent = Address::generate(&env);
-    let result = client.try_update_reputation(&admin, &non_existent, &100);
+    let result = client.try_update_reputation(&admin, &non_existent, &100, &None);
     assert_eq!(result, Err(Ok(ContributorError::ContributorNotFound)));
 }
 
@@ -286,9 +373,9 @@ fn test_multiple_contributors() {
     client.register_contributor(&contributor3, &handle3);
 
     // Update reputations
-    client.update_reputation(&admin, &contributor1, &50);
-    client.update_reputation(&admin, &contributor2, &75);
-    client.update_reputation(&admin, &contributor3, &100);
+    client.update_reputation(&admin, &contributor1, &50, &None);
+    client.update_reputation(&admin, &contributor2, &75, &None);
+    client.update_reputation(&admin, &contributor3, &100, &None);
 
     // Verify all contributors have correct data
     let data1 = client.get_contributor(&contributor1);
@@ -320,17 +407,17 @@ fn test_reputation_can_be_updated_multiple_times() {
     client.register_contributor(&contributor, &github_handle);
 
     // Update reputation multiple times
-    client.update_reputation(&admin, &contributor, &10);
+    client.update_reputation(&admin, &contributor, &10, &None);
     assert_eq!(client.get_contributor(&contributor).reputation_score, 10);
 
-    client.update_reputation(&admin, &contributor, &50);
+    client.update_reputation(&admin, &contributor, &50, &None);
     assert_eq!(client.get_contributor(&contributor).reputation_score, 60);
 
-    client.update_reputation(&admin, &contributor, &100);
+    client.update_reputation(&admin, &contributor, &100, &None);
     assert_eq!(client.get_contributor(&contributor).reputation_score, 160);
 
     // Can also decrease reputation
-    client.update_reputation(&admin, &contributor, &25);
+    client.update_reputation(&admin, &contributor, &25, &None);
     assert_eq!(client.get_contributor(&contributor).reputation_score, 185);
 }
 
@@ -349,17 +436,17 @@ fn test_reputation_can_be_updated_multiple_times_with_negative() {
     client.register_contributor(&contributor, &github_handle);
 
     // Update reputation multiple times
-    client.update_reputation(&admin, &contributor, &10);
+    client.update_reputation(&admin, &contributor, &10, &None);
     assert_eq!(client.get_contributor(&contributor).reputation_score, 10);
 
-    client.update_reputation(&admin, &contributor, &50);
+    client.update_reputation(&admin, &contributor, &50, &None);
     assert_eq!(client.get_contributor(&contributor).reputation_score, 60);
 
-    client.update_reputation(&admin, &contributor, &100);
+    client.update_reputation(&admin, &contributor, &100, &None);
     assert_eq!(client.get_contributor(&contributor).reputation_score, 160);
 
     // Can also decrease reputation
-    client.update_reputation(&admin, &contributor, &-25);
+    client.update_reputation(&admin, &contributor, &-25, &None);
     assert_eq!(client.get_contributor(&contributor).reputation_score, 135);
 }
 
@@ -378,13 +465,13 @@ fn test_reputation_can_be_updated_multiple_times_with_negative_check_under_flow(
     client.register_contributor(&contributor, &github_handle);
 
     // Update reputation multiple times
-    client.update_reputation(&admin, &contributor, &10);
+    client.update_reputation(&admin, &contributor, &10, &None);
     assert_eq!(client.get_contributor(&contributor).reputation_score, 10);
 
-    client.update_reputation(&admin, &contributor, &50);
+    client.update_reputation(&admin, &contributor, &50, &None);
     assert_eq!(client.get_contributor(&contributor).reputation_score, 60);
 
-    client.update_reputation(&admin, &contributor, &-100);
+    client.update_reputation(&admin, &contributor, &-100, &None);
     assert_eq!(client.get_contributor(&contributor).reputation_score, 0);
 }
 
@@ -403,16 +490,77 @@ fn test_reputation_get_reputation() {
     client.register_contributor(&contributor, &github_handle);
 
     // Update reputation multiple times
-    client.update_reputation(&admin, &contributor, &10);
+    client.update_reputation(&admin, &contributor, &10, &None);
     assert_eq!(client.get_reputation(&contributor), 10);
 
-    client.update_reputation(&admin, &contributor, &-20);
+    client.update_reputation(&admin, &contributor, &-20, &None);
     assert_eq!(client.get_reputation(&contributor), 0);
 
-    client.update_reputation(&admin, &contributor, &50);
+    client.update_reputation(&admin, &contributor, &50, &None);
     assert_eq!(client.get_reputation(&contributor), 50);
 }
 
+#[test]
+fn test_total_reputation_tracks_sum_of_scores() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, contributor1) = setup_test(&env);
+    let contributor2 = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_contributor(&contributor1, &String::from_str(&env, "user1"));
+    client.register_contributor(&contributor2, &String::from_str(&env, "user2"));
+    assert_eq!(client.total_reputation(), 0);
+
+    let sum = |client: &ContributorRegistryContractClient| {
+        client.get_reputation(&contributor1) + client.get_reputation(&contributor2)
+    };
+
+    client.update_reputation(&admin, &contributor1, &100, &None);
+    client.update_reputation(&admin, &contributor2, &40, &None);
+    assert_eq!(client.total_reputation(), 140);
+
+    client.update_reputation(&admin, &contributor1, &-30, &None);
+    assert_eq!(client.total_reputation(), sum(&client));
+
+    // A reduction clamped at zero only removes the score actually held
+    client.update_reputation(&admin, &contributor2, &-100, &None);
+    assert_eq!(client.total_reputation(), 70);
+    assert_eq!(client.total_reputation(), sum(&client));
+
+    client.set_reputation(&admin, &contributor2, &25);
+    assert_eq!(client.total_reputation(), sum(&client));
+}
+
+#[test]
+fn test_get_state_snapshot() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, contributor) = setup_test(&env);
+
+    // Uninitialized contract has no state
+    assert_eq!(
+        client.try_get_state(),
+        Err(Ok(ContributorError::NotInitialized))
+    );
+
+    client.initialize(&admin);
+
+    let state = client.get_state();
+    assert_eq!(state.admin, admin);
+    assert_eq!(state.contributor_count, 0);
+
+    // Mutate: register two contributors
+    client.register_contributor(&contributor, &String::from_str(&env, "first"));
+    let other = Address::generate(&env);
+    client.register_contributor(&other, &String::from_str(&env, "second"));
+
+    let state = client.get_state();
+    assert_eq!(state.admin, admin);
+    assert_eq!(state.contributor_count, 2);
+}
+
 // ---------------------------------------------------------------------------
 // Upgradeability tests
 // ---------------------------------------------------------------------------
@@ -435,6 +583,19 @@ fn test_set_admin_transfers_role() {
     );
 }
 
+#[test]
+fn test_set_admin_to_self_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _) = setup_test(&env);
+    client.initialize(&admin);
+
+    let result = client.try_set_admin(&admin, &admin);
+    assert_eq!(result, Err(Ok(crate::errors::ContributorError::SameAdmin)));
+    assert_eq!(client.get_admin(), admin);
+}
+
 #[test]
 fn test_only_admin_can_upgrade() {
     let env = Env::default();
@@ -471,3 +632,538 @@ fn test_old_admin_cannot_upgrade_after_rotation() {
         Err(Ok(crate::errors::ContributorError::Unauthorized))
     );
 }
+
+#[test]
+fn test_is_registered() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, contributor) = setup_test(&env);
+    let stranger = Address::generate(&env);
+
+    client.initialize(&admin);
+
+    // Not registered yet
+    assert!(!client.is_registered(&contributor));
+
+    client.register_contributor(&contributor, &String::from_str(&env, "testuser"));
+
+    assert!(client.is_registered(&contributor));
+    // Unrelated addresses stay unregistered
+    assert!(!client.is_registered(&stranger));
+}
+
+#[test]
+fn test_reputation_decrement_clamp_emits_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, contributor) = setup_test(&env);
+    client.initialize(&admin);
+    client.register_contributor(&contributor, &String::from_str(&env, "testuser"));
+    client.update_reputation(&admin, &contributor, &10, &None);
+
+    // Decrement below zero floors at 0
+    client.update_reputation(&admin, &contributor, &-25, &None);
+
+    // The event records the true pre and post values and flags the clamp
+    let expected = ReputationUpdatedEvent {
+        contributor: contributor.clone(),
+        old_reputation: 10,
+        new_reputation: 0,
+        clamped: true,
+        reference: None,
+        schema_version: 1,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                expected.topics(&env),
+                expected.data(&env)
+            ),
+            admin_action_event(&env, &client, "update_reputation", &admin)
+        ]
+    );
+    assert_eq!(client.get_reputation(&contributor), 0);
+}
+
+#[test]
+fn test_handle_update_requires_min_reputation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, contributor) = setup_test(&env);
+    client.initialize(&admin);
+    client.register_contributor(&contributor, &String::from_str(&env, "old_handle"));
+
+    client.set_min_rep_for_handle_update(&admin, &50);
+    assert_eq!(client.get_min_rep_for_handle_update(), 50);
+
+    // Low reputation is blocked
+    let new_handle = String::from_str(&env, "new_handle");
+    let result = client.try_update_contributor(&contributor, &new_handle);
+    assert_eq!(result, Err(Ok(ContributorError::InsufficientReputation)));
+
+    // Succeeds once the admin awards enough reputation
+    client.update_reputation(&admin, &contributor, &50, &None);
+    client.update_contributor(&contributor, &new_handle);
+    assert_eq!(
+        client.get_contributor(&contributor).github_handle,
+        new_handle
+    );
+}
+
+#[test]
+fn test_suspend_and_reinstate_contributor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, contributor) = setup_test(&env);
+    client.initialize(&admin);
+    client.register_contributor(&contributor, &String::from_str(&env, "testuser"));
+    client.update_reputation(&admin, &contributor, &10, &None);
+
+    client.suspend_contributor(&admin, &contributor);
+
+    // Views still work and show the flag
+    let data = client.get_contributor(&contributor);
+    assert!(data.suspended);
+    assert_eq!(data.reputation_score, 10);
+
+    // Reputation is frozen while suspended
+    let result = client.try_update_reputation(&admin, &contributor, &5, &None);
+    assert_eq!(result, Err(Ok(ContributorError::ContributorSuspended)));
+
+    client.reinstate_contributor(&admin, &contributor);
+    assert!(!client.get_contributor(&contributor).suspended);
+
+    client.update_reputation(&admin, &contributor, &5, &None);
+    assert_eq!(client.get_reputation(&contributor), 15);
+
+    // Admin only
+    let result = client.try_suspend_contributor(&contributor, &contributor);
+    assert_eq!(result, Err(Ok(ContributorError::Unauthorized)));
+}
+
+#[test]
+fn test_reputation_reference_recorded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, contributor) = setup_test(&env);
+    client.initialize(&admin);
+    client.register_contributor(&contributor, &String::from_str(&env, "testuser"));
+
+    let reference = String::from_str(&env, "https://github.com/org/repo/pull/42");
+    client.update_reputation(&admin, &contributor, &10, &Some(reference.clone()));
+
+    // The event carries the reference
+    let expected = ReputationUpdatedEvent {
+        contributor: contributor.clone(),
+        old_reputation: 0,
+        new_reputation: 10,
+        clamped: false,
+        reference: Some(reference.clone()),
+        schema_version: 1,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                expected.topics(&env),
+                expected.data(&env)
+            ),
+            admin_action_event(&env, &client, "update_reputation", &admin)
+        ]
+    );
+
+    // The history entry stores it
+    client.update_reputation(&admin, &contributor, &5, &None);
+    let history = client.get_reputation_history(&contributor);
+    assert_eq!(history.len(), 2);
+    let first = history.get(0).unwrap();
+    assert_eq!((first.old_reputation, first.new_reputation), (0, 10));
+    assert_eq!(first.reference, Some(reference));
+    assert_eq!(history.get(1).unwrap().reference, None);
+
+    // Overly long references are rejected
+    let long_reference = String::from_bytes(&env, &[b'a'; 257]);
+    let result = client.try_update_reputation(&admin, &contributor, &1, &Some(long_reference));
+    assert_eq!(result, Err(Ok(ContributorError::ReferenceTooLong)));
+}
+
+#[test]
+fn test_reputation_history_keeps_most_recent_changes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, contributor) = setup_test(&env);
+    client.initialize(&admin);
+    client.register_contributor(&contributor, &String::from_str(&env, "busy-dev"));
+
+    for _ in 0..105 {
+        client.update_reputation(&admin, &contributor, &1, &None);
+    }
+
+    // Only the last 100 changes are kept, oldest first
+    let history = client.get_reputation_history(&contributor);
+    assert_eq!(history.len(), 100);
+    let first = history.first().unwrap();
+    assert_eq!((first.old_reputation, first.new_reputation), (5, 6));
+    let last = history.last().unwrap();
+    assert_eq!((last.old_reputation, last.new_reputation), (104, 105));
+}
+
+#[test]
+fn test_get_rank() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, first) = setup_test(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    let tied = Address::generate(&env);
+    client.initialize(&admin);
+
+    client.register_contributor(&first, &String::from_str(&env, "first"));
+    client.register_contributor(&second, &String::from_str(&env, "second"));
+    client.register_contributor(&third, &String::from_str(&env, "third"));
+
+    client.update_reputation(&admin, &first, &10, &None);
+    client.update_reputation(&admin, &second, &30, &None);
+    client.update_reputation(&admin, &third, &20, &None);
+
+    assert_eq!(client.get_rank(&second), 1);
+    assert_eq!(client.get_rank(&third), 2);
+    assert_eq!(client.get_rank(&first), 3);
+
+    // A later registration with an equal score ranks behind
+    client.register_contributor(&tied, &String::from_str(&env, "tied"));
+    client.update_reputation(&admin, &tied, &20, &None);
+    assert_eq!(client.get_rank(&third), 2);
+    assert_eq!(client.get_rank(&tied), 3);
+    assert_eq!(client.get_rank(&first), 4);
+
+    let result = client.try_get_rank(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(ContributorError::ContributorNotFound)));
+}
+
+#[test]
+fn test_get_profile_matches_getters() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, contributor) = setup_test(&env);
+    let leader = Address::generate(&env);
+    client.initialize(&admin);
+
+    client.register_contributor(&leader, &String::from_str(&env, "leader"));
+    client.register_contributor(&contributor, &String::from_str(&env, "contrib"));
+    client.update_reputation(&admin, &leader, &50, &None);
+    client.update_reputation(&admin, &contributor, &20, &None);
+    client.suspend_contributor(&admin, &contributor);
+
+    let profile = client.get_profile(&contributor);
+    assert_eq!(profile.contributor, client.get_contributor(&contributor));
+    assert_eq!(profile.rank, client.get_rank(&contributor));
+    assert_eq!(profile.rank, 2);
+    assert!(profile.contributor.suspended);
+    assert_eq!(profile.is_registered, client.is_registered(&contributor));
+    assert!(profile.is_registered);
+
+    let stranger = Address::generate(&env);
+    let profile = client.get_profile(&stranger);
+    assert_eq!(profile.contributor.address, stranger);
+    assert_eq!(profile.contributor.reputation_score, 0);
+    assert_eq!(profile.rank, 0);
+    assert_eq!(profile.is_registered, client.is_registered(&stranger));
+    assert!(!profile.is_registered);
+}
+
+#[test]
+fn test_weighted_pick_follows_reputation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, low) = setup_test(&env);
+    let high = Address::generate(&env);
+    client.initialize(&admin);
+
+    // Nobody has reputation yet
+    let result = client.try_weighted_pick(&0);
+    assert_eq!(result, Err(Ok(ContributorError::NoEligibleContributors)));
+
+    client.register_contributor(&low, &String::from_str(&env, "low"));
+    client.register_contributor(&high, &String::from_str(&env, "high"));
+    client.update_reputation(&admin, &low, &1, &None);
+    client.update_reputation(&admin, &high, &9, &None);
+
+    // Same seed, same state, same pick
+    assert_eq!(client.weighted_pick(&42), client.weighted_pick(&42));
+
+    let draws = 500;
+    let high_picks = (0..draws)
+        .filter(|seed| client.weighted_pick(seed) == high)
+        .count();
+    assert!((420..=480).contains(&high_picks), "{high_picks}");
+}
+
+#[test]
+fn test_rank_and_pick_track_changes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _) = setup_test(&env);
+    client.initialize(&admin);
+
+    let scores = [5u64, 0, 12, 7, 3, 12, 1];
+    let handles = ["a", "b", "c", "d", "e", "f", "g"];
+    let contributors = [(); 7].map(|_| Address::generate(&env));
+    for (i, score) in scores.iter().enumerate() {
+        let contributor = contributors[i].clone();
+        client.register_contributor(&contributor, &String::from_str(&env, handles[i]));
+        if *score > 0 {
+            client.update_reputation(&admin, &contributor, &(*score as i64), &None);
+        }
+    }
+    client.suspend_contributor(&admin, &contributors[2]);
+    client.set_reputation(&admin, &contributors[5], &2);
+    client.suspend_contributor(&admin, &contributors[4]);
+    client.reinstate_contributor(&admin, &contributors[4]);
+
+    // Reference: first registration whose running weight exceeds the target
+    let weights = [5u64, 0, 0, 7, 3, 2, 1];
+    let total: u64 = weights.iter().sum();
+    for seed in 0..50u64 {
+        let mut mixed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        mixed ^= mixed >> 31;
+        let mut target = mixed % total;
+        let expected = weights
+            .iter()
+            .position(|weight| {
+                if target < *weight {
+                    return true;
+                }
+                target -= weight;
+                false
+            })
+            .unwrap();
+        assert_eq!(client.weighted_pick(&seed), contributors[expected]);
+    }
+
+    // Suspension keeps the score on the leaderboard
+    let ranks = contributors.each_ref().map(|c| client.get_rank(c));
+    assert_eq!(ranks, [3, 7, 1, 2, 4, 5, 6]);
+}
+
+#[test]
+fn test_rank_across_score_shards() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _) = setup_test(&env);
+    client.initialize(&admin);
+
+    // Exact, shared and far-apart shards, with zero scores in between
+    let mut scores = [0u64, 70, 0, 71, 1_000_000, 64, 127, 5, 0, 1 << 40];
+    let handles = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"];
+    let contributors = [(); 10].map(|_| Address::generate(&env));
+    for (i, score) in scores.iter().enumerate() {
+        client.register_contributor(&contributors[i], &String::from_str(&env, handles[i]));
+        client.set_reputation(&admin, &contributors[i], score);
+    }
+    // Scores move within and across shards, and back to zero
+    client.set_reputation(&admin, &contributors[3], &70);
+    client.set_reputation(&admin, &contributors[4], &0);
+    client.set_reputation(&admin, &contributors[2], &(1 << 40));
+    scores[3] = 70;
+    scores[4] = 0;
+    scores[2] = 1 << 40;
+
+    // Reference: higher scores first, then earlier registrations
+    for (i, score) in scores.iter().enumerate() {
+        let ahead = scores
+            .iter()
+            .enumerate()
+            .filter(|(j, other)| *other > score || (*other == score && *j < i))
+            .count() as u32;
+        assert_eq!(client.get_rank(&contributors[i]), ahead + 1);
+    }
+}
+
+#[test]
+fn test_admin_actions_share_audit_topic() {
+    use crate::storage::DataKey;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, contributor) = setup_test(&env);
+    client.initialize(&admin);
+    client.register_contributor(&contributor, &String::from_str(&env, "testuser"));
+    let new_admin = Address::generate(&env);
+
+    let assert_emitted = |action: &str| {
+        let (_, expected, _) = admin_action_event(&env, &client, action, &admin);
+        assert!(env
+            .events()
+            .all()
+            .iter()
+            .any(|(_, topics, _)| topics == expected));
+    };
+
+    env.as_contract(&client.address, || {
+        env.storage().instance().remove(&DataKey::Version);
+    });
+    client.migrate(&admin, &0, &vec![&env]);
+    assert_emitted("migrate");
+
+    client.suspend_contributor(&admin, &contributor);
+    assert_emitted("suspend_contributor");
+    client.reinstate_contributor(&admin, &contributor);
+    assert_emitted("reinstate_contributor");
+    client.set_reputation_source(&admin, &new_admin, &true);
+    assert_emitted("set_reputation_source");
+    client.set_min_rep_for_handle_update(&admin, &5);
+    assert_emitted("set_min_rep_for_handle_update");
+
+    client.set_admin(&admin, &new_admin);
+    assert_emitted("set_admin");
+}
+
+#[test]
+fn test_migrate_runs_once_per_version() {
+    use crate::events::VersionBumpedEvent;
+    use crate::storage::{ContributorDataV1, DataKey};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, contributor) = setup_test(&env);
+    client.initialize(&admin);
+
+    assert_eq!(client.get_version(), 2);
+
+    // Simulate storage written by a build that predates versioning: two
+    // contributor records in the old layout and no index or total
+    let other = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        for (address, handle, reputation_score) in
+            [(&contributor, "legacy-dev", 40), (&other, "legacy-two", 10)]
+        {
+            let handle = String::from_str(&env, handle);
+            env.storage().persistent().set(
+                &DataKey::Contributor(address.clone()),
+                &ContributorDataV1 {
+                    address: address.clone(),
+                    github_handle: handle.clone(),
+                    reputation_score,
+                    registered_timestamp: 0,
+                },
+            );
+            env.storage()
+                .persistent()
+                .set(&DataKey::GitHubIndex(handle), address);
+        }
+        env.storage().instance().remove(&DataKey::Version);
+    });
+
+    // Listed addresses must be registered
+    let result = client.try_migrate(&admin, &2, &vec![&env, Address::generate(&env)]);
+    assert_eq!(result, Err(Ok(ContributorError::ContributorNotFound)));
+
+    // The version holds until every legacy record is migrated
+    client.migrate(&admin, &2, &vec![&env, other.clone()]);
+    assert_eq!(client.get_version(), 0);
+
+    client.migrate(&admin, &2, &vec![&env, contributor.clone()]);
+    let expected = VersionBumpedEvent {
+        old_version: 0,
+        new_version: 2,
+    };
+    let events = env.events().all();
+    let emitted = events
+        .iter()
+        .find(|(_, topics, _)| *topics == expected.topics(&env))
+        .unwrap();
+    assert_eq!(vec![&env, emitted.2], vec![&env, expected.data(&env)]);
+    assert_eq!(client.get_version(), 2);
+
+    // The record reads in the new layout and is indexed
+    let migrated = client.get_contributor(&contributor);
+    assert_eq!(migrated.reputation_score, 40);
+    assert!(!migrated.suspended);
+    assert_eq!(client.get_state().contributor_count, 2);
+    assert_eq!(client.total_reputation(), 50);
+    assert_eq!(client.get_rank(&contributor), 1);
+    assert_eq!(client.get_rank(&other), 2);
+
+    let result = client.try_migrate(&admin, &0, &vec![&env]);
+    assert_eq!(result, Err(Ok(ContributorError::AlreadyMigrated)));
+}
+
+#[test]
+fn test_is_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, contributor) = setup_test(&env);
+    assert!(!client.is_admin(&admin));
+
+    client.initialize(&admin);
+    assert!(client.is_admin(&admin));
+    assert!(!client.is_admin(&contributor));
+}
+
+#[test]
+fn test_set_reputation_overrides_deltas() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, contributor) = setup_test(&env);
+    client.initialize(&admin);
+    client.register_contributor(&contributor, &String::from_str(&env, "testuser"));
+
+    client.update_reputation(&admin, &contributor, &40, &None);
+    client.update_reputation(&admin, &contributor, &-15, &None);
+    client.set_reputation(&admin, &contributor, &100);
+
+    // The event records the old and new values
+    let expected = ReputationUpdatedEvent {
+        contributor: contributor.clone(),
+        old_reputation: 25,
+        new_reputation: 100,
+        clamped: false,
+        reference: None,
+        schema_version: 1,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                expected.topics(&env),
+                expected.data(&env)
+            ),
+            admin_action_event(&env, &client, "set_reputation", &admin)
+        ]
+    );
+
+    assert_eq!(client.get_reputation(&contributor), 100);
+    let history = client.get_reputation_history(&contributor);
+    let last = history.last().unwrap();
+    assert_eq!((last.old_reputation, last.new_reputation), (25, 100));
+    assert!(last.correction);
+    assert!(!history.first().unwrap().correction);
+
+    let result = client.try_set_reputation(&contributor, &contributor, &1_000);
+    assert_eq!(result, Err(Ok(ContributorError::Unauthorized)));
+}
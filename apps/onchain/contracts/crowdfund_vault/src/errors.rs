@@ -24,4 +24,31 @@ pub enum CrowdfundError {
     AlreadyVoted = 18,
     InsufficientContributionToVote = 19,
     MilestoneAlreadyApproved = 20,
+    InvalidFee = 21,
+    ProjectLimitReached = 22,
+    ProjectNotPending = 23,
+    InvalidDeadline = 24,
+    DeadlinePassed = 25,
+    NameTaken = 26,
+    AlreadyWithdrawn = 27,
+    AlreadyMigrated = 28,
+    TokenNotAllowed = 29,
+    InvalidRoyalty = 30,
+    MemoTooLong = 31,
+    OwnerNotRegistered = 32,
+    NotWhitelisted = 33,
+    ApprovalCooldownActive = 34,
+    ZeroAmount = 35,
+    TransferFailed = 36,
+    DepositsPaused = 37,
+    DepositLocked = 38,
+    TargetTooLow = 39,
+    CannotReduceRelease = 40,
+    VoteInProgress = 41,
+    SelfTransfer = 42,
+    ProjectFailed = 43,
+    OracleUnavailable = 44,
+    PriceOverflow = 45,
+    VoteNotPassed = 46,
+    WithdrawalDelayActive = 47,
 }
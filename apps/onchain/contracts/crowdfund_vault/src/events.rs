@@ -1,4 +1,57 @@
-use soroban_sdk::{contractevent, Address};
+use soroban_sdk::{contractevent, Address, Env, Event, String, Symbol};
+
+use crate::storage::DataKey;
+
+/// Publishes events only while event emission is enabled for the vault.
+/// Deployers that index via state reads can switch emission off to save fees.
+/// Security-relevant events (admin actions, admin changes, pauses and
+/// upgrades) are published directly instead and cannot be switched off.
+pub trait Emit {
+    fn emit(&self, env: &Env);
+}
+
+impl<E: Event> Emit for E {
+    fn emit(&self, env: &Env) {
+        let enabled: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::EmitEvents)
+            .unwrap_or(true);
+        if enabled {
+            self.publish(env);
+        }
+    }
+}
+
+/// Emitted by every privileged entry point. All such events share the
+/// `admin_action` topic so an indexer can follow admin activity with one
+/// filter; `action` names the entry point.
+#[contractevent(topics = ["admin_action"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminActionEvent {
+    #[topic]
+    pub action: Symbol,
+    #[topic]
+    pub admin: Address,
+}
+
+/// Publish an [`AdminActionEvent`] for a privileged call
+///
+/// Always published, regardless of `set_emit_events`, so the audit trail
+/// cannot be switched off by the admin it records.
+pub fn admin_action(env: &Env, action: &str, admin: &Address) {
+    AdminActionEvent {
+        action: Symbol::new(env, action),
+        admin: admin.clone(),
+    }
+    .publish(env);
+}
+
+/// Schema version carried by versioned events; bump when their fields change
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// Schema version of [`DepositEvent`]; 2 added `memo`
+pub const DEPOSIT_EVENT_SCHEMA_VERSION: u32 = 2;
 
 #[contractevent]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -16,6 +69,11 @@ pub struct ProjectCreatedEvent {
     pub project_id: u64,
 }
 
+/// Emitted on every deposit. `user` is the address credited with the
+/// contribution and `sponsor` is the address the tokens were pulled from;
+/// they are equal for a regular `deposit`. `amount` is the net credited to
+/// the project after the deposit `fee`. `memo` is the caller's reference from
+/// `deposit_with_memo` and is not stored on-chain.
 #[contractevent]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DepositEvent {
@@ -24,6 +82,19 @@ pub struct DepositEvent {
     #[topic]
     pub project_id: u64,
     pub amount: i128,
+    pub sponsor: Address,
+    pub fee: i128,
+    pub memo: Option<String>,
+    pub schema_version: u32,
+}
+
+/// Emitted when the platform matches a deposit from the match pool.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositMatchedEvent {
+    #[topic]
+    pub project_id: u64,
+    pub amount: i128,
 }
 
 #[contractevent]
@@ -34,6 +105,27 @@ pub struct MilestoneApprovedEvent {
     pub project_id: u64,
 }
 
+/// Emitted when the admin raises the share of a project's deposits released
+/// to the owner
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneReleaseEvent {
+    #[topic]
+    pub admin: Address,
+    pub project_id: u64,
+    pub milestone_id: u32,
+    pub release_bps: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneRevokedEvent {
+    #[topic]
+    pub admin: Address,
+    pub project_id: u64,
+    pub milestone_id: u32,
+}
+
 #[contractevent]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct WithdrawEvent {
@@ -42,6 +134,7 @@ pub struct WithdrawEvent {
     #[topic]
     pub project_id: u64,
     pub amount: i128,
+    pub schema_version: u32,
 }
 
 #[contractevent]
@@ -57,6 +150,17 @@ pub struct ReputationUpdatedEvent {
     pub contributor: Address,
     pub old_reputation: i128,
     pub new_reputation: i128,
+    pub schema_version: u32,
+}
+
+/// Emitted when deposits alone are paused or resumed
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositPauseEvent {
+    #[topic]
+    pub admin: Address,
+    pub paused: bool,
+    pub timestamp: u64,
 }
 
 #[contractevent]
@@ -93,17 +197,44 @@ pub struct AdminChangedEvent {
     pub new_admin: Address,
 }
 
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectActivatedEvent {
+    #[topic]
+    pub project_id: u64,
+    pub owner: Address,
+}
+
 #[contractevent]
 pub struct ProjectCanceledEvent {
     pub project_id: u64,
     pub caller: Address,
 }
 
+/// Emitted when a funded project is archived after its balance is fully withdrawn
+#[contractevent]
+pub struct ProjectArchivedEvent {
+    pub project_id: u64,
+}
+
+/// Emitted per refunded contributor. `amount` is the net returned after the
+/// refund `fee`.
 #[contractevent]
 pub struct ContributionRefundedEvent {
     pub project_id: u64,
     pub contributor: Address,
     pub amount: i128,
+    pub fee: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributionTransferredEvent {
+    #[topic]
+    pub project_id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
 }
 
 #[contractevent]
@@ -133,3 +264,11 @@ pub struct MilestoneApprovedByVoteEvent {
     pub project_id: u64,
     pub milestone_id: u32,
 }
+
+/// Emitted when `migrate` moves storage to a new layout version.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VersionBumpedEvent {
+    pub old_version: u32,
+    pub new_version: u32,
+}
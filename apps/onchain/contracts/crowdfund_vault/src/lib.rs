@@ -3,16 +3,43 @@
 mod errors;
 mod events;
 mod math;
+pub mod oracle;
+pub mod registry;
 mod storage;
 mod token;
+pub mod treasury;
 
 use errors::CrowdfundError;
+use events::Emit;
 use math::{sqrt_scaled, unscale};
 use notification_interface::{Notification, NotificationReceiverClient};
+use oracle::{PriceOracleClient, PRICE_DECIMALS};
+use registry::ContributorRegistryClient;
 use soroban_sdk::token::TokenClient;
 use soroban_sdk::xdr::ToXdr;
-use soroban_sdk::{contract, contractimpl, vec, Address, BytesN, Env, Symbol, Vec};
-use storage::{DataKey, ProjectData};
+use soroban_sdk::{
+    contract, contractimpl, vec, Address, BytesN, Env, Map, String, Symbol, Val, Vec,
+};
+use storage::{DataKey, DeadlineStatus, ProjectData, ProjectDataV1, VaultState, PROJECT_V1_FIELDS};
+use treasury::FeeTreasuryClient;
+
+/// Storage version this build migrates to; bump alongside a `migrate` step
+const STORAGE_VERSION: u32 = 2;
+
+/// Maximum platform fee in basis points (10%)
+const MAX_FEE_BPS: u32 = 1_000;
+
+/// Basis point denominator (100%)
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Maximum project royalty in basis points (50%)
+const MAX_ROYALTY_BPS: u32 = 5_000;
+
+/// Maximum length of a deposit memo
+const MAX_MEMO_LEN: u32 = 128;
+
+/// Common precision that vote weights are normalized to
+const NORMALIZED_DECIMALS: u32 = 18;
 
 #[contract]
 pub struct CrowdfundVaultContract;
@@ -36,8 +63,8 @@ impl CrowdfundVaultContract {
         Ok(())
     }
 
-    /// Initialize the contract with an admin address
-    pub fn initialize(env: Env, admin: Address) -> Result<(), CrowdfundError> {
+    /// Shared setup for `initialize` and `initialize_with_config`
+    fn init_admin(env: &Env, admin: &Address) -> Result<(), CrowdfundError> {
         // Check if already initialized
         if env.storage().instance().has(&DataKey::Admin) {
             return Err(CrowdfundError::AlreadyInitialized);
@@ -47,16 +74,63 @@ impl CrowdfundVaultContract {
         admin.require_auth();
 
         // Store admin address
-        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Admin, admin);
 
         // Store Emergency Pause bool
         env.storage().instance().set(&DataKey::Paused, &false);
 
+        // Deployments start at version 1; each `migrate` bumps it by one
+        env.storage().instance().set(&DataKey::Version, &1u32);
+
         // Initialize project ID counter
         env.storage().instance().set(&DataKey::NextProjectId, &0u64);
 
+        Ok(())
+    }
+
+    /// Initialize the contract with an admin address
+    pub fn initialize(env: Env, admin: Address) -> Result<(), CrowdfundError> {
+        Self::init_admin(&env, &admin)?;
+
+        // Emit initialization event
+        events::InitializedEvent { admin }.emit(&env);
+
+        Ok(())
+    }
+
+    /// Initialize the contract with an admin address, withdrawal and deposit fee
+    /// parameters and an optional contributor registry in a single call
+    pub fn initialize_with_config(
+        env: Env,
+        admin: Address,
+        fee_bps: u32,
+        deposit_fee_bps: u32,
+        fee_recipient: Address,
+        registry: Option<Address>,
+    ) -> Result<(), CrowdfundError> {
+        Self::init_admin(&env, &admin)?;
+
+        // A rejected fee fails the call, so nothing stored above persists
+        if fee_bps > MAX_FEE_BPS || deposit_fee_bps > MAX_FEE_BPS {
+            return Err(CrowdfundError::InvalidFee);
+        }
+
+        // Store fee configuration
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+        env.storage()
+            .instance()
+            .set(&DataKey::DepositFeeBps, &deposit_fee_bps);
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeRecipient, &fee_recipient);
+
+        // Store registry reference if provided
+        if let Some(registry) = registry {
+            env.storage().instance().set(&DataKey::Registry, &registry);
+        }
+
         // Emit initialization event
-        events::InitializedEvent { admin }.publish(&env);
+        events::InitializedEvent { admin }.emit(&env);
 
         Ok(())
     }
@@ -88,9 +162,67 @@ impl CrowdfundVaultContract {
         }
 
         // Validate target amount
-        if target_amount <= 0 {
+        if target_amount == 0 {
+            return Err(CrowdfundError::ZeroAmount);
+        }
+        if target_amount < 0 {
             return Err(CrowdfundError::InvalidAmount);
         }
+        if target_amount < Self::get_min_target(env.clone()) {
+            return Err(CrowdfundError::TargetTooLow);
+        }
+
+        // With a registry linked, only registered contributors may own projects
+        if let Some(registry) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::Registry)
+        {
+            if !ContributorRegistryClient::new(&env, &registry).is_registered(&owner) {
+                return Err(CrowdfundError::OwnerNotRegistered);
+            }
+        }
+
+        // Restrict funding tokens once an allowlist is configured
+        if !Self::is_token_allowed(env.clone(), token_address.clone()) {
+            return Err(CrowdfundError::TokenNotAllowed);
+        }
+
+        // Project names are unique handles
+        let name_key = DataKey::ProjectByName(name.clone());
+        if env.storage().persistent().has(&name_key) {
+            return Err(CrowdfundError::NameTaken);
+        }
+
+        // Enforce the per-owner cap on active projects
+        let owner_projects_key = DataKey::OwnerProjects(owner.clone());
+        let mut owner_projects: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&owner_projects_key)
+            .unwrap_or(vec![&env]);
+        let max_projects: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxProjectsPerOwner)
+            .unwrap_or(0);
+        if max_projects > 0 {
+            let mut active_projects = 0u32;
+            for id in owner_projects.iter() {
+                if let Some(existing) = env
+                    .storage()
+                    .persistent()
+                    .get::<_, ProjectData>(&DataKey::Project(id))
+                {
+                    if existing.is_active || Self::is_pending(&env, id) {
+                        active_projects += 1;
+                    }
+                }
+            }
+            if active_projects >= max_projects {
+                return Err(CrowdfundError::ProjectLimitReached);
+            }
+        }
 
         // Get next project ID
         let project_id: u64 = env
@@ -99,6 +231,14 @@ impl CrowdfundVaultContract {
             .get(&DataKey::NextProjectId)
             .unwrap_or(0);
 
+        // Targets are priced in the reference unit configured at creation
+        let reference_denom: Option<Symbol> = if env.storage().instance().has(&DataKey::PriceOracle)
+        {
+            env.storage().instance().get(&DataKey::ReferenceDenom)
+        } else {
+            None
+        };
+
         // Create project data (avoid unnecessary clones)
         let project = ProjectData {
             id: project_id,
@@ -108,13 +248,35 @@ impl CrowdfundVaultContract {
             token_address: token_address.clone(),
             total_deposited: 0,
             total_withdrawn: 0,
-            is_active: true,
+            is_active: false,
+            deadline: 0,
+            deposit_count: 0,
+            unique_contributors: 0,
+            royalty_bps: 0,
+            royalty_recipient: None,
+            private: false,
+            grace_period: 0,
+            deposit_lockup: 0,
+            release_bps: 0,
+            vote_open_until: 0,
+            reference_denom,
+            outcome: DeadlineStatus::Open,
+            withdrawal_delay: 0,
+            milestone_count: 1,
+            release_milestone: 0,
         };
 
         // Store project
         env.storage()
             .persistent()
             .set(&DataKey::Project(project_id), &project);
+        env.storage().persistent().set(&name_key, &project_id);
+
+        // Project stays pending until the owner activates it
+        env.storage().persistent().set(
+            &DataKey::ProjectStatus(project_id),
+            &Symbol::new(&env, "PENDING"),
+        );
 
         // Initialize project balance (construct key once)
         let balance_key = DataKey::ProjectBalance(project_id, token_address.clone());
@@ -125,6 +287,12 @@ impl CrowdfundVaultContract {
             .persistent()
             .set(&DataKey::MilestoneApproved(project_id, 0), &false);
 
+        // Record project in the owner's index
+        owner_projects.push_back(project_id);
+        env.storage()
+            .persistent()
+            .set(&owner_projects_key, &owner_projects);
+
         // Increment project ID counter
         env.storage()
             .instance()
@@ -136,514 +304,829 @@ impl CrowdfundVaultContract {
             token_address,
             project_id,
         }
-        .publish(&env);
+        .emit(&env);
 
         Ok(project_id)
     }
 
-    /// Cancel project (owner or admin only)
-    pub fn cancel_project(
+    /// Check whether a project is still awaiting owner activation
+    fn is_pending(env: &Env, project_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get::<_, Symbol>(&DataKey::ProjectStatus(project_id))
+            .is_some_and(|status| status == Symbol::new(env, "PENDING"))
+    }
+
+    /// Activate a pending project so it can accept deposits (owner only)
+    pub fn activate_project(
         env: Env,
-        caller: Address,
+        owner: Address,
         project_id: u64,
     ) -> Result<(), CrowdfundError> {
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(CrowdfundError::NotInitialized)?;
-
         let mut project: ProjectData = env
             .storage()
             .persistent()
             .get(&DataKey::Project(project_id))
             .ok_or(CrowdfundError::ProjectNotFound)?;
 
-        let is_admin = caller == stored_admin;
-        let is_owner = caller == project.owner;
-
-        if !is_admin && !is_owner {
+        if owner != project.owner {
             return Err(CrowdfundError::Unauthorized);
         }
 
-        caller.require_auth();
+        // Require owner authorization
+        owner.require_auth();
 
-        if !project.is_active {
-            return Err(CrowdfundError::ProjectNotActive);
+        if !Self::is_pending(&env, project_id) {
+            return Err(CrowdfundError::ProjectNotPending);
         }
 
-        // Mark as canceled
-        project.is_active = false;
+        project.is_active = true;
         env.storage()
             .persistent()
             .set(&DataKey::Project(project_id), &project);
-
         env.storage().persistent().set(
             &DataKey::ProjectStatus(project_id),
-            &Symbol::new(&env, "CANCELED"),
+            &Symbol::new(&env, "ACTIVE"),
         );
 
-        events::ProjectCanceledEvent { project_id, caller }.publish(&env);
+        events::ProjectActivatedEvent { project_id, owner }.emit(&env);
 
         Ok(())
     }
 
-    /// Refund all contributors (anyone can call after cancel, but usually admin/owner)
-    pub fn refund_contributors(
+    /// Set the funding deadline of a project (owner only)
+    /// Deposits are rejected once the deadline passes; if the target was not met
+    /// by then, contributors can be refunded
+    pub fn set_project_deadline(
         env: Env,
+        owner: Address,
         project_id: u64,
-        caller: Address,
+        deadline: u64,
     ) -> Result<(), CrowdfundError> {
-        caller.require_auth();
-        let project: ProjectData = env
+        let mut project: ProjectData = env
             .storage()
             .persistent()
             .get(&DataKey::Project(project_id))
             .ok_or(CrowdfundError::ProjectNotFound)?;
 
-        if project.is_active {
-            return Err(CrowdfundError::ProjectNotCancellable);
+        if owner != project.owner {
+            return Err(CrowdfundError::Unauthorized);
         }
 
-        let status: Symbol = env
-            .storage()
-            .persistent()
-            .get(&DataKey::ProjectStatus(project_id))
-            .unwrap_or(Symbol::new(&env, "ACTIVE"));
+        owner.require_auth();
 
-        if status != Symbol::new(&env, "CANCELED") {
-            return Err(CrowdfundError::ProjectNotCancellable);
+        if !project.is_active && !Self::is_pending(&env, project_id) {
+            return Err(CrowdfundError::ProjectNotActive);
         }
 
-        let count_key = DataKey::ContributorCount(project_id);
-        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-
-        let contract_address = env.current_contract_address();
-        let token_client = TokenClient::new(&env, &project.token_address);
-
-        for i in 0..count {
-            let contrib_key = DataKey::Contributor(project_id, i);
-            let contributor: Address = env
-                .storage()
-                .persistent()
-                .get(&contrib_key)
-                .ok_or(CrowdfundError::ProjectNotFound)?;
-
-            let amount_key = DataKey::Contribution(project_id, contributor.clone());
-            let amount: i128 = env.storage().persistent().get(&amount_key).unwrap_or(0);
-
-            if amount > 0 {
-                token_client.transfer(&contract_address, &contributor, &amount);
-
-                env.storage().persistent().remove(&amount_key);
-
-                events::ContributionRefundedEvent {
-                    project_id,
-                    contributor,
-                    amount,
-                }
-                .publish(&env);
-            }
+        // The deadline must lie in the future and cannot be moved once reached
+        let now = env.ledger().timestamp();
+        if project.deadline != 0 && now >= project.deadline {
+            return Err(CrowdfundError::DeadlinePassed);
+        }
+        if deadline <= now {
+            return Err(CrowdfundError::InvalidDeadline);
         }
 
-        env.storage().persistent().remove(&count_key);
-        let balance_key = DataKey::ProjectBalance(project_id, project.token_address);
-        env.storage().persistent().set(&balance_key, &0i128);
+        project.deadline = deadline;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id), &project);
 
         Ok(())
     }
 
-    /// Deposit funds into a project
-    pub fn deposit(
+    /// Set how long after the deadline late deposits are still accepted (owner only)
+    /// The project is only classified as succeeded or failed once the grace
+    /// period has also passed
+    pub fn set_project_grace_period(
         env: Env,
-        user: Address,
+        owner: Address,
         project_id: u64,
-        amount: i128,
+        grace_period: u64,
     ) -> Result<(), CrowdfundError> {
-        // Check if contract is initialized
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(CrowdfundError::NotInitialized);
+        let mut project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        if owner != project.owner {
+            return Err(CrowdfundError::Unauthorized);
         }
 
-        // Require user authorization
-        user.require_auth();
+        owner.require_auth();
 
-        // Check Emergency Pause State (single read)
-        let is_paused: bool = env
-            .storage()
-            .instance()
-            .get(&DataKey::Paused)
-            .unwrap_or(false);
-        if is_paused {
-            return Err(CrowdfundError::ContractPaused);
+        if !project.is_active && !Self::is_pending(&env, project_id) {
+            return Err(CrowdfundError::ProjectNotActive);
         }
 
-        // Validate amount
-        if amount <= 0 {
-            return Err(CrowdfundError::InvalidAmount);
+        // Funding that has already closed cannot be reopened
+        if project.deadline != 0 && env.ledger().timestamp() >= Self::funding_closes_at(&project) {
+            return Err(CrowdfundError::DeadlinePassed);
         }
 
-        // Get project
+        project.grace_period = grace_period;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id), &project);
+
+        Ok(())
+    }
+
+    /// Set how long each contribution stays locked after the contributor's
+    /// first deposit (owner only)
+    /// Only possible while the project is pending; contribution transfers are
+    /// rejected until the lockup has elapsed. Refunds of a canceled or failed
+    /// project are never locked.
+    pub fn set_project_deposit_lockup(
+        env: Env,
+        owner: Address,
+        project_id: u64,
+        deposit_lockup: u64,
+    ) -> Result<(), CrowdfundError> {
         let mut project: ProjectData = env
             .storage()
             .persistent()
             .get(&DataKey::Project(project_id))
             .ok_or(CrowdfundError::ProjectNotFound)?;
 
-        // Check if project is active
-        if !project.is_active {
-            return Err(CrowdfundError::ProjectNotActive);
+        if owner != project.owner {
+            return Err(CrowdfundError::Unauthorized);
         }
 
-        // Transfer tokens from user to contract if they have sufficient balance
-        let contract_address = env.current_contract_address();
-        let user_balance = token::balance(&env, &project.token_address, &user);
-        if user_balance >= amount {
-            token::transfer(
-                &env,
-                &project.token_address,
-                &user,
-                &contract_address,
-                &amount,
-            );
+        owner.require_auth();
+
+        if !Self::is_pending(&env, project_id) {
+            return Err(CrowdfundError::ProjectNotPending);
         }
 
-        // Construct balance key once and reuse
-        let balance_key = DataKey::ProjectBalance(project_id, project.token_address.clone());
-        let current_balance: i128 = env.storage().persistent().get(&balance_key).unwrap_or(0);
+        project.deposit_lockup = deposit_lockup;
         env.storage()
             .persistent()
-            .set(&balance_key, &(current_balance + amount));
+            .set(&DataKey::Project(project_id), &project);
 
-        // Track individual contribution for quadratic funding
-        let contribution_key = DataKey::Contribution(project_id, user.clone());
-        let current_contribution: i128 = env
+        Ok(())
+    }
+
+    /// Set how many milestones make up the project (owner only)
+    /// Only possible while the project is pending. Projects start with a
+    /// single milestone (0); auto-archive waits until milestones
+    /// `0..milestone_count` are all approved.
+    pub fn set_project_milestones(
+        env: Env,
+        owner: Address,
+        project_id: u64,
+        milestone_count: u32,
+    ) -> Result<(), CrowdfundError> {
+        let mut project: ProjectData = env
             .storage()
             .persistent()
-            .get(&contribution_key)
-            .unwrap_or(0);
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
 
-        // If this is a new contributor, add them to the contributors list
-        if current_contribution == 0 {
-            let contributor_count_key = DataKey::ContributorCount(project_id);
-            let contributor_count: u32 = env
-                .storage()
-                .persistent()
-                .get(&contributor_count_key)
-                .unwrap_or(0);
+        if owner != project.owner {
+            return Err(CrowdfundError::Unauthorized);
+        }
 
-            // Store contributor at index
-            env.storage()
-                .persistent()
-                .set(&DataKey::Contributor(project_id, contributor_count), &user);
+        owner.require_auth();
 
-            // Increment contributor count
-            env.storage()
-                .persistent()
-                .set(&contributor_count_key, &(contributor_count + 1));
+        if !Self::is_pending(&env, project_id) {
+            return Err(CrowdfundError::ProjectNotPending);
         }
 
-        // Update contribution amount
-        env.storage()
-            .persistent()
-            .set(&contribution_key, &(current_contribution + amount));
+        if milestone_count == 0 {
+            return Err(CrowdfundError::InvalidAmount);
+        }
 
-        // Update project total deposited
-        project.total_deposited += amount;
+        project.milestone_count = milestone_count;
         env.storage()
             .persistent()
             .set(&DataKey::Project(project_id), &project);
 
-        // Emit deposit event
-        events::DepositEvent {
-            user: user.clone(),
-            project_id,
-            amount,
-        }
-        .publish(&env);
-
-        // Notify subscribers
-        Self::notify_subscribers(
-            &env,
-            Symbol::new(&env, "deposit"),
-            (user, project_id, amount).to_xdr(&env),
-        );
-
         Ok(())
     }
 
-    /// Add a notification subscriber (admin only)
-    pub fn add_subscriber(
+    /// Set how long after a milestone approval the owner must wait before
+    /// withdrawing (admin only, 0 = no delay)
+    pub fn set_project_withdrawal_delay(
         env: Env,
         admin: Address,
-        subscriber: Address,
+        project_id: u64,
+        withdrawal_delay: u64,
     ) -> Result<(), CrowdfundError> {
         Self::verify_admin(&env, &admin)?;
-        let mut subscribers: Vec<Address> = env
+
+        let mut project: ProjectData = env
             .storage()
-            .instance()
-            .get(&DataKey::Subscribers)
-            .unwrap_or(vec![&env]);
-        if !subscribers.contains(&subscriber) {
-            subscribers.push_back(subscriber);
-            env.storage()
-                .instance()
-                .set(&DataKey::Subscribers, &subscribers);
-        }
-        Ok(())
-    }
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
 
-    /// Remove a notification subscriber (admin only)
-    pub fn remove_subscriber(
-        env: Env,
-        admin: Address,
-        subscriber: Address,
-    ) -> Result<(), CrowdfundError> {
-        Self::verify_admin(&env, &admin)?;
-        let mut subscribers: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Subscribers)
-            .unwrap_or(vec![&env]);
-        if let Some(index) = subscribers.first_index_of(&subscriber) {
-            subscribers.remove(index);
-            env.storage()
-                .instance()
-                .set(&DataKey::Subscribers, &subscribers);
-        }
+        project.withdrawal_delay = withdrawal_delay;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id), &project);
+
+        events::admin_action(&env, "set_project_withdrawal_delay", &admin);
         Ok(())
     }
 
-    /// Internal helper to notify all subscribers
-    fn notify_subscribers(env: &Env, event_type: Symbol, data: soroban_sdk::Bytes) {
-        let subscribers: Vec<Address> = env
+    /// Reject moving a contribution out while the contributor's deposit lockup runs
+    fn ensure_deposit_unlocked(
+        env: &Env,
+        project: &ProjectData,
+        contributor: &Address,
+    ) -> Result<(), CrowdfundError> {
+        if project.deposit_lockup == 0 {
+            return Ok(());
+        }
+        let Some(first_deposit) = env
             .storage()
-            .instance()
-            .get(&DataKey::Subscribers)
-            .unwrap_or(vec![env]);
-        let notification = Notification {
-            source: env.current_contract_address(),
-            event_type,
-            data,
+            .persistent()
+            .get::<_, u64>(&DataKey::FirstDepositAt(project.id, contributor.clone()))
+        else {
+            return Ok(());
         };
+        if env.ledger().timestamp() < first_deposit.saturating_add(project.deposit_lockup) {
+            return Err(CrowdfundError::DepositLocked);
+        }
+        Ok(())
+    }
 
-        for subscriber in subscribers {
-            let client = NotificationReceiverClient::new(env, &subscriber);
-            client.on_notify(&notification);
+    /// Timestamp at which a project stops accepting deposits: its deadline
+    /// plus the grace period (0 = no deadline)
+    fn funding_closes_at(project: &ProjectData) -> u64 {
+        if project.deadline == 0 {
+            return 0;
         }
+        project.deadline.saturating_add(project.grace_period)
     }
 
-    /// Approve milestone for a project (admin only)
-    pub fn approve_milestone(
+    /// Set the royalty owed to an originator on every withdrawal (owner only)
+    /// Only possible while the project is pending; the royalty is taken from the
+    /// amount left after the platform fee
+    pub fn set_project_royalty(
         env: Env,
-        admin: Address,
+        owner: Address,
         project_id: u64,
-        milestone_id: u32,
+        royalty_bps: u32,
+        royalty_recipient: Address,
     ) -> Result<(), CrowdfundError> {
-        // Verify admin (single check with helper)
-        Self::verify_admin(&env, &admin)?;
-
-        // Check Emergency Pause State (single read)
-        let is_paused: bool = env
+        let mut project: ProjectData = env
             .storage()
-            .instance()
-            .get(&DataKey::Paused)
-            .unwrap_or(false);
-        if is_paused {
-            return Err(CrowdfundError::ContractPaused);
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        if owner != project.owner {
+            return Err(CrowdfundError::Unauthorized);
         }
 
-        // Check if project exists
+        owner.require_auth();
+
+        if !Self::is_pending(&env, project_id) {
+            return Err(CrowdfundError::ProjectNotPending);
+        }
+
+        // Royalty and platform fee together must leave the owner a share
+        let fee_bps: u32 = env.storage().instance().get(&DataKey::FeeBps).unwrap_or(0);
+        if royalty_bps > MAX_ROYALTY_BPS || (royalty_bps + fee_bps) as i128 >= BPS_DENOMINATOR {
+            return Err(CrowdfundError::InvalidRoyalty);
+        }
+
+        project.royalty_bps = royalty_bps;
+        project.royalty_recipient = Some(royalty_recipient);
         env.storage()
             .persistent()
-            .get::<_, ProjectData>(&DataKey::Project(project_id))
+            .set(&DataKey::Project(project_id), &project);
+
+        Ok(())
+    }
+
+    /// Make a project private so only allowlisted depositors can deposit, or
+    /// public again (owner only)
+    pub fn set_project_private(
+        env: Env,
+        owner: Address,
+        project_id: u64,
+        private: bool,
+    ) -> Result<(), CrowdfundError> {
+        let mut project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
             .ok_or(CrowdfundError::ProjectNotFound)?;
 
-        // Approve milestone
+        if owner != project.owner {
+            return Err(CrowdfundError::Unauthorized);
+        }
+
+        owner.require_auth();
+
+        project.private = private;
         env.storage()
             .persistent()
-            .set(&DataKey::MilestoneApproved(project_id, milestone_id), &true);
+            .set(&DataKey::Project(project_id), &project);
 
-        // Emit milestone approval event
-        events::MilestoneApprovedEvent { admin, project_id }.publish(&env);
+        Ok(())
+    }
 
+    /// Allow an address to deposit into a private project (owner only)
+    pub fn allow_depositor(env: Env, project_id: u64, addr: Address) -> Result<(), CrowdfundError> {
+        Self::require_project_owner(&env, project_id)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::ProjectAllowlist(project_id, addr), &true);
         Ok(())
     }
 
-    /// Start a vote for a milestone approval
-    pub fn start_milestone_vote(
+    /// Remove an address from a private project's depositor allowlist (owner only)
+    pub fn disallow_depositor(
         env: Env,
         project_id: u64,
-        milestone_id: u32,
-        duration_seconds: u64,
+        addr: Address,
     ) -> Result<(), CrowdfundError> {
-        // Get project
+        Self::require_project_owner(&env, project_id)?;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ProjectAllowlist(project_id, addr));
+        Ok(())
+    }
+
+    fn require_project_owner(env: &Env, project_id: u64) -> Result<(), CrowdfundError> {
         let project: ProjectData = env
             .storage()
             .persistent()
             .get(&DataKey::Project(project_id))
             .ok_or(CrowdfundError::ProjectNotFound)?;
-
-        // Only project owner can start a vote
         project.owner.require_auth();
+        Ok(())
+    }
 
-        // Check if already approved
-        let is_approved: bool = env
+    /// Check whether a project's deposits meet its target
+    /// A project created while a price oracle was configured has its target
+    /// denominated in that reference unit, and deposits are converted at the
+    /// oracle's rate
+    /// Whether every milestone of the project has been approved
+    fn all_milestones_approved(env: &Env, project: &ProjectData) -> bool {
+        (0..project.milestone_count).all(|milestone_id| {
+            env.storage()
+                .persistent()
+                .get(&DataKey::MilestoneApproved(project.id, milestone_id))
+                .unwrap_or(false)
+        })
+    }
+
+    fn target_met(env: &Env, project: &ProjectData) -> Result<bool, CrowdfundError> {
+        let raised = match &project.reference_denom {
+            Some(denom) => {
+                let oracle: Address = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::PriceOracle)
+                    .ok_or(CrowdfundError::OracleUnavailable)?;
+                let price = match PriceOracleClient::new(env, &oracle)
+                    .try_price(&project.token_address, denom)
+                {
+                    Ok(Ok(price)) if price > 0 => price,
+                    _ => return Err(CrowdfundError::OracleUnavailable),
+                };
+                project
+                    .total_deposited
+                    .checked_mul(price)
+                    .ok_or(CrowdfundError::PriceOverflow)?
+                    / 10i128.pow(PRICE_DECIMALS)
+            }
+            None => project.total_deposited,
+        };
+        Ok(raised >= project.target_amount)
+    }
+
+    /// Classify a project relative to its funding deadline
+    /// The first evaluation after funding closes is recorded on the project, so
+    /// later oracle price moves or outages cannot change the outcome
+    fn deadline_status(
+        env: &Env,
+        project: &mut ProjectData,
+    ) -> Result<DeadlineStatus, CrowdfundError> {
+        if project.outcome != DeadlineStatus::Open {
+            return Ok(project.outcome);
+        }
+        if project.deadline == 0 || env.ledger().timestamp() < Self::funding_closes_at(project) {
+            return Ok(DeadlineStatus::Open);
+        }
+        project.outcome = if Self::target_met(env, project)? {
+            DeadlineStatus::Succeeded
+        } else {
+            DeadlineStatus::Failed
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project.id), project);
+        Ok(project.outcome)
+    }
+
+    /// Cancel project (owner or admin only)
+    pub fn cancel_project(
+        env: Env,
+        caller: Address,
+        project_id: u64,
+    ) -> Result<(), CrowdfundError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CrowdfundError::NotInitialized)?;
+
+        let mut project: ProjectData = env
             .storage()
             .persistent()
-            .get(&DataKey::MilestoneApproved(project_id, milestone_id))
-            .unwrap_or(false);
-        if is_approved {
-            return Err(CrowdfundError::MilestoneAlreadyApproved);
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        let is_admin = caller == stored_admin;
+        let is_owner = caller == project.owner;
+
+        if !is_admin && !is_owner {
+            return Err(CrowdfundError::Unauthorized);
         }
 
-        // Set voting window
-        let end_time = env.ledger().timestamp() + duration_seconds;
-        env.storage().persistent().set(
-            &DataKey::MilestoneVoteWindow(project_id, milestone_id),
-            &end_time,
-        );
+        caller.require_auth();
+
+        if !project.is_active && !Self::is_pending(&env, project_id) {
+            return Err(CrowdfundError::ProjectNotActive);
+        }
+
+        // Mark as canceled
+        project.is_active = false;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id), &project);
 
-        // Reset votes for this milestone if needed (though they should be 0)
-        env.storage().persistent().set(
-            &DataKey::MilestoneVotesFor(project_id, milestone_id),
-            &0i128,
-        );
         env.storage().persistent().set(
-            &DataKey::MilestoneVotesAgainst(project_id, milestone_id),
-            &0i128,
+            &DataKey::ProjectStatus(project_id),
+            &Symbol::new(&env, "CANCELED"),
         );
 
-        // Emit event
-        events::MilestoneVoteStartedEvent {
-            project_id,
-            milestone_id,
-            end_time,
+        if is_admin {
+            events::admin_action(&env, "cancel_project", &caller);
         }
-        .publish(&env);
+        events::ProjectCanceledEvent { project_id, caller }.emit(&env);
 
         Ok(())
     }
 
-    /// Cast a vote for a milestone
-    pub fn vote_milestone(
+    /// Refund all contributors (anyone can call after cancel, but usually admin/owner)
+    ///
+    /// Each contributor gets their contribution back, or a pro-rata share of the
+    /// balance when it cannot cover them all; the run settles every contribution.
+    /// Whatever remains afterwards, such as deposit matches, goes to the admin.
+    pub fn refund_contributors(
         env: Env,
-        voter: Address,
         project_id: u64,
-        milestone_id: u32,
-        support: bool,
+        caller: Address,
     ) -> Result<(), CrowdfundError> {
-        voter.require_auth();
+        caller.require_auth();
+        let project = Self::refundable_project(&env, project_id)?;
 
-        // Check voting window
-        let end_time: u64 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::MilestoneVoteWindow(project_id, milestone_id))
-            .ok_or(CrowdfundError::VotingWindowNotStarted)?;
+        let (refunds, leftover) = Self::refund_plan(&env, &project);
+        let mut total_fee = 0;
 
-        if env.ledger().timestamp() > end_time {
-            return Err(CrowdfundError::VotingWindowClosed);
+        for (contributor, amount) in refunds.iter() {
+            total_fee += Self::pay_refund(&env, &project, contributor.clone(), amount)?;
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Contribution(project_id, contributor));
         }
 
-        // Check if already voted
-        if env.storage().persistent().has(&DataKey::MilestoneVote(
-            project_id,
-            milestone_id,
-            voter.clone(),
-        )) {
-            return Err(CrowdfundError::AlreadyVoted);
+        Self::pay_fee(&env, &project.token_address, total_fee)?;
+
+        // Matched funds and rounding dust left after every refund go to the admin
+        if leftover > 0 {
+            let admin: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::Admin)
+                .ok_or(CrowdfundError::NotInitialized)?;
+            token::transfer(
+                &env,
+                &project.token_address,
+                &env.current_contract_address(),
+                &admin,
+                &leftover,
+            )?;
         }
 
-        // Get contribution weight
-        let weight: i128 = env
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RefundCursor(project_id));
+        let balance_key = DataKey::ProjectBalance(project_id, project.token_address);
+        env.storage().persistent().set(&balance_key, &0i128);
+
+        Ok(())
+    }
+
+    /// Split a project's balance across its contributors for a full refund
+    ///
+    /// Every contributor gets their contribution back when the balance covers
+    /// all of them; otherwise each gets the same pro-rata share of the balance.
+    /// Returns the gross refund per contributor and the balance left over
+    fn refund_plan(env: &Env, project: &ProjectData) -> (Vec<(Address, i128)>, i128) {
+        let count: u32 = env
             .storage()
             .persistent()
-            .get(&DataKey::Contribution(project_id, voter.clone()))
+            .get(&DataKey::ContributorCount(project.id))
             .unwrap_or(0);
 
-        if weight <= 0 {
-            return Err(CrowdfundError::InsufficientContributionToVote);
-        }
-
-        // Update vote count
-        if support {
-            let current_for: i128 = env
+        let mut contributions = Vec::new(env);
+        let mut outstanding = 0i128;
+        for i in 0..count {
+            let Some(contributor) = env
                 .storage()
                 .persistent()
-                .get(&DataKey::MilestoneVotesFor(project_id, milestone_id))
-                .unwrap_or(0);
-            env.storage().persistent().set(
-                &DataKey::MilestoneVotesFor(project_id, milestone_id),
-                &(current_for + weight),
-            );
-        } else {
-            let current_against: i128 = env
+                .get::<_, Address>(&DataKey::Contributor(project.id, i))
+            else {
+                continue;
+            };
+            let amount: i128 = env
                 .storage()
                 .persistent()
-                .get(&DataKey::MilestoneVotesAgainst(project_id, milestone_id))
+                .get(&DataKey::Contribution(project.id, contributor.clone()))
                 .unwrap_or(0);
-            env.storage().persistent().set(
-                &DataKey::MilestoneVotesAgainst(project_id, milestone_id),
-                &(current_against + weight),
-            );
+            if amount > 0 {
+                contributions.push_back((contributor, amount));
+                outstanding += amount;
+            }
         }
 
-        // Mark as voted
-        env.storage().persistent().set(
-            &DataKey::MilestoneVote(project_id, milestone_id, voter.clone()),
-            &true,
-        );
-
-        // Emit event
-        events::VoteCastEvent {
-            project_id,
-            milestone_id,
-            voter,
-            weight,
-            support,
+        let balance: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProjectBalance(
+                project.id,
+                project.token_address.clone(),
+            ))
+            .unwrap_or(0)
+            .max(0);
+        let mut refunds = Vec::new(env);
+        let mut leftover = balance;
+        for (contributor, amount) in contributions.iter() {
+            let share = if balance >= outstanding {
+                amount
+            } else {
+                amount * balance / outstanding
+            };
+            if share > 0 {
+                refunds.push_back((contributor, share));
+                leftover -= share;
+            }
         }
-        .publish(&env);
+        (refunds, leftover)
+    }
 
-        // Auto-approve if threshold met (> 50% of total deposited)
-        let project: ProjectData = env
+    /// Refund a page of the contributor list (admin only)
+    ///
+    /// Refunds contributors at indices `start..start + limit`, skipping any
+    /// below the project's refund cursor, and returns how many were refunded.
+    /// Lets large failed campaigns be refunded over several transactions.
+    pub fn refund_chunk(
+        env: Env,
+        admin: Address,
+        project_id: u64,
+        start: u32,
+        limit: u32,
+    ) -> Result<u32, CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+        let project = Self::refundable_project(&env, project_id)?;
+
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ContributorCount(project_id))
+            .unwrap_or(0);
+        let cursor_key = DataKey::RefundCursor(project_id);
+        let cursor: u32 = env.storage().persistent().get(&cursor_key).unwrap_or(0);
+        let end = start.saturating_add(limit).min(count);
+
+        let mut refunded = 0;
+        let mut total_fee = 0;
+
+        for i in start.max(cursor)..end {
+            let contributor: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Contributor(project_id, i))
+                .ok_or(CrowdfundError::ProjectNotFound)?;
+
+            if let Some((_, fee)) = Self::refund_contribution(&env, &project, contributor)? {
+                refunded += 1;
+                total_fee += fee;
+            }
+        }
+
+        Self::pay_fee(&env, &project.token_address, total_fee)?;
+
+        if end > cursor && start <= cursor {
+            env.storage().persistent().set(&cursor_key, &end);
+        }
+
+        events::admin_action(&env, "refund_chunk", &admin);
+        Ok(refunded)
+    }
+
+    /// Load a project that is open for refunds: canceled, or past its deadline
+    /// without reaching the target
+    fn refundable_project(env: &Env, project_id: u64) -> Result<ProjectData, CrowdfundError> {
+        let mut project: ProjectData = env
             .storage()
             .persistent()
             .get(&DataKey::Project(project_id))
             .ok_or(CrowdfundError::ProjectNotFound)?;
 
-        let current_for: i128 = env
+        let status: Symbol = env
             .storage()
             .persistent()
-            .get(&DataKey::MilestoneVotesFor(project_id, milestone_id))
-            .unwrap_or(0);
+            .get(&DataKey::ProjectStatus(project_id))
+            .unwrap_or(Symbol::new(env, "ACTIVE"));
+        let canceled = !project.is_active && status == Symbol::new(env, "CANCELED");
+        let failed = Self::deadline_status(env, &mut project)? == DeadlineStatus::Failed;
+
+        if !canceled && !failed {
+            return Err(CrowdfundError::ProjectNotCancellable);
+        }
+        Ok(project)
+    }
+
+    /// Refund one contributor's outstanding contribution net of the refund fee
+    ///
+    /// Refunds are capped at the project's remaining balance, so contributions
+    /// already paid out to the owner are not refunded from other projects'
+    /// funds; any shortfall stays recorded against the contributor.
+    /// Returns the gross amount and fee, or `None` if nothing was refunded
+    fn refund_contribution(
+        env: &Env,
+        project: &ProjectData,
+        contributor: Address,
+    ) -> Result<Option<(i128, i128)>, CrowdfundError> {
+        let project_id = project.id;
+        let amount_key = DataKey::Contribution(project_id, contributor.clone());
+        let contributed: i128 = env.storage().persistent().get(&amount_key).unwrap_or(0);
+        let balance_key = DataKey::ProjectBalance(project_id, project.token_address.clone());
+        let balance: i128 = env.storage().persistent().get(&balance_key).unwrap_or(0);
+        let amount = contributed.min(balance);
+        if amount <= 0 {
+            return Ok(None);
+        }
 
-        if current_for > project.total_deposited / 2 {
+        let fee = Self::pay_refund(env, project, contributor, amount)?;
+        if amount == contributed {
+            env.storage().persistent().remove(&amount_key);
+        } else {
             env.storage()
                 .persistent()
-                .set(&DataKey::MilestoneApproved(project_id, milestone_id), &true);
-            events::MilestoneApprovedByVoteEvent {
-                project_id,
-                milestone_id,
+                .set(&amount_key, &(contributed - amount));
+        }
+        env.storage()
+            .persistent()
+            .set(&balance_key, &(balance - amount));
+
+        Ok(Some((amount, fee)))
+    }
+
+    /// Send a contributor a refund of `amount` net of the refund fee
+    /// Returns the fee withheld, which the caller pays out
+    fn pay_refund(
+        env: &Env,
+        project: &ProjectData,
+        contributor: Address,
+        amount: i128,
+    ) -> Result<i128, CrowdfundError> {
+        let fee = Self::refund_fee(env, amount);
+        token::transfer(
+            env,
+            &project.token_address,
+            &env.current_contract_address(),
+            &contributor,
+            &(amount - fee),
+        )?;
+
+        events::ContributionRefundedEvent {
+            project_id: project.id,
+            contributor,
+            amount: amount - fee,
+            fee,
+        }
+        .emit(env);
+        Ok(fee)
+    }
+
+    /// Preview what `refund_contributors` would pay each contributor, net of
+    /// the refund fee, without moving tokens
+    pub fn preview_refunds(
+        env: Env,
+        project_id: u64,
+    ) -> Result<Vec<(Address, i128)>, CrowdfundError> {
+        let project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        let mut refunds = Vec::new(&env);
+        for (contributor, amount) in Self::refund_plan(&env, &project).0.iter() {
+            refunds.push_back((contributor, amount - Self::refund_fee(&env, amount)));
+        }
+        Ok(refunds)
+    }
+
+    /// List the projects `contributor` can be refunded from right now, with the
+    /// amount they would receive net of the refund fee
+    ///
+    /// A project qualifies when it is canceled or failed its deadline.
+    pub fn refundable_projects(env: Env, contributor: Address) -> Vec<(u64, i128)> {
+        let mut refundable = Vec::new(&env);
+        for project_id in Self::get_contributor_projects(env.clone(), contributor.clone()).iter() {
+            if Self::refundable_project(&env, project_id).is_err() {
+                continue;
+            }
+            let amount: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Contribution(project_id, contributor.clone()))
+                .unwrap_or(0);
+            if amount > 0 {
+                refundable.push_back((project_id, amount - Self::refund_fee(&env, amount)));
             }
-            .publish(&env);
         }
+        refundable
+    }
 
-        Ok(())
+    /// Refund fee kept from a refunded contribution of `amount`
+    fn refund_fee(env: &Env, amount: i128) -> i128 {
+        let refund_fee_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RefundFeeBps)
+            .unwrap_or(0);
+        amount * refund_fee_bps as i128 / BPS_DENOMINATOR
     }
 
-    /// Withdraw funds from a project (owner only, requires milestone approval)
-    pub fn withdraw(
+    /// Deposit funds into a project
+    pub fn deposit(
         env: Env,
+        user: Address,
         project_id: u64,
-        milestone_id: u32,
         amount: i128,
     ) -> Result<(), CrowdfundError> {
-        // Check if contract is initialized
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(CrowdfundError::NotInitialized);
+        Self::do_deposit(&env, &user, user.clone(), project_id, amount, None)
+    }
+
+    /// Deposit funds into a project with a reference for off-chain reconciliation
+    /// The memo is only published in the `DepositEvent`, never stored
+    pub fn deposit_with_memo(
+        env: Env,
+        user: Address,
+        project_id: u64,
+        amount: i128,
+        memo: String,
+    ) -> Result<(), CrowdfundError> {
+        if memo.len() > MAX_MEMO_LEN {
+            return Err(CrowdfundError::MemoTooLong);
         }
+        Self::do_deposit(&env, &user, user.clone(), project_id, amount, Some(memo))
+    }
 
+    /// Deposit funds into a project on behalf of another address
+    /// Tokens are pulled from `sponsor` but the contribution is credited to `credited_to`,
+    /// so refunds and matching are attributed to the credited address
+    pub fn deposit_for(
+        env: Env,
+        sponsor: Address,
+        project_id: u64,
+        amount: i128,
+        credited_to: Address,
+    ) -> Result<(), CrowdfundError> {
+        Self::do_deposit(&env, &sponsor, credited_to, project_id, amount, None)
+    }
+
+    /// Whether a deposit of `amount` from `user` would be accepted right now
+    ///
+    /// Applies the same pause, amount, status, allowlist and deadline gates as
+    /// `deposit`, so frontends need not replicate them. Token balance and
+    /// allowance are not checked.
+    pub fn can_deposit(env: Env, project_id: u64, user: Address, amount: i128) -> bool {
+        env.storage().instance().has(&DataKey::Admin)
+            && Self::check_deposit(&env, &user, project_id, amount).is_ok()
+    }
+
+    /// Check the deposit gates and return the project being deposited into
+    fn check_deposit(
+        env: &Env,
+        user: &Address,
+        project_id: u64,
+        amount: i128,
+    ) -> Result<ProjectData, CrowdfundError> {
         // Check Emergency Pause State (single read)
         let is_paused: bool = env
             .storage()
@@ -653,213 +1136,1872 @@ impl CrowdfundVaultContract {
         if is_paused {
             return Err(CrowdfundError::ContractPaused);
         }
+        if Self::is_deposits_paused(env.clone()) {
+            return Err(CrowdfundError::DepositsPaused);
+        }
+
+        // Validate amount
+        if amount == 0 {
+            return Err(CrowdfundError::ZeroAmount);
+        }
+        if amount < 0 {
+            return Err(CrowdfundError::InvalidAmount);
+        }
 
         // Get project
-        let mut project: ProjectData = env
+        let project: ProjectData = env
             .storage()
             .persistent()
             .get(&DataKey::Project(project_id))
             .ok_or(CrowdfundError::ProjectNotFound)?;
 
-        // Require owner authorization
-        project.owner.require_auth();
-
         // Check if project is active
         if !project.is_active {
             return Err(CrowdfundError::ProjectNotActive);
         }
 
-        // Validate amount
-        if amount <= 0 {
-            return Err(CrowdfundError::InvalidAmount);
+        // Private rounds only accept allowlisted depositors
+        if project.private
+            && !env
+                .storage()
+                .persistent()
+                .has(&DataKey::ProjectAllowlist(project_id, user.clone()))
+        {
+            return Err(CrowdfundError::NotWhitelisted);
         }
 
-        // Check specific milestone approval
-        let is_approved: bool = env
-            .storage()
-            .persistent()
-            .get(&DataKey::MilestoneApproved(project_id, milestone_id))
-            .unwrap_or(false);
-
-        if !is_approved {
-            return Err(CrowdfundError::MilestoneNotApproved);
+        // Reject deposits once the funding deadline and any grace period have passed
+        if project.deadline != 0 && env.ledger().timestamp() >= Self::funding_closes_at(&project) {
+            return Err(CrowdfundError::DeadlinePassed);
         }
 
-        // Construct balance key once
-        let balance_key = DataKey::ProjectBalance(project_id, project.token_address.clone());
-        let current_balance: i128 = env.storage().persistent().get(&balance_key).unwrap_or(0);
+        Ok(project)
+    }
 
-        if current_balance < amount {
-            return Err(CrowdfundError::InsufficientBalance);
+    /// Internal deposit logic shared by `deposit`, `deposit_with_memo` and `deposit_for`
+    fn do_deposit(
+        env: &Env,
+        sponsor: &Address,
+        user: Address,
+        project_id: u64,
+        amount: i128,
+        memo: Option<String>,
+    ) -> Result<(), CrowdfundError> {
+        // Check if contract is initialized
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(CrowdfundError::NotInitialized);
         }
 
-        // Transfer tokens from contract to owner
+        // Require sponsor authorization
+        sponsor.require_auth();
+
+        let mut project = Self::check_deposit(env, &user, project_id, amount)?;
+
+        // Split off the deposit fee, if one is configured; the project is credited the net
+        let deposit_fee_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::DepositFeeBps)
+            .unwrap_or(0);
+        let fee = amount * deposit_fee_bps as i128 / BPS_DENOMINATOR;
+        let amount = amount - fee;
+
+        // Transfer the full deposit from sponsor to contract, then pay the fee out of it
         let contract_address = env.current_contract_address();
         token::transfer(
-            &env,
+            env,
             &project.token_address,
+            sponsor,
             &contract_address,
-            &project.owner,
-            &amount,
+            &(amount + fee),
+        )?;
+        Self::pay_fee(env, &project.token_address, fee)?;
+
+        // Platform match for projects of reputable owners
+        let matched = Self::deposit_match(env, &project, amount);
+
+        // Construct balance key once and reuse
+        let balance_key = DataKey::ProjectBalance(project_id, project.token_address.clone());
+        let current_balance: i128 = env.storage().persistent().get(&balance_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&balance_key, &(current_balance + amount + matched));
+
+        // Track individual contribution for quadratic funding
+        let contribution_key = DataKey::Contribution(project_id, user.clone());
+        let current_contribution: i128 = env
+            .storage()
+            .persistent()
+            .get(&contribution_key)
+            .unwrap_or(0);
+
+        // If this is a new contributor, add them to the contributors list
+        if !Self::is_listed_contributor(env, project_id, &user) {
+            Self::add_contributor(env, project_id, &user);
+            project.unique_contributors += 1;
+        }
+
+        // Remember the first deposit so the project's lockup runs from it
+        let first_deposit_key = DataKey::FirstDepositAt(project_id, user.clone());
+        if !env.storage().persistent().has(&first_deposit_key) {
+            env.storage()
+                .persistent()
+                .set(&first_deposit_key, &env.ledger().timestamp());
+        }
+
+        // Update contribution amount
+        env.storage()
+            .persistent()
+            .set(&contribution_key, &(current_contribution + amount));
+
+        // Update project total deposited
+        project.total_deposited += amount + matched;
+        project.deposit_count += 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id), &project);
+
+        // Emit deposit event
+        events::DepositEvent {
+            user: user.clone(),
+            project_id,
+            amount,
+            sponsor: sponsor.clone(),
+            fee,
+            memo,
+            schema_version: events::DEPOSIT_EVENT_SCHEMA_VERSION,
+        }
+        .emit(env);
+
+        // Notify subscribers
+        Self::notify_subscribers(
+            env,
+            Symbol::new(env, "deposit"),
+            (user, project_id, amount).to_xdr(env),
         );
 
-        // Update project balance
+        Ok(())
+    }
+
+    /// Check whether an address is already in a project's contributor list
+    /// A contribution can drop to zero through a refund or transfer while the
+    /// address stays listed, so membership is read from the participation list
+    fn is_listed_contributor(env: &Env, project_id: u64, user: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .get::<_, Vec<u64>>(&DataKey::ContributorProjects(user.clone()))
+            .is_some_and(|projects| projects.contains(project_id))
+    }
+
+    /// Append a new contributor to a project's contributor list and record the
+    /// project in the contributor's participation list
+    fn add_contributor(env: &Env, project_id: u64, user: &Address) {
+        let contributor_count_key = DataKey::ContributorCount(project_id);
+        let contributor_count: u32 = env
+            .storage()
+            .persistent()
+            .get(&contributor_count_key)
+            .unwrap_or(0);
+
+        // Store contributor at index
+        env.storage()
+            .persistent()
+            .set(&DataKey::Contributor(project_id, contributor_count), user);
+
+        // Increment contributor count
+        env.storage()
+            .persistent()
+            .set(&contributor_count_key, &(contributor_count + 1));
+
+        // Record the project in the contributor's participation list
+        let projects_key = DataKey::ContributorProjects(user.clone());
+        let mut projects: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&projects_key)
+            .unwrap_or(vec![env]);
+        if !projects.contains(project_id) {
+            projects.push_back(project_id);
+            env.storage().persistent().set(&projects_key, &projects);
+        }
+    }
+
+    /// Transfer part or all of a contribution to another address
+    /// Only allowed before any milestone of the project is approved and while no
+    /// milestone vote is open; refunds, matching and voting weight follow the
+    /// transferred position
+    pub fn transfer_contribution(
+        env: Env,
+        from: Address,
+        to: Address,
+        project_id: u64,
+        amount: i128,
+    ) -> Result<(), CrowdfundError> {
+        from.require_auth();
+
+        if amount <= 0 {
+            return Err(CrowdfundError::InvalidAmount);
+        }
+        if from == to {
+            return Err(CrowdfundError::SelfTransfer);
+        }
+
+        let mut project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        let approved_count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ApprovedMilestoneCount(project_id))
+            .unwrap_or(0);
+        // Positions are fixed once funds can be released to the owner
+        if approved_count > 0 || project.release_bps != 0 {
+            return Err(CrowdfundError::MilestoneAlreadyApproved);
+        }
+
+        // A position moved mid-vote could be voted a second time
+        if project.vote_open_until != 0 && env.ledger().timestamp() <= project.vote_open_until {
+            return Err(CrowdfundError::VoteInProgress);
+        }
+
+        Self::ensure_deposit_unlocked(&env, &project, &from)?;
+
+        let from_key = DataKey::Contribution(project_id, from.clone());
+        let from_contribution: i128 = env.storage().persistent().get(&from_key).unwrap_or(0);
+        if amount > from_contribution {
+            return Err(CrowdfundError::InsufficientBalance);
+        }
+
+        let to_key = DataKey::Contribution(project_id, to.clone());
+        let to_contribution: i128 = env.storage().persistent().get(&to_key).unwrap_or(0);
+        if !Self::is_listed_contributor(&env, project_id, &to) {
+            Self::add_contributor(&env, project_id, &to);
+            project.unique_contributors += 1;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Project(project_id), &project);
+        }
+
+        // The receiver inherits the sender's lockup so it cannot be sidestepped
+        let to_first_key = DataKey::FirstDepositAt(project_id, to.clone());
+        if let Some(first_deposit) = env
+            .storage()
+            .persistent()
+            .get::<_, u64>(&DataKey::FirstDepositAt(project_id, from.clone()))
+        {
+            if !env.storage().persistent().has(&to_first_key) {
+                env.storage()
+                    .persistent()
+                    .set(&to_first_key, &first_deposit);
+            }
+        }
+
+        env.storage()
+            .persistent()
+            .set(&from_key, &(from_contribution - amount));
+        env.storage()
+            .persistent()
+            .set(&to_key, &(to_contribution + amount));
+
+        events::ContributionTransferredEvent {
+            project_id,
+            from,
+            to,
+            amount,
+        }
+        .emit(&env);
+
+        Ok(())
+    }
+
+    /// Pull the configured match for a deposit from the match pool
+    /// Returns 0 when matching is not configured, the owner's reputation is not
+    /// above the threshold, or the pool's balance or allowance cannot cover the match
+    fn deposit_match(env: &Env, project: &ProjectData, amount: i128) -> i128 {
+        let match_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::DepositMatchBps)
+            .unwrap_or(0);
+        if match_bps == 0 {
+            return 0;
+        }
+        let (Some(match_pool), Some(registry)) = (
+            env.storage()
+                .instance()
+                .get::<_, Address>(&DataKey::DepositMatchPool),
+            env.storage()
+                .instance()
+                .get::<_, Address>(&DataKey::Registry),
+        ) else {
+            return 0;
+        };
+
+        // Unregistered owners have no reputation
+        let min_reputation: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::DepositMatchMinReputation)
+            .unwrap_or(0);
+        let reputation = match ContributorRegistryClient::new(env, &registry)
+            .try_get_reputation(&project.owner)
+        {
+            Ok(Ok(reputation)) => reputation,
+            _ => 0,
+        };
+        if reputation <= min_reputation {
+            return 0;
+        }
+
+        let matched = amount * match_bps as i128 / BPS_DENOMINATOR;
+        let contract_address = env.current_contract_address();
+        if matched <= 0
+            || token::balance(env, &project.token_address, &match_pool) < matched
+            || token::allowance(env, &project.token_address, &match_pool, &contract_address)
+                < matched
+        {
+            return 0;
+        }
+
+        if token::transfer_from(
+            env,
+            &project.token_address,
+            &contract_address,
+            &match_pool,
+            &contract_address,
+            &matched,
+        )
+        .is_err()
+        {
+            return 0;
+        }
+
+        events::DepositMatchedEvent {
+            project_id: project.id,
+            amount: matched,
+        }
+        .emit(env);
+
+        matched
+    }
+
+    /// Add a notification subscriber (admin only)
+    pub fn add_subscriber(
+        env: Env,
+        admin: Address,
+        subscriber: Address,
+    ) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+        let mut subscribers: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Subscribers)
+            .unwrap_or(vec![&env]);
+        if !subscribers.contains(&subscriber) {
+            subscribers.push_back(subscriber);
+            env.storage()
+                .instance()
+                .set(&DataKey::Subscribers, &subscribers);
+        }
+        events::admin_action(&env, "add_subscriber", &admin);
+        Ok(())
+    }
+
+    /// Remove a notification subscriber (admin only)
+    pub fn remove_subscriber(
+        env: Env,
+        admin: Address,
+        subscriber: Address,
+    ) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+        let mut subscribers: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Subscribers)
+            .unwrap_or(vec![&env]);
+        if let Some(index) = subscribers.first_index_of(&subscriber) {
+            subscribers.remove(index);
+            env.storage()
+                .instance()
+                .set(&DataKey::Subscribers, &subscribers);
+        }
+        events::admin_action(&env, "remove_subscriber", &admin);
+        Ok(())
+    }
+
+    /// Internal helper to notify all subscribers
+    fn notify_subscribers(env: &Env, event_type: Symbol, data: soroban_sdk::Bytes) {
+        let subscribers: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Subscribers)
+            .unwrap_or(vec![env]);
+        let notification = Notification {
+            source: env.current_contract_address(),
+            event_type,
+            data,
+        };
+
+        for subscriber in subscribers {
+            let client = NotificationReceiverClient::new(env, &subscriber);
+            client.on_notify(&notification);
+        }
+    }
+
+    /// Release a share of a project's deposits to the owner for a milestone
+    /// (admin only)
+    ///
+    /// Once set, `withdraw` for `milestone_id` is capped at
+    /// `total_deposited * release_bps / 10000` in total instead of requiring an
+    /// approved milestone; withdrawals for any other milestone are rejected.
+    /// The release can be raised later but never lowered, and a raise may move
+    /// it on to the next milestone.
+    pub fn set_milestone_release(
+        env: Env,
+        admin: Address,
+        project_id: u64,
+        milestone_id: u32,
+        release_bps: u32,
+    ) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+
+        // Check Emergency Pause State (single read)
+        let is_paused: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false);
+        if is_paused {
+            return Err(CrowdfundError::ContractPaused);
+        }
+
+        let mut project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        if release_bps as i128 > BPS_DENOMINATOR {
+            return Err(CrowdfundError::InvalidAmount);
+        }
+        if release_bps < project.release_bps {
+            return Err(CrowdfundError::CannotReduceRelease);
+        }
+        if Self::deadline_status(&env, &mut project)? == DeadlineStatus::Failed {
+            return Err(CrowdfundError::ProjectFailed);
+        }
+
+        project.release_bps = release_bps;
+        project.release_milestone = milestone_id;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id), &project);
+
+        events::admin_action(&env, "set_milestone_release", &admin);
+        events::MilestoneReleaseEvent {
+            admin,
+            project_id,
+            milestone_id,
+            release_bps,
+        }
+        .emit(&env);
+
+        Ok(())
+    }
+
+    /// Get the share of a project's deposits released to the owner (0 if unset)
+    pub fn get_milestone_release(env: Env, project_id: u64) -> Result<u32, CrowdfundError> {
+        let project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+        Ok(project.release_bps)
+    }
+
+    /// Amount still withdrawable under the project's release, or `None` when
+    /// withdrawals are gated by milestone approval instead
+    fn release_remaining(project: &ProjectData) -> Option<i128> {
+        if project.release_bps == 0 {
+            return None;
+        }
+        let released = project.total_deposited * project.release_bps as i128 / BPS_DENOMINATOR;
+        Some((released - project.total_withdrawn).max(0))
+    }
+
+    /// Mark a milestone approved, tracking how many milestones of the project are approved
+    fn mark_milestone_approved(env: &Env, project_id: u64, milestone_id: u32) {
+        let approved_key = DataKey::MilestoneApproved(project_id, milestone_id);
+        let already_approved: bool = env
+            .storage()
+            .persistent()
+            .get(&approved_key)
+            .unwrap_or(false);
+        if already_approved {
+            return;
+        }
+
+        env.storage().persistent().set(&approved_key, &true);
+        env.storage().persistent().set(
+            &DataKey::LastMilestoneApproval(project_id),
+            &env.ledger().timestamp(),
+        );
+
+        let count_key = DataKey::ApprovedMilestoneCount(project_id);
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        env.storage().persistent().set(&count_key, &(count + 1));
+    }
+
+    /// Whether the project's last milestone approval is still within the cooldown
+    fn approval_cooldown_active(env: &Env, project_id: u64) -> bool {
+        let cooldown: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MilestoneCooldown)
+            .unwrap_or(0);
+        env.storage()
+            .persistent()
+            .get::<_, u64>(&DataKey::LastMilestoneApproval(project_id))
+            .is_some_and(|last| env.ledger().timestamp() < last.saturating_add(cooldown))
+    }
+
+    /// Send a fee held by the vault to the fee recipient
+    fn pay_fee(env: &Env, token_address: &Address, fee: i128) -> Result<(), CrowdfundError> {
+        if fee <= 0 {
+            return Ok(());
+        }
+
+        let fee_recipient: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FeeRecipient)
+            .ok_or(CrowdfundError::NotInitialized)?;
+        token::transfer(
+            env,
+            token_address,
+            &env.current_contract_address(),
+            &fee_recipient,
+            &fee,
+        )?;
+        Self::record_fee(env, token_address, fee);
+
+        // Let a treasury contract account for the inflow
+        let recipient_is_contract: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::FeeRecipientIsContract)
+            .unwrap_or(false);
+        if recipient_is_contract {
+            FeeTreasuryClient::new(env, &fee_recipient).receive_fee(token_address, &fee);
+        }
+        Ok(())
+    }
+
+    /// Add a skimmed fee to the running per-token total
+    fn record_fee(env: &Env, token_address: &Address, fee: i128) {
+        let key = DataKey::FeesCollected(token_address.clone());
+        let collected: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(collected + fee));
+    }
+
+    /// Platform fee charged on a withdrawal of `amount`
+    fn withdrawal_fee(env: &Env, amount: i128) -> i128 {
+        let fee_bps: u32 = env.storage().instance().get(&DataKey::FeeBps).unwrap_or(0);
+        amount * fee_bps as i128 / BPS_DENOMINATOR
+    }
+
+    /// Approve milestone for a project (admin only)
+    pub fn approve_milestone(
+        env: Env,
+        admin: Address,
+        project_id: u64,
+        milestone_id: u32,
+    ) -> Result<(), CrowdfundError> {
+        // Verify admin (single check with helper)
+        Self::verify_admin(&env, &admin)?;
+
+        // Check Emergency Pause State (single read)
+        let is_paused: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false);
+        if is_paused {
+            return Err(CrowdfundError::ContractPaused);
+        }
+
+        // Check if project exists
+        env.storage()
+            .persistent()
+            .get::<_, ProjectData>(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        if Self::approval_cooldown_active(&env, project_id) {
+            return Err(CrowdfundError::ApprovalCooldownActive);
+        }
+
+        // Approve milestone
+        Self::mark_milestone_approved(&env, project_id, milestone_id);
+
+        events::admin_action(&env, "approve_milestone", &admin);
+        // Emit milestone approval event
+        events::MilestoneApprovedEvent { admin, project_id }.emit(&env);
+
+        Ok(())
+    }
+
+    /// Approve the current milestone of several projects in one call (admin only)
+    ///
+    /// The current milestone is the lowest milestone id not yet approved.
+    /// Unknown project ids and projects still within the approval cooldown are
+    /// skipped; returns the ids that were approved.
+    pub fn approve_milestones(
+        env: Env,
+        admin: Address,
+        project_ids: Vec<u64>,
+    ) -> Result<Vec<u64>, CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+
+        let is_paused: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false);
+        if is_paused {
+            return Err(CrowdfundError::ContractPaused);
+        }
+
+        let mut approved = Vec::new(&env);
+        for project_id in project_ids.iter() {
+            if !env
+                .storage()
+                .persistent()
+                .has(&DataKey::Project(project_id))
+                || Self::approval_cooldown_active(&env, project_id)
+            {
+                continue;
+            }
+
+            let mut milestone_id = 0;
+            while env
+                .storage()
+                .persistent()
+                .get(&DataKey::MilestoneApproved(project_id, milestone_id))
+                .unwrap_or(false)
+            {
+                milestone_id += 1;
+            }
+            Self::mark_milestone_approved(&env, project_id, milestone_id);
+
+            events::MilestoneApprovedEvent {
+                admin: admin.clone(),
+                project_id,
+            }
+            .emit(&env);
+            approved.push_back(project_id);
+        }
+        events::admin_action(&env, "approve_milestones", &admin);
+        Ok(approved)
+    }
+
+    /// Revoke a milestone approval granted in error (admin only)
+    /// Only possible while nothing has been withdrawn against the milestone
+    pub fn revoke_milestone(
+        env: Env,
+        admin: Address,
+        project_id: u64,
+        milestone_id: u32,
+    ) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Project(project_id))
+        {
+            return Err(CrowdfundError::ProjectNotFound);
+        }
+
+        let approved_key = DataKey::MilestoneApproved(project_id, milestone_id);
+        let is_approved: bool = env
+            .storage()
+            .persistent()
+            .get(&approved_key)
+            .unwrap_or(false);
+        if !is_approved {
+            return Err(CrowdfundError::MilestoneNotApproved);
+        }
+
+        let withdrawn: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MilestoneWithdrawn(project_id, milestone_id))
+            .unwrap_or(0);
+        if withdrawn > 0 {
+            return Err(CrowdfundError::AlreadyWithdrawn);
+        }
+
+        env.storage().persistent().set(&approved_key, &false);
+        let count_key = DataKey::ApprovedMilestoneCount(project_id);
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&count_key, &count.saturating_sub(1));
+
+        events::admin_action(&env, "revoke_milestone", &admin);
+        events::MilestoneRevokedEvent {
+            admin,
+            project_id,
+            milestone_id,
+        }
+        .emit(&env);
+
+        Ok(())
+    }
+
+    /// Start a vote for a milestone approval
+    pub fn start_milestone_vote(
+        env: Env,
+        project_id: u64,
+        milestone_id: u32,
+        duration_seconds: u64,
+    ) -> Result<(), CrowdfundError> {
+        // Get project
+        let mut project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        // Only project owner can start a vote
+        project.owner.require_auth();
+
+        // Check if already approved
+        let is_approved: bool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MilestoneApproved(project_id, milestone_id))
+            .unwrap_or(false);
+        if is_approved {
+            return Err(CrowdfundError::MilestoneAlreadyApproved);
+        }
+
+        // Set voting window
+        let end_time = env.ledger().timestamp() + duration_seconds;
+        env.storage().persistent().set(
+            &DataKey::MilestoneVoteWindow(project_id, milestone_id),
+            &end_time,
+        );
+        project.vote_open_until = project.vote_open_until.max(end_time);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id), &project);
+
+        // Reset votes for this milestone if needed (though they should be 0)
+        env.storage().persistent().set(
+            &DataKey::MilestoneVotesFor(project_id, milestone_id),
+            &0i128,
+        );
+        env.storage().persistent().set(
+            &DataKey::MilestoneVotesAgainst(project_id, milestone_id),
+            &0i128,
+        );
+
+        // Emit event
+        events::MilestoneVoteStartedEvent {
+            project_id,
+            milestone_id,
+            end_time,
+        }
+        .emit(&env);
+
+        Ok(())
+    }
+
+    /// Cast a vote for a milestone
+    pub fn vote_milestone(
+        env: Env,
+        voter: Address,
+        project_id: u64,
+        milestone_id: u32,
+        support: bool,
+    ) -> Result<(), CrowdfundError> {
+        voter.require_auth();
+
+        // Check voting window
+        let end_time: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MilestoneVoteWindow(project_id, milestone_id))
+            .ok_or(CrowdfundError::VotingWindowNotStarted)?;
+
+        if env.ledger().timestamp() > end_time {
+            return Err(CrowdfundError::VotingWindowClosed);
+        }
+
+        // Check if already voted
+        if env.storage().persistent().has(&DataKey::MilestoneVote(
+            project_id,
+            milestone_id,
+            voter.clone(),
+        )) {
+            return Err(CrowdfundError::AlreadyVoted);
+        }
+
+        // Get project (its token determines how weights are normalized)
+        let project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        // Get contribution weight
+        let contribution: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(project_id, voter.clone()))
+            .unwrap_or(0);
+
+        if contribution <= 0 {
+            return Err(CrowdfundError::InsufficientContributionToVote);
+        }
+
+        let weight = Self::normalize_amount(&env, &project.token_address, contribution);
+
+        // Update vote count
+        if support {
+            let current_for: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::MilestoneVotesFor(project_id, milestone_id))
+                .unwrap_or(0);
+            env.storage().persistent().set(
+                &DataKey::MilestoneVotesFor(project_id, milestone_id),
+                &(current_for + weight),
+            );
+        } else {
+            let current_against: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::MilestoneVotesAgainst(project_id, milestone_id))
+                .unwrap_or(0);
+            env.storage().persistent().set(
+                &DataKey::MilestoneVotesAgainst(project_id, milestone_id),
+                &(current_against + weight),
+            );
+        }
+
+        // Mark as voted
+        env.storage().persistent().set(
+            &DataKey::MilestoneVote(project_id, milestone_id, voter.clone()),
+            &true,
+        );
+
+        // Emit event
+        events::VoteCastEvent {
+            project_id,
+            milestone_id,
+            voter,
+            weight,
+            support,
+        }
+        .emit(&env);
+
+        // Auto-approve if threshold met (> 50% of total deposited)
+        // A passing vote waits out the approval cooldown like an admin approval;
+        // `finalize_milestone_vote` approves it once the cooldown ends
+        if Self::vote_passed(&env, &project, milestone_id)
+            && !Self::approval_cooldown_active(&env, project_id)
+        {
+            Self::mark_milestone_approved(&env, project_id, milestone_id);
+            events::MilestoneApprovedByVoteEvent {
+                project_id,
+                milestone_id,
+            }
+            .emit(&env);
+        }
+
+        Ok(())
+    }
+
+    /// Approve a milestone whose vote passed while the approval cooldown was
+    /// active (anyone can call once the cooldown has elapsed)
+    pub fn finalize_milestone_vote(
+        env: Env,
+        project_id: u64,
+        milestone_id: u32,
+    ) -> Result<(), CrowdfundError> {
+        let project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::MilestoneVoteWindow(project_id, milestone_id))
+        {
+            return Err(CrowdfundError::VotingWindowNotStarted);
+        }
+        let is_approved: bool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MilestoneApproved(project_id, milestone_id))
+            .unwrap_or(false);
+        if is_approved {
+            return Err(CrowdfundError::MilestoneAlreadyApproved);
+        }
+        if !Self::vote_passed(&env, &project, milestone_id) {
+            return Err(CrowdfundError::VoteNotPassed);
+        }
+        if Self::approval_cooldown_active(&env, project_id) {
+            return Err(CrowdfundError::ApprovalCooldownActive);
+        }
+
+        Self::mark_milestone_approved(&env, project_id, milestone_id);
+        events::MilestoneApprovedByVoteEvent {
+            project_id,
+            milestone_id,
+        }
+        .emit(&env);
+
+        Ok(())
+    }
+
+    /// Whether votes in favour of a milestone exceed half of the project's
+    /// total deposited weight
+    fn vote_passed(env: &Env, project: &ProjectData, milestone_id: u32) -> bool {
+        let current_for: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MilestoneVotesFor(project.id, milestone_id))
+            .unwrap_or(0);
+        let total_weight =
+            Self::normalize_amount(env, &project.token_address, project.total_deposited);
+        current_for > total_weight / 2
+    }
+
+    /// Get a token's decimals, caching the value on first use
+    fn token_decimals(env: &Env, token_address: &Address) -> u32 {
+        let key = DataKey::TokenDecimals(token_address.clone());
+        if let Some(decimals) = env.storage().persistent().get(&key) {
+            return decimals;
+        }
+        let decimals = TokenClient::new(env, token_address).decimals();
+        env.storage().persistent().set(&key, &decimals);
+        decimals
+    }
+
+    /// Scale a raw token amount to NORMALIZED_DECIMALS when weight
+    /// normalization is enabled, so amounts in different tokens compare fairly
+    fn normalize_amount(env: &Env, token_address: &Address, amount: i128) -> i128 {
+        let normalize: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::NormalizeWeights)
+            .unwrap_or(false);
+        if !normalize {
+            return amount;
+        }
+
+        let decimals = Self::token_decimals(env, token_address);
+        if decimals <= NORMALIZED_DECIMALS {
+            amount * 10i128.pow(NORMALIZED_DECIMALS - decimals)
+        } else {
+            amount / 10i128.pow(decimals - NORMALIZED_DECIMALS)
+        }
+    }
+
+    /// Enable or disable normalizing contribution weights by token decimals (admin only)
+    pub fn set_normalize_weights(
+        env: Env,
+        admin: Address,
+        enabled: bool,
+    ) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::NormalizeWeights, &enabled);
+        events::admin_action(&env, "set_normalize_weights", &admin);
+        Ok(())
+    }
+
+    /// Enable or disable event emission (admin only, enabled by default)
+    pub fn set_emit_events(env: Env, admin: Address, enabled: bool) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::EmitEvents, &enabled);
+        events::admin_action(&env, "set_emit_events", &admin);
+        Ok(())
+    }
+
+    /// Enable or disable archiving projects with every milestone approved once
+    /// fully withdrawn (admin only)
+    pub fn set_auto_archive(env: Env, admin: Address, enabled: bool) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::AutoArchive, &enabled);
+        events::admin_action(&env, "set_auto_archive", &admin);
+        Ok(())
+    }
+
+    /// Check whether completed projects are archived once fully withdrawn
+    pub fn get_auto_archive(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::AutoArchive)
+            .unwrap_or(false)
+    }
+
+    /// Check whether the vault publishes events
+    pub fn get_emit_events(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::EmitEvents)
+            .unwrap_or(true)
+    }
+
+    /// Get the vote weight a contributor would cast on a project's milestones
+    pub fn get_vote_weight(
+        env: Env,
+        project_id: u64,
+        voter: Address,
+    ) -> Result<i128, CrowdfundError> {
+        let project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        let contribution: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(project_id, voter))
+            .unwrap_or(0);
+
+        Ok(Self::normalize_amount(
+            &env,
+            &project.token_address,
+            contribution,
+        ))
+    }
+
+    /// Largest amount the owner may withdraw right now, checking every gate
+    /// `withdraw` enforces: pause, activity, a failed deadline, the release cap
+    /// or milestone approval, the withdrawal delay and the project balance
+    /// Without a `milestone_id`, any approved milestone opens the gate
+    fn withdraw_limit(
+        env: &Env,
+        project: &mut ProjectData,
+        milestone_id: Option<u32>,
+    ) -> Result<i128, CrowdfundError> {
+        let is_paused: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false);
+        if is_paused {
+            return Err(CrowdfundError::ContractPaused);
+        }
+        if !project.is_active {
+            return Err(CrowdfundError::ProjectNotActive);
+        }
+
+        // Deposits of a failed campaign are held for refunds
+        if Self::deadline_status(env, project)? == DeadlineStatus::Failed {
+            return Err(CrowdfundError::ProjectFailed);
+        }
+
+        // A release caps cumulative withdrawals for its milestone; otherwise
+        // the milestone must be approved
+        let released = Self::release_remaining(project);
+        if released.is_some() {
+            if milestone_id.is_some_and(|milestone_id| milestone_id != project.release_milestone) {
+                return Err(CrowdfundError::MilestoneNotApproved);
+            }
+        } else {
+            let is_approved = match milestone_id {
+                Some(milestone_id) => env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::MilestoneApproved(project.id, milestone_id))
+                    .unwrap_or(false),
+                None => {
+                    env.storage()
+                        .persistent()
+                        .get::<_, u32>(&DataKey::ApprovedMilestoneCount(project.id))
+                        .unwrap_or(0)
+                        > 0
+                }
+            };
+            if !is_approved {
+                return Err(CrowdfundError::MilestoneNotApproved);
+            }
+        }
+
+        let delayed = env
+            .storage()
+            .persistent()
+            .get::<_, u64>(&DataKey::LastMilestoneApproval(project.id))
+            .is_some_and(|last| {
+                env.ledger().timestamp() < last.saturating_add(project.withdrawal_delay)
+            });
+        if delayed {
+            return Err(CrowdfundError::WithdrawalDelayActive);
+        }
+
+        let balance: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProjectBalance(
+                project.id,
+                project.token_address.clone(),
+            ))
+            .unwrap_or(0);
+        Ok(balance.min(released.unwrap_or(balance)))
+    }
+
+    /// Withdraw funds from a project (owner only, requires milestone approval)
+    pub fn withdraw(
+        env: Env,
+        project_id: u64,
+        milestone_id: u32,
+        amount: i128,
+    ) -> Result<(), CrowdfundError> {
+        // Check if contract is initialized
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(CrowdfundError::NotInitialized);
+        }
+
+        // Get project
+        let mut project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        // Require owner authorization
+        project.owner.require_auth();
+
+        let limit = Self::withdraw_limit(&env, &mut project, Some(milestone_id))?;
+
+        // Validate amount
+        if amount == 0 {
+            return Err(CrowdfundError::ZeroAmount);
+        }
+        if amount < 0 {
+            return Err(CrowdfundError::InvalidAmount);
+        }
+        if amount > limit {
+            return Err(CrowdfundError::InsufficientBalance);
+        }
+
+        let balance_key = DataKey::ProjectBalance(project_id, project.token_address.clone());
+        let current_balance: i128 = env.storage().persistent().get(&balance_key).unwrap_or(0);
+
+        // Split off the platform fee, if one is configured
+        let contract_address = env.current_contract_address();
+        let fee = Self::withdrawal_fee(&env, amount);
+        Self::pay_fee(&env, &project.token_address, fee)?;
+
+        // Split the royalty off what remains after the platform fee
+        let net = amount - fee;
+        let royalty = net * project.royalty_bps as i128 / BPS_DENOMINATOR;
+        if let Some(royalty_recipient) = project.royalty_recipient.as_ref() {
+            if royalty > 0 {
+                token::transfer(
+                    &env,
+                    &project.token_address,
+                    &contract_address,
+                    royalty_recipient,
+                    &royalty,
+                )?;
+            }
+        }
+
+        // Transfer remaining tokens from contract to owner
+        token::transfer(
+            &env,
+            &project.token_address,
+            &contract_address,
+            &project.owner,
+            &(net - royalty),
+        )?;
+
+        // Update project balance
+        env.storage()
+            .persistent()
+            .set(&balance_key, &(current_balance - amount));
+
+        // Update project total withdrawn, archiving a completed project once drained
+        project.total_withdrawn += amount;
+        let archive = current_balance == amount
+            && Self::get_auto_archive(env.clone())
+            && Self::all_milestones_approved(&env, &project);
+        if archive {
+            project.is_active = false;
+            env.storage().persistent().set(
+                &DataKey::ProjectStatus(project_id),
+                &Symbol::new(&env, "ARCHIVED"),
+            );
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Project(project_id), &project);
+
+        // Track what was withdrawn against this milestone
+        let milestone_withdrawn_key = DataKey::MilestoneWithdrawn(project_id, milestone_id);
+        let milestone_withdrawn: i128 = env
+            .storage()
+            .persistent()
+            .get(&milestone_withdrawn_key)
+            .unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&milestone_withdrawn_key, &(milestone_withdrawn + amount));
+
+        // Emit withdraw event
+        events::WithdrawEvent {
+            owner: project.owner,
+            project_id,
+            amount,
+            schema_version: events::EVENT_SCHEMA_VERSION,
+        }
+        .emit(&env);
+
+        if archive {
+            events::ProjectArchivedEvent { project_id }.emit(&env);
+        }
+
+        Ok(())
+    }
+
+    /// Register a new contributor
+    pub fn register_contributor(env: Env, contributor: Address) -> Result<(), CrowdfundError> {
+        // Require contributor authorization
+        contributor.require_auth();
+
+        // Check if already registered
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::RegisteredContributor(contributor.clone()))
+        {
+            return Err(CrowdfundError::AlreadyRegistered);
+        }
+
+        // Store registration
+        env.storage()
+            .persistent()
+            .set(&DataKey::RegisteredContributor(contributor.clone()), &true);
+
+        // Initialize reputation
+        env.storage()
+            .persistent()
+            .set(&DataKey::Reputation(contributor.clone()), &0i128);
+
+        // Emit registration event
+        events::ContributorRegisteredEvent { contributor }.emit(&env);
+
+        Ok(())
+    }
+
+    /// Update contributor reputation (admin only for now, or could be internal)
+    pub fn update_reputation(
+        env: Env,
+        admin: Address,
+        contributor: Address,
+        change: i128,
+    ) -> Result<(), CrowdfundError> {
+        // Verify admin (single check with helper)
+        Self::verify_admin(&env, &admin)?;
+
+        // Check if contributor is registered
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::RegisteredContributor(contributor.clone()))
+        {
+            return Err(CrowdfundError::ContributorNotFound);
+        }
+
+        // Get current reputation
+        let old_reputation: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Reputation(contributor.clone()))
+            .unwrap_or(0);
+        let new_reputation = old_reputation + change;
+
+        // Store new reputation
+        env.storage()
+            .persistent()
+            .set(&DataKey::Reputation(contributor.clone()), &new_reputation);
+
+        // Emit reputation change event
+        events::ReputationUpdatedEvent {
+            contributor,
+            old_reputation,
+            new_reputation,
+            schema_version: events::EVENT_SCHEMA_VERSION,
+        }
+        .emit(&env);
+
+        events::admin_action(&env, "update_reputation", &admin);
+        Ok(())
+    }
+
+    /// Get contributor reputation
+    pub fn get_reputation(env: Env, contributor: Address) -> Result<i128, CrowdfundError> {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::RegisteredContributor(contributor.clone()))
+        {
+            return Err(CrowdfundError::ContributorNotFound);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::Reputation(contributor))
+            .unwrap_or(0))
+    }
+
+    /// Get project data
+    pub fn get_project(env: Env, project_id: u64) -> Result<ProjectData, CrowdfundError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)
+    }
+
+    /// Get project data by its unique name
+    pub fn get_project_by_name(env: Env, name: Symbol) -> Result<ProjectData, CrowdfundError> {
+        let project_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProjectByName(name))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+        Self::get_project(env, project_id)
+    }
+
+    /// Get project balance
+    pub fn get_balance(env: Env, project_id: u64) -> Result<i128, CrowdfundError> {
+        // Get project to get token address (use destructuring to avoid full clone)
+        let project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        let balance_key = DataKey::ProjectBalance(project_id, project.token_address);
+        Ok(env.storage().persistent().get(&balance_key).unwrap_or(0))
+    }
+
+    /// Get `(total_deposited, total_withdrawn, current_balance)` of a project in one call
+    pub fn get_flows(env: Env, project_id: u64) -> Result<(i128, i128, i128), CrowdfundError> {
+        let project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        let balance: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProjectBalance(project_id, project.token_address))
+            .unwrap_or(0);
+
+        Ok((project.total_deposited, project.total_withdrawn, balance))
+    }
+
+    /// Get the largest `amount` that `withdraw` would accept right now
+    ///
+    /// Returns 0 while any gate of `withdraw` is closed: the vault is paused,
+    /// the project is inactive or failed, no milestone is approved or release
+    /// set, or the withdrawal delay is still running; otherwise the gross
+    /// project balance, fees included, capped at what the release still allows.
+    pub fn withdrawable_now(env: Env, project_id: u64) -> Result<i128, CrowdfundError> {
+        let mut project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        Ok(Self::withdraw_limit(&env, &mut project, None).unwrap_or(0))
+    }
+
+    /// Get the amount the owner would currently receive by withdrawing everything
+    /// This is `withdrawable_now` with the platform fee deducted, so it is 0
+    /// whenever any gate of `withdraw` is closed
+    pub fn available_to_withdraw(env: Env, project_id: u64) -> Result<i128, CrowdfundError> {
+        let mut project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        let balance = Self::withdraw_limit(&env, &mut project, None).unwrap_or(0);
+        Ok(balance - Self::withdrawal_fee(&env, balance))
+    }
+
+    /// Check whether a project has reached its funding target
+    pub fn is_target_met(env: Env, project_id: u64) -> Result<bool, CrowdfundError> {
+        let project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        match project.outcome {
+            DeadlineStatus::Open => Self::target_met(&env, &project),
+            outcome => Ok(outcome == DeadlineStatus::Succeeded),
+        }
+    }
+
+    /// Get whether a project is still open, or succeeded or failed at its deadline
+    pub fn get_deadline_status(
+        env: Env,
+        project_id: u64,
+    ) -> Result<DeadlineStatus, CrowdfundError> {
+        let mut project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        Self::deadline_status(&env, &mut project)
+    }
+
+    /// Get the number of seconds left until a project's deadline
+    /// Returns 0 once the deadline has passed or when no deadline is set
+    pub fn time_to_deadline(env: Env, project_id: u64) -> Result<u64, CrowdfundError> {
+        let project: ProjectData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        Ok(project.deadline.saturating_sub(env.ledger().timestamp()))
+    }
+
+    /// Get the ids of all active projects
+    pub fn get_active_projects(env: Env) -> Vec<u64> {
+        let project_count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProjectId)
+            .unwrap_or(0);
+
+        let mut active = Vec::new(&env);
+        for project_id in 0..project_count {
+            if let Some(project) = env
+                .storage()
+                .persistent()
+                .get::<_, ProjectData>(&DataKey::Project(project_id))
+            {
+                if project.is_active {
+                    active.push_back(project_id);
+                }
+            }
+        }
+        active
+    }
+
+    /// Get the ids of active projects whose funding closes (deadline plus
+    /// grace period) at or before `timestamp` and whose target has not been met
+    ///
+    /// Projects without a deadline are never included.
+    pub fn projects_ending_before(env: Env, timestamp: u64) -> Vec<u64> {
+        let project_count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProjectId)
+            .unwrap_or(0);
+
+        let mut ending = Vec::new(&env);
+        for project_id in 0..project_count {
+            if let Some(project) = env
+                .storage()
+                .persistent()
+                .get::<_, ProjectData>(&DataKey::Project(project_id))
+            {
+                if project.is_active
+                    && project.deadline != 0
+                    && Self::funding_closes_at(&project) <= timestamp
+                    && match project.outcome {
+                        DeadlineStatus::Open => Self::target_met(&env, &project) == Ok(false),
+                        outcome => outcome == DeadlineStatus::Failed,
+                    }
+                {
+                    ending.push_back(project_id);
+                }
+            }
+        }
+        ending
+    }
+
+    /// Check if milestone is approved for a project
+    pub fn is_milestone_approved(
+        env: Env,
+        project_id: u64,
+        milestone_id: u32,
+    ) -> Result<bool, CrowdfundError> {
+        // Check if project exists (single get instead of has + get)
+        env.storage()
+            .persistent()
+            .get::<_, ProjectData>(&DataKey::Project(project_id))
+            .ok_or(CrowdfundError::ProjectNotFound)?;
+
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::MilestoneApproved(project_id, milestone_id))
+            .unwrap_or(false))
+    }
+
+    /// Get admin address
+    pub fn get_admin(env: Env) -> Result<Address, CrowdfundError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CrowdfundError::NotInitialized)
+    }
+
+    /// Check whether `address` is the stored admin (false before initialization)
+    pub fn is_admin(env: Env, address: Address) -> bool {
+        env.storage()
+            .instance()
+            .get::<_, Address>(&DataKey::Admin)
+            .is_some_and(|admin| admin == address)
+    }
+
+    /// Get a compact snapshot of the vault's global state
+    pub fn get_state(env: Env) -> Result<VaultState, CrowdfundError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CrowdfundError::NotInitialized)?;
+
+        Ok(VaultState {
+            admin,
+            project_count: env
+                .storage()
+                .instance()
+                .get(&DataKey::NextProjectId)
+                .unwrap_or(0),
+            paused: env
+                .storage()
+                .instance()
+                .get(&DataKey::Paused)
+                .unwrap_or(false),
+            fee_bps: env.storage().instance().get(&DataKey::FeeBps).unwrap_or(0),
+        })
+    }
+
+    /// Set the smallest target a new project may have (admin only, 0 = no floor)
+    pub fn set_min_target(
+        env: Env,
+        admin: Address,
+        min_target: i128,
+    ) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+        if min_target < 0 {
+            return Err(CrowdfundError::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MinTarget, &min_target);
+        events::admin_action(&env, "set_min_target", &admin);
+        Ok(())
+    }
+
+    /// Get the smallest target a new project may have (0 = no floor)
+    pub fn get_min_target(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinTarget)
+            .unwrap_or(0)
+    }
+
+    /// Set the maximum number of active projects per owner (admin only, 0 = unlimited)
+    pub fn set_max_projects_per_owner(
+        env: Env,
+        admin: Address,
+        max_projects: u32,
+    ) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxProjectsPerOwner, &max_projects);
+        events::admin_action(&env, "set_max_projects_per_owner", &admin);
+        Ok(())
+    }
+
+    /// Get the maximum number of active projects per owner (0 = unlimited)
+    pub fn get_max_projects_per_owner(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxProjectsPerOwner)
+            .unwrap_or(0)
+    }
+
+    /// Set the minimum time between milestone approvals of a project (admin only, 0 = none)
+    pub fn set_milestone_cooldown(
+        env: Env,
+        admin: Address,
+        milestone_cooldown: u64,
+    ) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::MilestoneCooldown, &milestone_cooldown);
+        events::admin_action(&env, "set_milestone_cooldown", &admin);
+        Ok(())
+    }
+
+    /// Get the minimum time between milestone approvals of a project (0 = none)
+    pub fn get_milestone_cooldown(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MilestoneCooldown)
+            .unwrap_or(0)
+    }
+
+    /// Get the ids of all projects created by an owner
+    pub fn get_owner_projects(env: Env, owner: Address) -> Vec<u64> {
         env.storage()
             .persistent()
-            .set(&balance_key, &(current_balance - amount));
+            .get(&DataKey::OwnerProjects(owner))
+            .unwrap_or(vec![&env])
+    }
 
-        // Update project total withdrawn
-        project.total_withdrawn += amount;
+    /// Get the withdrawal fee in basis points (0 if not configured)
+    pub fn get_fee_bps(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::FeeBps).unwrap_or(0)
+    }
+
+    /// Get the withdrawal fee recipient, if configured
+    pub fn get_fee_recipient(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::FeeRecipient)
+    }
+
+    /// Get the total deposit, withdrawal and refund fees collected in `token`
+    pub fn get_fees_collected(env: Env, token: Address) -> i128 {
         env.storage()
             .persistent()
-            .set(&DataKey::Project(project_id), &project);
+            .get(&DataKey::FeesCollected(token))
+            .unwrap_or(0)
+    }
 
-        // Emit withdraw event
-        events::WithdrawEvent {
-            owner: project.owner,
-            project_id,
-            amount,
+    /// Denominate project targets in a reference unit priced by an oracle (admin only)
+    /// Passing `None` restores raw token targets. Projects keep the denomination
+    /// configured when they were created.
+    pub fn set_price_oracle(
+        env: Env,
+        admin: Address,
+        price_oracle: Option<Address>,
+        reference_denom: Symbol,
+    ) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+        match price_oracle {
+            Some(price_oracle) => {
+                env.storage()
+                    .instance()
+                    .set(&DataKey::PriceOracle, &price_oracle);
+                env.storage()
+                    .instance()
+                    .set(&DataKey::ReferenceDenom, &reference_denom);
+            }
+            None => {
+                env.storage().instance().remove(&DataKey::PriceOracle);
+                env.storage().instance().remove(&DataKey::ReferenceDenom);
+            }
         }
-        .publish(&env);
-
+        events::admin_action(&env, "set_price_oracle", &admin);
         Ok(())
     }
 
-    /// Register a new contributor
-    pub fn register_contributor(env: Env, contributor: Address) -> Result<(), CrowdfundError> {
-        // Require contributor authorization
-        contributor.require_auth();
+    /// Set the fee skimmed from each deposit in basis points (admin only)
+    /// The fee goes to the configured fee recipient and the project is credited the net
+    pub fn set_deposit_fee_bps(
+        env: Env,
+        admin: Address,
+        deposit_fee_bps: u32,
+    ) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
 
-        // Check if already registered
-        if env
-            .storage()
-            .persistent()
-            .has(&DataKey::RegisteredContributor(contributor.clone()))
+        if deposit_fee_bps > MAX_FEE_BPS
+            || (deposit_fee_bps > 0 && !env.storage().instance().has(&DataKey::FeeRecipient))
         {
-            return Err(CrowdfundError::AlreadyRegistered);
+            return Err(CrowdfundError::InvalidFee);
         }
 
-        // Store registration
-        env.storage()
-            .persistent()
-            .set(&DataKey::RegisteredContributor(contributor.clone()), &true);
-
-        // Initialize reputation
         env.storage()
-            .persistent()
-            .set(&DataKey::Reputation(contributor.clone()), &0i128);
-
-        // Emit registration event
-        events::ContributorRegisteredEvent { contributor }.publish(&env);
+            .instance()
+            .set(&DataKey::DepositFeeBps, &deposit_fee_bps);
+        events::admin_action(&env, "set_deposit_fee_bps", &admin);
+        Ok(())
+    }
 
+    /// Add a token to the funding token allowlist (admin only)
+    pub fn allow_token(env: Env, admin: Address, token: Address) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+        Self::set_token_allowed(&env, &token, true);
+        events::admin_action(&env, "allow_token", &admin);
         Ok(())
     }
 
-    /// Update contributor reputation (admin only for now, or could be internal)
-    pub fn update_reputation(
-        env: Env,
-        admin: Address,
-        contributor: Address,
-        change: i128,
-    ) -> Result<(), CrowdfundError> {
-        // Verify admin (single check with helper)
+    /// Remove a token from the funding token allowlist (admin only)
+    ///
+    /// Existing projects funded in the token are unaffected.
+    pub fn disallow_token(env: Env, admin: Address, token: Address) -> Result<(), CrowdfundError> {
         Self::verify_admin(&env, &admin)?;
+        Self::set_token_allowed(&env, &token, false);
+        events::admin_action(&env, "disallow_token", &admin);
+        Ok(())
+    }
 
-        // Check if contributor is registered
-        if !env
+    /// Check whether new projects may be funded in `token`
+    ///
+    /// Every token is allowed while the allowlist is empty.
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        let count: u32 = env
             .storage()
-            .persistent()
-            .has(&DataKey::RegisteredContributor(contributor.clone()))
-        {
-            return Err(CrowdfundError::ContributorNotFound);
+            .instance()
+            .get(&DataKey::AllowedTokenCount)
+            .unwrap_or(0);
+        count == 0
+            || env
+                .storage()
+                .persistent()
+                .get(&DataKey::AllowedToken(token))
+                .unwrap_or(false)
+    }
+
+    fn set_token_allowed(env: &Env, token: &Address, allowed: bool) {
+        let key = DataKey::AllowedToken(token.clone());
+        let was_allowed: bool = env.storage().persistent().get(&key).unwrap_or(false);
+        if was_allowed == allowed {
+            return;
         }
 
-        // Get current reputation
-        let old_reputation: i128 = env
+        let count: u32 = env
             .storage()
-            .persistent()
-            .get(&DataKey::Reputation(contributor.clone()))
+            .instance()
+            .get(&DataKey::AllowedTokenCount)
             .unwrap_or(0);
-        let new_reputation = old_reputation + change;
-
-        // Store new reputation
+        let count = if allowed { count + 1 } else { count - 1 };
         env.storage()
-            .persistent()
-            .set(&DataKey::Reputation(contributor.clone()), &new_reputation);
+            .instance()
+            .set(&DataKey::AllowedTokenCount, &count);
 
-        // Emit reputation change event
-        events::ReputationUpdatedEvent {
-            contributor,
-            old_reputation,
-            new_reputation,
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
         }
-        .publish(&env);
-
-        Ok(())
     }
 
-    /// Get contributor reputation
-    pub fn get_reputation(env: Env, contributor: Address) -> Result<i128, CrowdfundError> {
-        if !env
-            .storage()
-            .persistent()
-            .has(&DataKey::RegisteredContributor(contributor.clone()))
+    /// Set the fee kept from each refunded contribution in basis points (admin only)
+    /// The fee goes to the configured fee recipient and the contributor receives the net
+    pub fn set_refund_fee_bps(
+        env: Env,
+        admin: Address,
+        refund_fee_bps: u32,
+    ) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+
+        if refund_fee_bps > MAX_FEE_BPS
+            || (refund_fee_bps > 0 && !env.storage().instance().has(&DataKey::FeeRecipient))
         {
-            return Err(CrowdfundError::ContributorNotFound);
+            return Err(CrowdfundError::InvalidFee);
         }
-        Ok(env
-            .storage()
-            .persistent()
-            .get(&DataKey::Reputation(contributor))
-            .unwrap_or(0))
-    }
 
-    /// Get project data
-    pub fn get_project(env: Env, project_id: u64) -> Result<ProjectData, CrowdfundError> {
         env.storage()
-            .persistent()
-            .get(&DataKey::Project(project_id))
-            .ok_or(CrowdfundError::ProjectNotFound)
+            .instance()
+            .set(&DataKey::RefundFeeBps, &refund_fee_bps);
+        events::admin_action(&env, "set_refund_fee_bps", &admin);
+        Ok(())
     }
 
-    /// Get project balance
-    pub fn get_balance(env: Env, project_id: u64) -> Result<i128, CrowdfundError> {
-        // Get project to get token address (use destructuring to avoid full clone)
-        let project: ProjectData = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Project(project_id))
-            .ok_or(CrowdfundError::ProjectNotFound)?;
+    /// Get the refund fee in basis points (0 if not configured)
+    pub fn get_refund_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RefundFeeBps)
+            .unwrap_or(0)
+    }
 
-        let balance_key = DataKey::ProjectBalance(project_id, project.token_address);
-        Ok(env.storage().persistent().get(&balance_key).unwrap_or(0))
+    /// Get the deposit fee in basis points (0 if not configured)
+    pub fn get_deposit_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::DepositFeeBps)
+            .unwrap_or(0)
     }
 
-    /// Check if milestone is approved for a project
-    pub fn is_milestone_approved(
+    /// Configure platform matching of deposits into projects whose owner's
+    /// registry reputation exceeds `min_reputation` (admin only, 0 bps disables)
+    /// The match is pulled from `match_pool` through the allowance it grants the vault
+    pub fn set_deposit_match(
         env: Env,
-        project_id: u64,
-        milestone_id: u32,
-    ) -> Result<bool, CrowdfundError> {
-        // Check if project exists (single get instead of has + get)
+        admin: Address,
+        match_bps: u32,
+        match_pool: Address,
+        min_reputation: u64,
+    ) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+
+        if match_bps as i128 > BPS_DENOMINATOR {
+            return Err(CrowdfundError::InvalidAmount);
+        }
+
         env.storage()
-            .persistent()
-            .get::<_, ProjectData>(&DataKey::Project(project_id))
-            .ok_or(CrowdfundError::ProjectNotFound)?;
+            .instance()
+            .set(&DataKey::DepositMatchBps, &match_bps);
+        env.storage()
+            .instance()
+            .set(&DataKey::DepositMatchPool, &match_pool);
+        env.storage()
+            .instance()
+            .set(&DataKey::DepositMatchMinReputation, &min_reputation);
+        events::admin_action(&env, "set_deposit_match", &admin);
+        Ok(())
+    }
 
-        Ok(env
-            .storage()
-            .persistent()
-            .get(&DataKey::MilestoneApproved(project_id, milestone_id))
-            .unwrap_or(false))
+    /// Flag the fee recipient as a treasury contract (admin only)
+    /// When set, every fee the vault pays out (deposit, withdrawal and refund
+    /// fees) is followed by a `receive_fee(token, amount)` call on the recipient
+    pub fn set_fee_recipient_is_contract(
+        env: Env,
+        admin: Address,
+        is_contract: bool,
+    ) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeRecipientIsContract, &is_contract);
+        events::admin_action(&env, "set_fee_recipient_is_contract", &admin);
+        Ok(())
     }
 
-    /// Get admin address
-    pub fn get_admin(env: Env) -> Result<Address, CrowdfundError> {
+    /// Check whether the fee recipient is called as a treasury contract
+    pub fn get_fee_recipient_is_contract(env: Env) -> bool {
         env.storage()
             .instance()
-            .get(&DataKey::Admin)
-            .ok_or(CrowdfundError::NotInitialized)
+            .get(&DataKey::FeeRecipientIsContract)
+            .unwrap_or(false)
+    }
+
+    /// Get the referenced contributor registry, if configured
+    pub fn get_registry(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Registry)
     }
 
     /// Fund the matching pool (admin only)
@@ -884,6 +3026,7 @@ impl CrowdfundVaultContract {
             .persistent()
             .set(&pool_key, &(current_pool + amount));
 
+        events::admin_action(&env, "fund_matching_pool", &admin);
         Ok(())
     }
 
@@ -1041,6 +3184,14 @@ impl CrowdfundVaultContract {
             .unwrap_or(0))
     }
 
+    /// Get the ids of all projects a contributor has funded
+    pub fn get_contributor_projects(env: Env, contributor: Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ContributorProjects(contributor))
+            .unwrap_or(vec![&env])
+    }
+
     /// Get contributor count for a project
     pub fn get_contributor_count(env: Env, project_id: u64) -> Result<u32, CrowdfundError> {
         // Check if contract is initialized
@@ -1080,6 +3231,7 @@ impl CrowdfundVaultContract {
         // Set pause state in instance storage (cheaper than persistent)
         env.storage().instance().set(&DataKey::Paused, &true);
 
+        events::admin_action(&env, "pause", &admin);
         events::ContractPauseEvent {
             admin,
             paused: true,
@@ -1108,6 +3260,7 @@ impl CrowdfundVaultContract {
         // Set pause state in instance storage (cheaper than persistent)
         env.storage().instance().set(&DataKey::Paused, &false);
 
+        events::admin_action(&env, "unpause", &admin);
         events::ContractUnpauseEvent {
             admin,
             paused: false,
@@ -1118,6 +3271,45 @@ impl CrowdfundVaultContract {
         Ok(true)
     }
 
+    /// Stop new deposits while withdrawals and refunds stay open (admin only)
+    pub fn pause_deposits(env: Env, admin: Address) -> Result<(), CrowdfundError> {
+        Self::set_deposits_paused(&env, admin, true)
+    }
+
+    /// Accept deposits again after `pause_deposits` (admin only)
+    pub fn resume_deposits(env: Env, admin: Address) -> Result<(), CrowdfundError> {
+        Self::set_deposits_paused(&env, admin, false)
+    }
+
+    /// Check whether deposits alone are paused
+    pub fn is_deposits_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::DepositsPaused)
+            .unwrap_or(false)
+    }
+
+    fn set_deposits_paused(env: &Env, admin: Address, paused: bool) -> Result<(), CrowdfundError> {
+        Self::verify_admin(env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::DepositsPaused, &paused);
+
+        let action = if paused {
+            "pause_deposits"
+        } else {
+            "resume_deposits"
+        };
+        events::admin_action(env, action, &admin);
+        events::DepositPauseEvent {
+            admin,
+            paused,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(env);
+        Ok(())
+    }
+
     pub fn require_not_paused(env: &Env) -> bool {
         env.storage()
             .instance()
@@ -1138,6 +3330,7 @@ impl CrowdfundVaultContract {
 
         env.deployer()
             .update_current_contract_wasm(new_wasm_hash.clone());
+        events::admin_action(&env, "upgrade", &caller);
         events::UpgradedEvent {
             admin: caller,
             new_wasm_hash,
@@ -1146,6 +3339,134 @@ impl CrowdfundVaultContract {
         Ok(())
     }
 
+    /// Migrate storage to the current layout after an upgrade (admin only)
+    ///
+    /// Call once after `upgrade` installs WASM with a newer storage version;
+    /// each call bumps the version by one.
+    /// Rejects with `AlreadyMigrated` once storage is at [`STORAGE_VERSION`].
+    pub fn migrate(env: Env, admin: Address) -> Result<(), CrowdfundError> {
+        Self::verify_admin(&env, &admin)?;
+
+        let version = Self::get_version(env.clone());
+        if version >= STORAGE_VERSION {
+            return Err(CrowdfundError::AlreadyMigrated);
+        }
+
+        // Version 2 extended `ProjectData` and added the owner, name and
+        // contributor project indexes
+        if version < 2 {
+            Self::migrate_projects(&env);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Version, &(version + 1));
+
+        events::VersionBumpedEvent {
+            old_version: version,
+            new_version: version + 1,
+        }
+        .emit(&env);
+        events::admin_action(&env, "migrate", &admin);
+        Ok(())
+    }
+
+    /// Rewrite projects still stored as `ProjectDataV1` in the current layout
+    ///
+    /// Added fields start at their defaults, except the contributor count which
+    /// is taken from the contributor list. The owner, name and contributor
+    /// project indexes are backfilled for each rewritten project.
+    fn migrate_projects(env: &Env) {
+        let project_count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProjectId)
+            .unwrap_or(0);
+
+        for project_id in 0..project_count {
+            let key = DataKey::Project(project_id);
+            let Some(stored) = env.storage().persistent().get::<_, Map<Symbol, Val>>(&key) else {
+                continue;
+            };
+            if stored.len() != PROJECT_V1_FIELDS {
+                continue;
+            }
+            let legacy: ProjectDataV1 = env.storage().persistent().get(&key).unwrap();
+
+            let contributor_count: u32 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::ContributorCount(project_id))
+                .unwrap_or(0);
+            for i in 0..contributor_count {
+                if let Some(contributor) = env
+                    .storage()
+                    .persistent()
+                    .get::<_, Address>(&DataKey::Contributor(project_id, i))
+                {
+                    let projects_key = DataKey::ContributorProjects(contributor);
+                    let mut projects: Vec<u64> = env
+                        .storage()
+                        .persistent()
+                        .get(&projects_key)
+                        .unwrap_or(vec![env]);
+                    if !projects.contains(project_id) {
+                        projects.push_back(project_id);
+                        env.storage().persistent().set(&projects_key, &projects);
+                    }
+                }
+            }
+
+            let owner_projects_key = DataKey::OwnerProjects(legacy.owner.clone());
+            let mut owner_projects: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&owner_projects_key)
+                .unwrap_or(vec![env]);
+            if !owner_projects.contains(project_id) {
+                owner_projects.push_back(project_id);
+                env.storage()
+                    .persistent()
+                    .set(&owner_projects_key, &owner_projects);
+            }
+            let name_key = DataKey::ProjectByName(legacy.name.clone());
+            if !env.storage().persistent().has(&name_key) {
+                env.storage().persistent().set(&name_key, &project_id);
+            }
+
+            let project = ProjectData {
+                id: legacy.id,
+                owner: legacy.owner,
+                name: legacy.name,
+                target_amount: legacy.target_amount,
+                token_address: legacy.token_address,
+                total_deposited: legacy.total_deposited,
+                total_withdrawn: legacy.total_withdrawn,
+                is_active: legacy.is_active,
+                deadline: 0,
+                deposit_count: 0,
+                unique_contributors: contributor_count as u64,
+                royalty_bps: 0,
+                royalty_recipient: None,
+                private: false,
+                grace_period: 0,
+                deposit_lockup: 0,
+                release_bps: 0,
+                vote_open_until: 0,
+                reference_denom: None,
+                outcome: DeadlineStatus::Open,
+                withdrawal_delay: 0,
+                milestone_count: 1,
+                release_milestone: 0,
+            };
+            env.storage().persistent().set(&key, &project);
+        }
+    }
+
+    /// Get the storage version (1 for storage that predates versioning)
+    pub fn get_version(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Version).unwrap_or(1)
+    }
+
     /// Transfer the admin role to `new_admin`.
     ///
     /// Requires authorization from the current admin. Emits [`AdminChangedEvent`].
@@ -1158,6 +3479,7 @@ impl CrowdfundVaultContract {
         Self::verify_admin(&env, &current_admin)?;
 
         env.storage().instance().set(&DataKey::Admin, &new_admin);
+        events::admin_action(&env, "set_admin", &current_admin);
         events::AdminChangedEvent {
             old_admin: current_admin,
             new_admin,
@@ -1194,11 +3516,55 @@ impl CrowdfundVaultContract {
             .get::<_, ProjectData>(&DataKey::Project(project_id))
             .ok_or(CrowdfundError::ProjectNotFound)?;
 
-        Ok(env
+        Ok(Self::status_of(&env, project_id))
+    }
+
+    /// Page over projects whose status matches `status`
+    ///
+    /// `start` and `limit` index into the filtered list; past the end an empty
+    /// vec is returned.
+    pub fn list_projects_by_status(
+        env: Env,
+        status: Symbol,
+        start: u32,
+        limit: u32,
+    ) -> Vec<ProjectData> {
+        let project_count: u64 = env
             .storage()
+            .instance()
+            .get(&DataKey::NextProjectId)
+            .unwrap_or(0);
+
+        let mut projects = Vec::new(&env);
+        let mut matched = 0u32;
+        for project_id in 0..project_count {
+            if projects.len() >= limit {
+                break;
+            }
+            let Some(project) = env
+                .storage()
+                .persistent()
+                .get::<_, ProjectData>(&DataKey::Project(project_id))
+            else {
+                continue;
+            };
+            if Self::status_of(&env, project_id) != status {
+                continue;
+            }
+            if matched >= start {
+                projects.push_back(project);
+            }
+            matched += 1;
+        }
+        projects
+    }
+
+    /// Read a project's status, defaulting to ACTIVE when none was recorded
+    fn status_of(env: &Env, project_id: u64) -> Symbol {
+        env.storage()
             .persistent()
             .get(&DataKey::ProjectStatus(project_id))
-            .unwrap_or(Symbol::new(&env, "ACTIVE")))
+            .unwrap_or(Symbol::new(env, "ACTIVE"))
     }
 }
 
@@ -0,0 +1,12 @@
+use soroban_sdk::{contractclient, Address, Env, Symbol};
+
+/// Decimals of the prices returned by the oracle
+pub const PRICE_DECIMALS: u32 = 7;
+
+/// Interface of a price oracle quoting tokens in a reference unit (e.g. USD)
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    /// Price of one base unit of `token` in base units of `denom`, scaled by
+    /// 10^PRICE_DECIMALS
+    fn price(env: Env, token: Address, denom: Symbol) -> i128;
+}
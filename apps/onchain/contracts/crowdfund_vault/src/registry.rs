@@ -0,0 +1,8 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// The part of the contributor registry interface the vault relies on
+#[contractclient(name = "ContributorRegistryClient")]
+pub trait ContributorRegistry {
+    fn get_reputation(env: Env, contributor: Address) -> u64;
+    fn is_registered(env: Env, address: Address) -> bool;
+}
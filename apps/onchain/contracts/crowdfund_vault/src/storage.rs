@@ -21,6 +21,38 @@ pub enum DataKey {
     Paused,
     ProjectStatus(u64),
     Subscribers,
+    FeeBps,                         // -> u32 (withdrawal fee in basis points)
+    FeeRecipient,                   // -> Address
+    Registry,                       // -> Address (contributor registry)
+    OwnerProjects(Address),         // owner -> Vec<u64>
+    MaxProjectsPerOwner,            // -> u32 (0 = unlimited)
+    NormalizeWeights,               // -> bool
+    TokenDecimals(Address),         // token -> u32
+    ContributorProjects(Address),   // contributor -> Vec<u64>
+    ApprovedMilestoneCount(u64),    // project_id -> u32
+    EmitEvents,                     // -> bool (defaults to true)
+    ProjectByName(Symbol),          // name -> u64
+    FeeRecipientIsContract,         // -> bool
+    DepositMatchBps,                // -> u32
+    DepositMatchPool,               // -> Address
+    DepositMatchMinReputation,      // -> u64
+    DepositFeeBps,                  // -> u32 (deposit fee in basis points)
+    MilestoneWithdrawn(u64, u32),   // (project_id, milestone_id) -> i128
+    PriceOracle,                    // -> Address
+    ReferenceDenom,                 // -> Symbol (unit project targets are priced in)
+    Version,                        // -> u32 (storage layout version)
+    AllowedToken(Address),          // -> bool
+    AllowedTokenCount,              // -> u32 (0 = every token allowed)
+    RefundFeeBps,                   // -> u32 (refund fee in basis points)
+    ProjectAllowlist(u64, Address), // (project_id, depositor) -> bool
+    RefundCursor(u64),              // project_id -> u32 (next contributor index to refund)
+    MilestoneCooldown,              // -> u64 (seconds between milestone approvals)
+    LastMilestoneApproval(u64),     // project_id -> u64 (timestamp)
+    AutoArchive,                    // -> bool (archive funded projects once fully withdrawn)
+    DepositsPaused,                 // -> bool (blocks deposits only)
+    FirstDepositAt(u64, Address),   // (project_id, contributor) -> u64 (earliest deposit timestamp)
+    FeesCollected(Address),         // token -> i128 (platform fees taken so far)
+    MinTarget,                      // -> i128 (smallest project target, 0 = no floor)
 }
 
 #[contracttype]
@@ -34,4 +66,53 @@ pub struct ProjectData {
     pub total_deposited: i128,
     pub total_withdrawn: i128,
     pub is_active: bool,
+    pub deadline: u64, // funding deadline timestamp (0 = no deadline)
+    pub deposit_count: u64,
+    pub unique_contributors: u64,
+    pub royalty_bps: u32, // share of each net withdrawal owed to the royalty recipient
+    pub royalty_recipient: Option<Address>,
+    pub private: bool,                   // only allowlisted depositors may deposit
+    pub grace_period: u64, // seconds after the deadline that deposits are still accepted
+    pub deposit_lockup: u64, // seconds after a contributor's first deposit before it can leave
+    pub release_bps: u32,  // share of deposits released to the owner (0 = milestone gated)
+    pub vote_open_until: u64, // end of the latest milestone vote window (0 = none started)
+    pub reference_denom: Option<Symbol>, // unit the target is priced in (None = raw tokens)
+    pub outcome: DeadlineStatus, // result recorded once the deadline is evaluated (Open = not yet)
+    pub withdrawal_delay: u64, // seconds after a milestone approval before funds can be withdrawn
+    pub milestone_count: u32, // milestones 0..milestone_count that make up the project
+    pub release_milestone: u32, // milestone that withdrawals under the release are booked against
+}
+
+/// `ProjectData` as stored before storage version 2, migrated by `migrate`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectDataV1 {
+    pub id: u64,
+    pub owner: Address,
+    pub name: Symbol,
+    pub target_amount: i128,
+    pub token_address: Address,
+    pub total_deposited: i128,
+    pub total_withdrawn: i128,
+    pub is_active: bool,
+}
+
+/// Number of fields in a stored `ProjectDataV1`
+pub const PROJECT_V1_FIELDS: u32 = 8;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeadlineStatus {
+    Open,      // no deadline, or deadline not yet reached
+    Succeeded, // deadline passed and target met
+    Failed,    // deadline passed and target not met, refunds enabled
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VaultState {
+    pub admin: Address,
+    pub project_count: u64,
+    pub paused: bool,
+    pub fee_bps: u32,
 }
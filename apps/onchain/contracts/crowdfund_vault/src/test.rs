@@ -1,11 +1,192 @@
 use crate::errors::CrowdfundError;
+use crate::events::{AdminActionEvent, ContractPauseEvent, DepositEvent};
+use crate::storage::DeadlineStatus;
 use crate::{CrowdfundVaultContract, CrowdfundVaultContractClient};
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Events, Ledger},
     token::{StellarAssetClient, TokenClient},
-    Address, Env,
+    vec, Address, Env, Event, Symbol,
 };
+
+mod mock_treasury {
+    use crate::treasury::FeeTreasury;
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+    #[contracttype]
+    pub enum MockTreasuryKey {
+        Received(Address),
+    }
+
+    /// Treasury that records the fees reported to it
+    #[contract]
+    pub struct MockTreasury;
+
+    #[contractimpl]
+    impl FeeTreasury for MockTreasury {
+        fn receive_fee(env: Env, token: Address, amount: i128) {
+            let received = Self::received(env.clone(), token.clone());
+            env.storage()
+                .persistent()
+                .set(&MockTreasuryKey::Received(token), &(received + amount));
+        }
+    }
+
+    #[contractimpl]
+    impl MockTreasury {
+        pub fn received(env: Env, token: Address) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&MockTreasuryKey::Received(token))
+                .unwrap_or(0)
+        }
+    }
+}
+
+mod mock_registry {
+    use crate::registry::ContributorRegistry;
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+    #[contracttype]
+    pub enum MockRegistryKey {
+        Reputation(Address),
+    }
+
+    /// Registry with directly settable reputation; setting a reputation
+    /// registers the contributor
+    #[contract]
+    pub struct MockRegistry;
+
+    #[contractimpl]
+    impl ContributorRegistry for MockRegistry {
+        fn get_reputation(env: Env, contributor: Address) -> u64 {
+            env.storage()
+                .persistent()
+                .get(&MockRegistryKey::Reputation(contributor))
+                .unwrap_or(0)
+        }
+
+        fn is_registered(env: Env, address: Address) -> bool {
+            env.storage()
+                .persistent()
+                .has(&MockRegistryKey::Reputation(address))
+        }
+    }
+
+    #[contractimpl]
+    impl MockRegistry {
+        pub fn set_reputation(env: Env, contributor: Address, reputation: u64) {
+            env.storage()
+                .persistent()
+                .set(&MockRegistryKey::Reputation(contributor), &reputation);
+        }
+    }
+}
+
+mod mock_oracle {
+    use crate::oracle::PriceOracle;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Symbol};
+
+    /// Oracle quoting every token at a fixed price
+    #[contract]
+    pub struct MockOracle;
+
+    #[contractimpl]
+    impl PriceOracle for MockOracle {
+        fn price(env: Env, _token: Address, _denom: Symbol) -> i128 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("price"))
+                .unwrap()
+        }
+    }
+
+    #[contractimpl]
+    impl MockOracle {
+        pub fn __constructor(env: Env, price: i128) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("price"), &price);
+        }
+    }
+}
+
+mod mock_token {
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+    #[contracttype]
+    pub enum MockTokenKey {
+        Decimals,
+        Balance(Address),
+    }
+
+    /// Minimal token with configurable decimals
+    #[contract]
+    pub struct MockToken;
+
+    #[contractimpl]
+    impl MockToken {
+        pub fn __constructor(env: Env, decimals: u32) {
+            env.storage()
+                .instance()
+                .set(&MockTokenKey::Decimals, &decimals);
+        }
+
+        pub fn decimals(env: Env) -> u32 {
+            env.storage()
+                .instance()
+                .get(&MockTokenKey::Decimals)
+                .unwrap()
+        }
+
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            let balance = Self::balance(env.clone(), to.clone());
+            env.storage()
+                .persistent()
+                .set(&MockTokenKey::Balance(to), &(balance + amount));
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&MockTokenKey::Balance(id))
+                .unwrap_or(0)
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            from.require_auth();
+            let from_balance = Self::balance(env.clone(), from.clone());
+            env.storage()
+                .persistent()
+                .set(&MockTokenKey::Balance(from), &(from_balance - amount));
+            Self::mint(env, to, amount);
+        }
+    }
+}
+
+mod mock_frozen_token {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    /// Token that reports funds but rejects every transfer
+    #[contract]
+    pub struct MockFrozenToken;
+
+    #[contractimpl]
+    impl MockFrozenToken {
+        pub fn decimals(_env: Env) -> u32 {
+            7
+        }
+
+        pub fn balance(_env: Env, _id: Address) -> i128 {
+            1_000_000
+        }
+
+        pub fn transfer(_env: Env, _from: Address, _to: Address, _amount: i128) {
+            panic!("transfers are frozen");
+        }
+    }
+}
+
 fn create_token_contract<'a>(
     env: &Env,
     admin: &Address,
@@ -72,6 +253,91 @@ fn test_double_initialization_fails() {
     assert_eq!(result, Err(Ok(CrowdfundError::AlreadyInitialized)));
 }
 
+#[test]
+fn test_initialize_with_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _, _) = setup_test(&env);
+    let fee_recipient = Address::generate(&env);
+    let registry = Address::generate(&env);
+
+    // Initialize with full configuration
+    client.initialize_with_config(&admin, &250, &0, &fee_recipient, &Some(registry.clone()));
+
+    // Verify all fields are set
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_fee_bps(), 250);
+    assert_eq!(client.get_fee_recipient(), Some(fee_recipient));
+    assert_eq!(client.get_registry(), Some(registry));
+}
+
+#[test]
+fn test_initialize_with_config_double_init_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _, _) = setup_test(&env);
+    let fee_recipient = Address::generate(&env);
+
+    client.initialize_with_config(&admin, &250, &0, &fee_recipient, &None);
+
+    // Neither initializer can run a second time
+    let result = client.try_initialize_with_config(&admin, &250, &0, &fee_recipient, &None);
+    assert_eq!(result, Err(Ok(CrowdfundError::AlreadyInitialized)));
+    let result = client.try_initialize_with_config(&admin, &10_001, &0, &fee_recipient, &None);
+    assert_eq!(result, Err(Ok(CrowdfundError::AlreadyInitialized)));
+    let result = client.try_initialize(&admin);
+    assert_eq!(result, Err(Ok(CrowdfundError::AlreadyInitialized)));
+
+    // And the full-config init is rejected after a simple init
+    let (client2, admin2, _, _, _) = setup_test(&env);
+    client2.initialize(&admin2);
+    let result = client2.try_initialize_with_config(&admin2, &250, &0, &fee_recipient, &None);
+    assert_eq!(result, Err(Ok(CrowdfundError::AlreadyInitialized)));
+}
+
+#[test]
+fn test_initialize_with_config_invalid_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _, _) = setup_test(&env);
+    let fee_recipient = Address::generate(&env);
+
+    let result = client.try_initialize_with_config(&admin, &10_001, &0, &fee_recipient, &None);
+    assert_eq!(result, Err(Ok(CrowdfundError::InvalidFee)));
+}
+
+#[test]
+fn test_withdraw_with_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let fee_recipient = Address::generate(&env);
+
+    // 2.5% withdrawal fee
+    client.initialize_with_config(&admin, &250, &0, &fee_recipient, &None);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &100_000);
+    client.approve_milestone(&admin, &project_id, &0);
+
+    client.withdraw(&project_id, &0, &100_000);
+
+    // Fee goes to the recipient, the rest to the owner
+    assert_eq!(token_client.balance(&fee_recipient), 2_500);
+    assert_eq!(token_client.balance(&owner), 97_500);
+    assert_eq!(client.get_balance(&project_id), 0);
+}
+
 #[test]
 fn test_create_project() {
     let env = Env::default();
@@ -89,6 +355,7 @@ fn test_create_project() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     assert_eq!(project_id, 0);
 
@@ -137,6 +404,7 @@ fn test_deposit() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Deposit funds
     let deposit_amount: i128 = 500_000;
@@ -167,10 +435,11 @@ fn test_deposit_invalid_amount() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Try to deposit zero
     let result = client.try_deposit(&user, &project_id, &0);
-    assert_eq!(result, Err(Ok(CrowdfundError::InvalidAmount)));
+    assert_eq!(result, Err(Ok(CrowdfundError::ZeroAmount)));
 }
 
 #[test]
@@ -190,6 +459,7 @@ fn test_withdraw_without_approval_fails() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Deposit funds
     client.deposit(&user, &project_id, &500_000);
@@ -216,6 +486,7 @@ fn test_withdraw_after_approval() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Deposit funds
     let deposit_amount: i128 = 500_000;
@@ -262,6 +533,7 @@ fn test_non_admin_cannot_approve() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Non-admin tries to approve milestone - should fail
     let non_admin = Address::generate(&env);
@@ -286,6 +558,7 @@ fn test_insufficient_balance_withdrawal() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Deposit small amount
     client.deposit(&user, &project_id, &100_000);
@@ -330,6 +603,7 @@ fn test_multiple_projects() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id_1);
 
     let project_id_2 = client.create_project(
         &owner,
@@ -337,6 +611,7 @@ fn test_multiple_projects() {
         &2_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id_2);
 
     assert_eq!(project_id_1, 0);
     assert_eq!(project_id_2, 1);
@@ -360,7 +635,7 @@ fn test_create_project_invalid_amount() {
 
     let result =
         client.try_create_project(&owner, &symbol_short!("Test"), &0, &token_client.address);
-    assert_eq!(result, Err(Ok(CrowdfundError::InvalidAmount)));
+    assert_eq!(result, Err(Ok(CrowdfundError::ZeroAmount)));
 }
 
 #[test]
@@ -417,11 +692,12 @@ fn test_withdraw_invalid_amount() {
         &1000000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
     client.deposit(&user, &project_id, &500000);
     client.approve_milestone(&admin, &project_id, &0);
 
     let result = client.try_withdraw(&project_id, &0, &0);
-    assert_eq!(result, Err(Ok(CrowdfundError::InvalidAmount)));
+    assert_eq!(result, Err(Ok(CrowdfundError::ZeroAmount)));
 }
 
 #[test]
@@ -499,6 +775,7 @@ fn test_deposit_negative_amount() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Try to deposit negative amount
     let result = client.try_deposit(&user, &project_id, &-500);
@@ -521,6 +798,7 @@ fn test_deposit_to_inactive_project() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Get project and deactivate it (simulate project closure)
     let mut project = client.get_project(&project_id);
@@ -545,6 +823,7 @@ fn test_withdraw_from_inactive_project() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     client.deposit(&user, &project_id, &500_000);
     client.approve_milestone(&admin, &project_id, &0);
@@ -573,6 +852,7 @@ fn test_multiple_deposits() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // First deposit
     client.deposit(&user, &project_id, &200_000);
@@ -603,6 +883,7 @@ fn test_partial_withdrawal() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Deposit more than target
     client.deposit(&user, &project_id, &1_500_000);
@@ -638,6 +919,7 @@ fn test_unauthorized_withdrawal() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     client.deposit(&user, &project_id, &500_000);
     client.approve_milestone(&admin, &project_id, &0);
@@ -663,6 +945,7 @@ fn test_milestone_approval_status() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Before approval
     assert!(!client.is_milestone_approved(&project_id, &0));
@@ -690,6 +973,7 @@ fn test_balance_tracking() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Initial balance should be 0
     assert_eq!(client.get_balance(&project_id), 0);
@@ -720,6 +1004,7 @@ fn test_project_data_integrity() {
         &2_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Verify initial project data
     let project = client.get_project(&project_id);
@@ -755,7 +1040,7 @@ fn test_create_project_zero_target() {
 
     let result =
         client.try_create_project(&owner, &symbol_short!("Zero"), &0, &token_client.address);
-    assert_eq!(result, Err(Ok(CrowdfundError::InvalidAmount)));
+    assert_eq!(result, Err(Ok(CrowdfundError::ZeroAmount)));
 }
 
 // ===== exact balance withdrawal =====
@@ -774,6 +1059,7 @@ fn test_withdraw_exact_balance() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     let deposit_amount = 300_000;
     client.deposit(&user, &project_id, &deposit_amount);
@@ -810,18 +1096,21 @@ fn test_sequential_project_creation() {
         &100_000,
         &token_client.address,
     );
+    client.activate_project(&owner1, &id1);
     let id2 = client.create_project(
         &owner2,
         &symbol_short!("P2"),
         &200_000,
         &token_client.address,
     );
+    client.activate_project(&owner2, &id2);
     let id3 = client.create_project(
         &owner3,
         &symbol_short!("P3"),
         &300_000,
         &token_client.address,
     );
+    client.activate_project(&owner3, &id3);
 
     assert_eq!(id1, 0);
     assert_eq!(id2, 1);
@@ -868,6 +1157,7 @@ fn test_calculate_match_single_contributor() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Deposit funds from single contributor
     let contribution: i128 = 1_000_000; // 1M tokens
@@ -903,6 +1193,7 @@ fn test_calculate_match_multiple_contributors() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Create multiple users
     let user1 = Address::generate(&env);
@@ -910,7 +1201,7 @@ fn test_calculate_match_multiple_contributors() {
     let user3 = Address::generate(&env);
 
     // Mint tokens to users
-    let (_, token_admin_client) = create_token_contract(&env, &admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_client.address);
     token_admin_client.mint(&user1, &10_000_000);
     token_admin_client.mint(&user2, &10_000_000);
     token_admin_client.mint(&user3, &10_000_000);
@@ -954,6 +1245,7 @@ fn test_calculate_match_no_contributors() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Calculate match with no contributors
     let match_amount = client.calculate_match(&project_id);
@@ -977,6 +1269,7 @@ fn test_distribute_match() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Deposit funds
     let contribution: i128 = 1_000_000;
@@ -984,7 +1277,7 @@ fn test_distribute_match() {
 
     // Fund matching pool
     let pool_amount: i128 = 10_000_000;
-    let (_, token_admin_client) = create_token_contract(&env, &admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_client.address);
     token_admin_client.mint(&admin, &pool_amount);
     client.fund_matching_pool(&admin, &token_client.address, &pool_amount);
 
@@ -1069,11 +1362,12 @@ fn test_events_emission() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Deposit funds from multiple users to create large match
     let user1 = Address::generate(&env);
     let user2 = Address::generate(&env);
-    let (_, token_admin_client) = create_token_contract(&env, &admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_client.address);
     token_admin_client.mint(&user1, &10_000_000);
     token_admin_client.mint(&user2, &10_000_000);
 
@@ -1117,6 +1411,7 @@ fn test_multiple_contributions_same_user() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Same user makes multiple contributions
     client.deposit(&user, &project_id, &100);
@@ -1190,6 +1485,7 @@ fn test_create_project_pause() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &_project_id);
 }
 
 #[test]
@@ -1219,6 +1515,7 @@ fn test_create_project_pause_unpause() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     assert_eq!(project_id, 0);
 
@@ -1253,6 +1550,7 @@ fn test_deposit_pause() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     let _ = client.pause(&admin);
 
@@ -1278,6 +1576,7 @@ fn test_deposit_pause_unpause() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     let _ = client.pause(&admin);
 
@@ -1301,6 +1600,48 @@ fn test_deposit_pause_unpause() {
     assert_eq!(project.total_deposited, deposit_amount);
 }
 
+#[test]
+fn test_get_state_snapshot() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, _, token_client) = setup_test(&env);
+    let fee_recipient = Address::generate(&env);
+
+    // Uninitialized contract has no state
+    assert_eq!(
+        client.try_get_state(),
+        Err(Ok(CrowdfundError::NotInitialized))
+    );
+
+    client.initialize_with_config(&admin, &100, &0, &fee_recipient, &None);
+
+    let state = client.get_state();
+    assert_eq!(state.admin, admin);
+    assert_eq!(state.project_count, 0);
+    assert!(!state.paused);
+    assert_eq!(state.fee_bps, 100);
+
+    // Mutate: create two projects and pause
+    client.create_project(
+        &owner,
+        &symbol_short!("Proj1"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.create_project(
+        &owner,
+        &symbol_short!("Proj2"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.pause(&admin);
+
+    let state = client.get_state();
+    assert_eq!(state.project_count, 2);
+    assert!(state.paused);
+}
+
 // ---------------------------------------------------------------------------
 // Upgradeability tests
 // ---------------------------------------------------------------------------
@@ -1371,6 +1712,7 @@ fn test_cancel_project() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     assert_eq!(project_id, 0);
 
@@ -1403,6 +1745,7 @@ fn test_cancel_project_owner_can_cancel() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
     assert_eq!(project_id, 0);
 
     let project = client.get_project(&project_id);
@@ -1430,6 +1773,7 @@ fn test_cancel_project_cant_deposit() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
     assert_eq!(project_id, 0);
 
     let project = client.get_project(&project_id);
@@ -1458,6 +1802,7 @@ fn test_cancel_projects() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     token_client.transfer(&user, &user1, &100_000);
     token_client.transfer(&user, &user2, &200_000);
@@ -1515,6 +1860,7 @@ fn test_cancel_project_failed() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Deposit funds
     let deposit_amount: i128 = 100_000;
@@ -1526,6 +1872,43 @@ fn test_cancel_project_failed() {
     client.refund_contributors(&project_id, &user);
 }
 
+#[test]
+fn test_list_projects_by_status() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, _, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let mut ids = vec![&env];
+    for name in [
+        symbol_short!("First"),
+        symbol_short!("Second"),
+        symbol_short!("Third"),
+    ] {
+        let project_id = client.create_project(&owner, &name, &1_000, &token_client.address);
+        client.activate_project(&owner, &project_id);
+        ids.push_back(project_id);
+    }
+    client.cancel_project(&owner, &ids.get(1).unwrap());
+
+    let active = Symbol::new(&env, "ACTIVE");
+    let canceled = Symbol::new(&env, "CANCELED");
+    let active_projects = client.list_projects_by_status(&active, &0, &10);
+    assert_eq!(active_projects.len(), 2);
+    assert_eq!(active_projects.get(0).unwrap().id, ids.get(0).unwrap());
+    assert_eq!(active_projects.get(1).unwrap().id, ids.get(2).unwrap());
+
+    let canceled_projects = client.list_projects_by_status(&canceled, &0, &10);
+    assert_eq!(canceled_projects.len(), 1);
+    assert_eq!(canceled_projects.get(0).unwrap().id, ids.get(1).unwrap());
+
+    // Paging applies to the filtered list
+    let page = client.list_projects_by_status(&active, &1, &1);
+    assert_eq!(page.get(0).unwrap().id, ids.get(2).unwrap());
+    assert!(client.list_projects_by_status(&active, &2, &10).is_empty());
+}
+
 #[test]
 fn test_analytics_views() {
     let env = Env::default();
@@ -1544,8 +1927,9 @@ fn test_analytics_views() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
-    let (_, token_admin_client) = create_token_contract(&env, &admin);
+    let token_admin_client = StellarAssetClient::new(&env, &token_client.address);
     token_admin_client.mint(&user2, &200_000);
 
     // Initial checks
@@ -1597,6 +1981,7 @@ fn test_milestone_voting_success() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Deposit funds to project
     client.deposit(&user, &project_id, &600_000);
@@ -1633,6 +2018,7 @@ fn test_milestone_voting_insufficient_weight() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Two users deposit
     let user2 = Address::generate(&env);
@@ -1671,6 +2057,7 @@ fn test_milestone_voting_window_expires() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     client.deposit(&user, &project_id, &600_000);
 
@@ -1699,6 +2086,7 @@ fn test_unauthorized_vote_start() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     // Non-owner (e.g., admin or user) tries to start a vote - should fail
     let _result = client.try_start_milestone_vote(&project_id, &0, &3600);
@@ -1725,6 +2113,7 @@ fn test_already_voted_fails() {
         &1_000_000,
         &token_client.address,
     );
+    client.activate_project(&owner, &project_id);
 
     client.deposit(&user, &project_id, &100_000);
     client.start_milestone_vote(&project_id, &0, &3600);
@@ -1735,3 +2124,2267 @@ fn test_already_voted_fails() {
     let result = client.try_vote_milestone(&user, &project_id, &0, &true);
     assert_eq!(result, Err(Ok(CrowdfundError::AlreadyVoted)));
 }
+
+#[test]
+fn test_deposit_for_credits_target_contributor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, sponsor, token_client) = setup_test(&env);
+    let credited = Address::generate(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("Sponsor"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+
+    // Sponsor funds the deposit, credited address receives the contribution
+    client.deposit_for(&sponsor, &project_id, &250_000, &credited);
+
+    assert_eq!(token_client.balance(&sponsor), 10_000_000 - 250_000);
+    assert_eq!(client.get_contribution(&project_id, &credited), 250_000);
+    assert_eq!(client.get_contribution(&project_id, &sponsor), 0);
+    assert_eq!(client.get_contributor_count(&project_id), 1);
+    assert_eq!(client.get_balance(&project_id), 250_000);
+
+    // Refunds flow to the credited address, not the sponsor
+    client.cancel_project(&owner, &project_id);
+    client.refund_contributors(&project_id, &admin);
+
+    assert_eq!(token_client.balance(&credited), 250_000);
+    assert_eq!(token_client.balance(&sponsor), 10_000_000 - 250_000);
+}
+
+#[test]
+fn test_min_target_floor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, _, token_client) = setup_test(&env);
+    client.initialize(&admin);
+    client.set_min_target(&admin, &1_000);
+    assert_eq!(client.get_min_target(), 1_000);
+
+    let result =
+        client.try_create_project(&owner, &symbol_short!("Tiny"), &999, &token_client.address);
+    assert_eq!(result, Err(Ok(CrowdfundError::TargetTooLow)));
+
+    client.create_project(
+        &owner,
+        &symbol_short!("Small"),
+        &1_001,
+        &token_client.address,
+    );
+
+    // Clearing the floor allows any positive target again
+    client.set_min_target(&admin, &0);
+    client.create_project(&owner, &symbol_short!("Tiny"), &1, &token_client.address);
+
+    let result = client.try_set_min_target(&admin, &-1);
+    assert_eq!(result, Err(Ok(CrowdfundError::InvalidAmount)));
+}
+
+#[test]
+fn test_max_projects_per_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, _, token_client) = setup_test(&env);
+    client.initialize(&admin);
+    client.set_max_projects_per_owner(&admin, &2);
+    assert_eq!(client.get_max_projects_per_owner(), 2);
+
+    // Create up to the limit
+    let first = client.create_project(
+        &owner,
+        &symbol_short!("Proj1"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &first);
+    client.create_project(
+        &owner,
+        &symbol_short!("Proj2"),
+        &1_000_000,
+        &token_client.address,
+    );
+
+    // Third active project is rejected
+    let result = client.try_create_project(
+        &owner,
+        &symbol_short!("Proj3"),
+        &1_000_000,
+        &token_client.address,
+    );
+    assert_eq!(result, Err(Ok(CrowdfundError::ProjectLimitReached)));
+
+    // Other owners are unaffected
+    let other_owner = Address::generate(&env);
+    client.create_project(
+        &other_owner,
+        &symbol_short!("Other"),
+        &1_000_000,
+        &token_client.address,
+    );
+
+    // Canceling frees a slot
+    client.cancel_project(&owner, &first);
+    let third = client.create_project(
+        &owner,
+        &symbol_short!("Proj3"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &third);
+    assert_eq!(client.get_owner_projects(&owner).len(), 3);
+    assert_eq!(client.get_owner_projects(&owner).last(), Some(third));
+}
+
+#[test]
+fn test_set_max_projects_per_owner_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _, _) = setup_test(&env);
+    client.initialize(&admin);
+
+    let non_admin = Address::generate(&env);
+    let result = client.try_set_max_projects_per_owner(&non_admin, &1);
+    assert_eq!(result, Err(Ok(CrowdfundError::Unauthorized)));
+}
+
+#[test]
+fn test_project_pending_until_owner_activates() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("Pending"),
+        &1_000_000,
+        &token_client.address,
+    );
+
+    // New projects start pending
+    assert_eq!(
+        client.get_project_status(&project_id),
+        symbol_short!("PENDING")
+    );
+    assert!(!client.get_project(&project_id).is_active);
+
+    // Deposits fail until activation
+    let result = client.try_deposit(&user, &project_id, &100_000);
+    assert_eq!(result, Err(Ok(CrowdfundError::ProjectNotActive)));
+
+    // Only the owner can activate
+    let result = client.try_activate_project(&admin, &project_id);
+    assert_eq!(result, Err(Ok(CrowdfundError::Unauthorized)));
+
+    client.activate_project(&owner, &project_id);
+    assert_eq!(
+        client.get_project_status(&project_id),
+        symbol_short!("ACTIVE")
+    );
+
+    // Activation is one-shot
+    let result = client.try_activate_project(&owner, &project_id);
+    assert_eq!(result, Err(Ok(CrowdfundError::ProjectNotPending)));
+
+    // Deposits now succeed
+    client.deposit(&user, &project_id, &100_000);
+    assert_eq!(client.get_balance(&project_id), 100_000);
+}
+
+#[test]
+fn test_vote_weights_normalized_by_token_decimals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    // Second token with 18 decimals (the Stellar asset uses 7)
+    let token18 = env.register(mock_token::MockToken, (18u32,));
+    let token18_client = mock_token::MockTokenClient::new(&env, &token18);
+    token18_client.mint(&user, &10_000_000_000_000_000_000);
+
+    let project7 = client.create_project(
+        &owner,
+        &symbol_short!("Dec7"),
+        &100_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project7);
+    let project18 = client.create_project(
+        &owner,
+        &symbol_short!("Dec18"),
+        &100_000_000_000_000_000_000,
+        &token18,
+    );
+    client.activate_project(&owner, &project18);
+
+    // One whole token into each project
+    client.deposit(&user, &project7, &10_000_000);
+    client.deposit(&user, &project18, &1_000_000_000_000_000_000);
+
+    // Raw weights differ by the decimals gap
+    assert_eq!(client.get_vote_weight(&project7, &user), 10_000_000);
+    assert_eq!(
+        client.get_vote_weight(&project18, &user),
+        1_000_000_000_000_000_000
+    );
+
+    // Normalized weights are equal
+    client.set_normalize_weights(&admin, &true);
+    assert_eq!(
+        client.get_vote_weight(&project7, &user),
+        client.get_vote_weight(&project18, &user)
+    );
+    assert_eq!(
+        client.get_vote_weight(&project7, &user),
+        1_000_000_000_000_000_000
+    );
+
+    // Voting still approves against the normalized total
+    client.start_milestone_vote(&project7, &0, &3600);
+    client.vote_milestone(&user, &project7, &0, &true);
+    assert!(client.is_milestone_approved(&project7, &0));
+}
+
+#[test]
+fn test_contributor_projects_list() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let project_a = client.create_project(
+        &owner,
+        &symbol_short!("ProjA"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_a);
+    let project_b = client.create_project(
+        &owner,
+        &symbol_short!("ProjB"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_b);
+
+    assert_eq!(client.get_contributor_projects(&user).len(), 0);
+
+    // Two deposits into the first project, one into the second
+    client.deposit(&user, &project_a, &100);
+    client.deposit(&user, &project_a, &200);
+    client.deposit(&user, &project_b, &300);
+
+    let projects = client.get_contributor_projects(&user);
+    assert_eq!(projects, soroban_sdk::vec![&env, project_a, project_b]);
+}
+
+#[test]
+fn test_available_to_withdraw() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let fee_recipient = Address::generate(&env);
+
+    // 2.5% withdrawal fee
+    client.initialize_with_config(&admin, &250, &0, &fee_recipient, &None);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &100_000);
+
+    // Nothing releasable before approval
+    assert_eq!(client.available_to_withdraw(&project_id), 0);
+
+    client.approve_milestone(&admin, &project_id, &0);
+
+    // Full balance net of the fee
+    assert_eq!(client.available_to_withdraw(&project_id), 97_500);
+
+    // Nothing can be taken out while the vault is paused
+    client.pause(&admin);
+    assert_eq!(client.available_to_withdraw(&project_id), 0);
+    client.unpause(&admin);
+
+    // Matches what the owner actually receives
+    client.withdraw(&project_id, &0, &100_000);
+    assert_eq!(token_client.balance(&owner), 97_500);
+    assert_eq!(client.available_to_withdraw(&project_id), 0);
+
+    // Unknown project
+    let result = client.try_available_to_withdraw(&99);
+    assert_eq!(result, Err(Ok(CrowdfundError::ProjectNotFound)));
+}
+
+#[test]
+fn test_deposit_reports_transfer_failure() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, _) = setup_test(&env);
+    let frozen_token = env.register(mock_frozen_token::MockFrozenToken, ());
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &frozen_token,
+    );
+    client.activate_project(&owner, &project_id);
+
+    let result = client.try_deposit(&user, &project_id, &1_000);
+    assert_eq!(result, Err(Ok(CrowdfundError::TransferFailed)));
+    assert_eq!(client.get_balance(&project_id), 0);
+}
+
+#[test]
+fn test_pause_deposits_keeps_withdrawals_open() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &1_000);
+    client.approve_milestone(&admin, &project_id, &0);
+
+    client.pause_deposits(&admin);
+    assert!(client.is_deposits_paused());
+    let result = client.try_deposit(&user, &project_id, &1_000);
+    assert_eq!(result, Err(Ok(CrowdfundError::DepositsPaused)));
+
+    // Owners can still pull funds out
+    client.withdraw(&project_id, &0, &400);
+    assert_eq!(token_client.balance(&owner), 400);
+
+    client.resume_deposits(&admin);
+    client.deposit(&user, &project_id, &1_000);
+    assert_eq!(client.get_balance(&project_id), 1_600);
+
+    let result = client.try_pause_deposits(&owner);
+    assert_eq!(result, Err(Ok(CrowdfundError::Unauthorized)));
+}
+
+#[test]
+fn test_withdrawable_now_respects_gates() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let fee_recipient = Address::generate(&env);
+    client.initialize_with_config(&admin, &250, &0, &fee_recipient, &None);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &100_000);
+
+    // Locked until a milestone is approved
+    client.set_project_withdrawal_delay(&admin, &project_id, &600);
+    assert_eq!(client.withdrawable_now(&project_id), 0);
+    client.approve_milestone(&admin, &project_id, &0);
+
+    // Locked by the withdrawal delay after the approval
+    assert_eq!(client.withdrawable_now(&project_id), 0);
+    let result = client.try_withdraw(&project_id, &0, &1);
+    assert_eq!(result, Err(Ok(CrowdfundError::WithdrawalDelayActive)));
+    env.ledger().with_mut(|li| li.timestamp += 600);
+    assert_eq!(client.withdrawable_now(&project_id), 100_000);
+
+    // Locked again while paused
+    client.pause(&admin);
+    assert_eq!(client.withdrawable_now(&project_id), 0);
+    client.unpause(&admin);
+
+    // The reported figure is exactly what withdraw accepts
+    let result = client.try_withdraw(&project_id, &0, &100_001);
+    assert_eq!(result, Err(Ok(CrowdfundError::InsufficientBalance)));
+    client.withdraw(&project_id, &0, &client.withdrawable_now(&project_id));
+    assert_eq!(client.withdrawable_now(&project_id), 0);
+
+    // A failed campaign releases nothing more, even with a release set
+    let now = env.ledger().timestamp();
+    let failed_id = client.create_project(
+        &owner,
+        &symbol_short!("Under"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &failed_id);
+    client.set_project_deadline(&owner, &failed_id, &(now + 1000));
+    client.deposit(&user, &failed_id, &1_000);
+    client.set_milestone_release(&admin, &failed_id, &0, &5_000);
+    assert_eq!(client.withdrawable_now(&failed_id), 500);
+    env.ledger().with_mut(|li| li.timestamp = now + 1000);
+    assert_eq!(client.withdrawable_now(&failed_id), 0);
+
+    let result = client.try_set_project_withdrawal_delay(&owner, &project_id, &0);
+    assert_eq!(result, Err(Ok(CrowdfundError::Unauthorized)));
+}
+
+#[test]
+fn test_emit_events_toggle() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+    assert!(client.get_emit_events());
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+
+    let vault_events = |env: &Env| {
+        env.events()
+            .all()
+            .iter()
+            .filter(|(contract, _, _)| *contract == client.address)
+            .count()
+    };
+
+    // Enabled by default: deposit publishes a DepositEvent
+    client.deposit(&user, &project_id, &1_000);
+    let expected = DepositEvent {
+        user: user.clone(),
+        project_id,
+        amount: 1_000,
+        sponsor: user.clone(),
+        fee: 0,
+        memo: None,
+        schema_version: 2,
+    };
+    let events = env.events().all();
+    let (_, topics, _) = events
+        .iter()
+        .find(|(contract, _, _)| *contract == client.address)
+        .unwrap();
+    assert_eq!(topics, expected.topics(&env));
+    assert_eq!(vault_events(&env), 1);
+
+    // Disabled: the vault stays silent
+    client.set_emit_events(&admin, &false);
+    assert!(!client.get_emit_events());
+    client.deposit(&user, &project_id, &1_000);
+    assert_eq!(vault_events(&env), 0);
+
+    // Security-relevant events are still published: the audit event and the pause
+    client.pause(&admin);
+    let audit = AdminActionEvent {
+        action: Symbol::new(&env, "pause"),
+        admin: admin.clone(),
+    };
+    let pause_event = ContractPauseEvent {
+        admin: admin.clone(),
+        paused: true,
+        timestamp: env.ledger().timestamp(),
+    };
+    let mut topics = vec![&env];
+    for (contract, event_topics, _) in env.events().all().iter() {
+        if contract == client.address {
+            topics.push_back(event_topics);
+        }
+    }
+    assert_eq!(
+        topics,
+        vec![&env, audit.topics(&env), pause_event.topics(&env)]
+    );
+    client.unpause(&admin);
+
+    // Only the admin may toggle emission
+    let result = client.try_set_emit_events(&user, &true);
+    assert_eq!(result, Err(Ok(CrowdfundError::Unauthorized)));
+}
+
+#[test]
+fn test_deadline_status() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let now = env.ledger().timestamp();
+    let create = |name: Symbol| {
+        let id = client.create_project(&owner, &name, &1_000, &token_client.address);
+        client.activate_project(&owner, &id);
+        client.set_project_deadline(&owner, &id, &(now + 1000));
+        id
+    };
+    let funded = create(symbol_short!("Funded"));
+    let underfunded = create(symbol_short!("Under"));
+
+    // No deadline is always open
+    let open_ended = client.create_project(
+        &owner,
+        &symbol_short!("OpenEnd"),
+        &1_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &open_ended);
+
+    client.deposit(&user, &funded, &1_000);
+    client.deposit(&user, &underfunded, &400);
+    assert_eq!(client.get_deadline_status(&funded), DeadlineStatus::Open);
+    assert_eq!(
+        client.get_deadline_status(&underfunded),
+        DeadlineStatus::Open
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = now + 1000);
+    assert_eq!(
+        client.get_deadline_status(&funded),
+        DeadlineStatus::Succeeded
+    );
+    assert_eq!(
+        client.get_deadline_status(&underfunded),
+        DeadlineStatus::Failed
+    );
+    assert_eq!(
+        client.get_deadline_status(&open_ended),
+        DeadlineStatus::Open
+    );
+
+    // Deposits are closed after the deadline
+    let result = client.try_deposit(&user, &underfunded, &600);
+    assert_eq!(result, Err(Ok(CrowdfundError::DeadlinePassed)));
+
+    // Failed projects can be refunded, successful ones cannot
+    let balance_before = token_client.balance(&user);
+    client.refund_contributors(&underfunded, &admin);
+    assert_eq!(token_client.balance(&user), balance_before + 400);
+    let result = client.try_refund_contributors(&funded, &admin);
+    assert_eq!(result, Err(Ok(CrowdfundError::ProjectNotCancellable)));
+
+    // Unknown project
+    let result = client.try_get_deadline_status(&99);
+    assert_eq!(result, Err(Ok(CrowdfundError::ProjectNotFound)));
+}
+
+#[test]
+fn test_failed_project_holds_funds_for_refunds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let user2 = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_client.address).mint(&user2, &400);
+    client.initialize(&admin);
+
+    let now = env.ledger().timestamp();
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("Under"),
+        &1_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.set_project_deadline(&owner, &project_id, &(now + 1000));
+    client.deposit(&user, &project_id, &400);
+    client.deposit(&user2, &project_id, &400);
+
+    // Half the deposits are released and withdrawn before the deadline
+    client.set_milestone_release(&admin, &project_id, &0, &5_000);
+    client.withdraw(&project_id, &0, &400);
+
+    env.ledger().with_mut(|li| li.timestamp = now + 1000);
+    assert_eq!(
+        client.get_deadline_status(&project_id),
+        DeadlineStatus::Failed
+    );
+
+    // Nothing more leaves for the owner once the campaign failed
+    let result = client.try_withdraw(&project_id, &0, &1);
+    assert_eq!(result, Err(Ok(CrowdfundError::ProjectFailed)));
+    let result = client.try_set_milestone_release(&admin, &project_id, &0, &10_000);
+    assert_eq!(result, Err(Ok(CrowdfundError::ProjectFailed)));
+
+    // What the project still holds is shared pro-rata
+    let user_before = token_client.balance(&user);
+    client.refund_contributors(&project_id, &admin);
+    assert_eq!(token_client.balance(&user), user_before + 200);
+    assert_eq!(token_client.balance(&user2), 200);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(client.get_contribution(&project_id, &user2), 0);
+}
+
+#[test]
+fn test_set_project_deadline_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000,
+        &token_client.address,
+    );
+
+    // Only the owner may set the deadline
+    let result = client.try_set_project_deadline(&user, &project_id, &2_000);
+    assert_eq!(result, Err(Ok(CrowdfundError::Unauthorized)));
+
+    // Deadline must be in the future
+    let result = client.try_set_project_deadline(&owner, &project_id, &1_000);
+    assert_eq!(result, Err(Ok(CrowdfundError::InvalidDeadline)));
+
+    client.set_project_deadline(&owner, &project_id, &2_000);
+    assert_eq!(client.get_project(&project_id).deadline, 2_000);
+
+    // Cannot be moved once reached
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    let result = client.try_set_project_deadline(&owner, &project_id, &3_000);
+    assert_eq!(result, Err(Ok(CrowdfundError::DeadlinePassed)));
+}
+
+#[test]
+fn test_can_deposit_reflects_deposit_gates() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000,
+        &token_client.address,
+    );
+    // Pending projects do not accept deposits yet
+    assert!(!client.can_deposit(&project_id, &user, &100));
+
+    client.activate_project(&owner, &project_id);
+    client.set_project_deadline(&owner, &project_id, &2_000);
+    assert!(client.can_deposit(&project_id, &user, &100));
+    assert!(!client.can_deposit(&project_id, &user, &0));
+
+    client.pause_deposits(&admin);
+    assert!(!client.can_deposit(&project_id, &user, &100));
+    client.resume_deposits(&admin);
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert!(!client.can_deposit(&project_id, &user, &100));
+    let result = client.try_deposit(&user, &project_id, &100);
+    assert_eq!(result, Err(Ok(CrowdfundError::DeadlinePassed)));
+}
+
+#[test]
+fn test_deposit_grace_period_after_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.set_project_deadline(&owner, &project_id, &2_000);
+    client.set_project_grace_period(&owner, &project_id, &500);
+
+    // Late deposits are accepted inside the grace window
+    env.ledger().with_mut(|li| li.timestamp = 2_200);
+    client.deposit(&user, &project_id, &400);
+    assert_eq!(
+        client.get_deadline_status(&project_id),
+        DeadlineStatus::Open
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 2_500);
+    let result = client.try_deposit(&user, &project_id, &600);
+    assert_eq!(result, Err(Ok(CrowdfundError::DeadlinePassed)));
+    assert_eq!(
+        client.get_deadline_status(&project_id),
+        DeadlineStatus::Failed
+    );
+
+    // Funding that has closed cannot be reopened
+    let result = client.try_set_project_grace_period(&owner, &project_id, &1_000);
+    assert_eq!(result, Err(Ok(CrowdfundError::DeadlinePassed)));
+}
+
+#[test]
+fn test_transfer_contribution() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &1_000);
+
+    // Transfer half of the position
+    client.transfer_contribution(&user, &buyer, &project_id, &500);
+    assert_eq!(client.get_contribution(&project_id, &user), 500);
+    assert_eq!(client.get_contribution(&project_id, &buyer), 500);
+    assert_eq!(
+        client.get_contributor_projects(&buyer),
+        vec![&env, project_id]
+    );
+
+    // Cannot transfer more than held
+    let result = client.try_transfer_contribution(&user, &buyer, &project_id, &501);
+    assert_eq!(result, Err(Ok(CrowdfundError::InsufficientBalance)));
+
+    // Refunds follow the transferred position
+    let user_before = token_client.balance(&user);
+    client.cancel_project(&owner, &project_id);
+    client.refund_contributors(&project_id, &admin);
+    assert_eq!(token_client.balance(&user), user_before + 500);
+    assert_eq!(token_client.balance(&buyer), 500);
+}
+
+#[test]
+fn test_transfer_back_keeps_contributor_list() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &1_000);
+
+    // Move the whole position out and back again
+    client.transfer_contribution(&user, &buyer, &project_id, &1_000);
+    client.transfer_contribution(&buyer, &user, &project_id, &1_000);
+    assert_eq!(client.get_contributor_count(&project_id), 2);
+    assert_eq!(client.get_project(&project_id).unique_contributors, 2);
+
+    client.transfer_contribution(&user, &buyer, &project_id, &400);
+    assert_eq!(client.get_contributor_count(&project_id), 2);
+    assert_eq!(
+        client.preview_refunds(&project_id),
+        vec![&env, (user.clone(), 600), (buyer.clone(), 400)]
+    );
+}
+
+#[test]
+fn test_deposit_lockup_blocks_early_exit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.set_project_deposit_lockup(&owner, &project_id, &1_000);
+    client.activate_project(&owner, &project_id);
+    assert_eq!(client.get_project(&project_id).deposit_lockup, 1_000);
+
+    // The lockup can only be set while the project is pending
+    let result = client.try_set_project_deposit_lockup(&owner, &project_id, &0);
+    assert_eq!(result, Err(Ok(CrowdfundError::ProjectNotPending)));
+
+    let deposited_at = env.ledger().timestamp();
+    client.deposit(&user, &project_id, &1_000);
+    client.cancel_project(&owner, &project_id);
+
+    let other = Address::generate(&env);
+    let result = client.try_transfer_contribution(&user, &other, &project_id, &500);
+    assert_eq!(result, Err(Ok(CrowdfundError::DepositLocked)));
+    env.ledger().set_timestamp(deposited_at + 1_000);
+    client.transfer_contribution(&user, &other, &project_id, &500);
+}
+
+#[test]
+fn test_deposit_lockup_does_not_block_refunds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.set_project_deposit_lockup(&owner, &project_id, &1_000);
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &1_000);
+    client.cancel_project(&owner, &project_id);
+
+    // A canceled project refunds right away, lockup or not
+    assert_eq!(
+        client.refundable_projects(&user),
+        vec![&env, (project_id, 1_000)]
+    );
+    let user_before = token_client.balance(&user);
+    client.refund_contributors(&project_id, &admin);
+    assert_eq!(token_client.balance(&user), user_before + 1_000);
+}
+
+#[test]
+fn test_transfer_contribution_guards() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &1_000);
+
+    // A self-transfer must not mint contribution
+    let result = client.try_transfer_contribution(&user, &user, &project_id, &500);
+    assert_eq!(result, Err(Ok(CrowdfundError::SelfTransfer)));
+    assert_eq!(client.get_contribution(&project_id, &user), 1_000);
+
+    // Positions cannot move while a vote is open, so they cannot vote twice
+    client.start_milestone_vote(&project_id, &0, &100);
+    client.vote_milestone(&user, &project_id, &0, &false);
+    let result = client.try_transfer_contribution(&user, &buyer, &project_id, &500);
+    assert_eq!(result, Err(Ok(CrowdfundError::VoteInProgress)));
+
+    env.ledger().with_mut(|li| li.timestamp += 101);
+    client.transfer_contribution(&user, &buyer, &project_id, &500);
+    assert_eq!(client.get_project(&project_id).unique_contributors, 2);
+
+    // Positions are fixed once a share of the deposits is released
+    client.set_milestone_release(&admin, &project_id, &0, &2_500);
+    let result = client.try_transfer_contribution(&user, &buyer, &project_id, &100);
+    assert_eq!(result, Err(Ok(CrowdfundError::MilestoneAlreadyApproved)));
+}
+
+#[test]
+fn test_transfer_contribution_after_approval_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &1_000);
+    client.approve_milestone(&admin, &project_id, &0);
+
+    let result = client.try_transfer_contribution(&user, &buyer, &project_id, &500);
+    assert_eq!(result, Err(Ok(CrowdfundError::MilestoneAlreadyApproved)));
+}
+
+#[test]
+fn test_get_flows() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &10_000);
+    client.approve_milestone(&admin, &project_id, &0);
+    client.withdraw(&project_id, &0, &4_000);
+
+    let (deposited, withdrawn, balance) = client.get_flows(&project_id);
+    assert_eq!((deposited, withdrawn, balance), (10_000, 4_000, 6_000));
+    assert_eq!(deposited - withdrawn, balance);
+
+    let result = client.try_get_flows(&99);
+    assert_eq!(result, Err(Ok(CrowdfundError::ProjectNotFound)));
+}
+
+#[test]
+fn test_get_project_by_name() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, _, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let alpha = client.create_project(
+        &owner,
+        &symbol_short!("Alpha"),
+        &1_000,
+        &token_client.address,
+    );
+    let beta = client.create_project(
+        &owner,
+        &symbol_short!("Beta"),
+        &2_000,
+        &token_client.address,
+    );
+
+    assert_eq!(
+        client.get_project_by_name(&symbol_short!("Alpha")).id,
+        alpha
+    );
+    assert_eq!(client.get_project_by_name(&symbol_short!("Beta")).id, beta);
+
+    // Names are unique
+    let result = client.try_create_project(
+        &owner,
+        &symbol_short!("Alpha"),
+        &1_000,
+        &token_client.address,
+    );
+    assert_eq!(result, Err(Ok(CrowdfundError::NameTaken)));
+
+    let result = client.try_get_project_by_name(&symbol_short!("Gamma"));
+    assert_eq!(result, Err(Ok(CrowdfundError::ProjectNotFound)));
+}
+
+#[test]
+fn test_fee_routed_to_treasury_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let treasury_id = env.register(mock_treasury::MockTreasury, ());
+    let treasury = mock_treasury::MockTreasuryClient::new(&env, &treasury_id);
+
+    // 2.5% withdrawal and 1% deposit fees sent to a treasury contract
+    client.initialize_with_config(&admin, &250, &100, &treasury_id, &None);
+    client.set_fee_recipient_is_contract(&admin, &true);
+    assert!(client.get_fee_recipient_is_contract());
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &100_000);
+    assert_eq!(treasury.received(&token_client.address), 1_000);
+    client.approve_milestone(&admin, &project_id, &0);
+    client.withdraw(&project_id, &0, &99_000);
+
+    // The treasury received the tokens and was told about them
+    assert_eq!(token_client.balance(&treasury_id), 3_475);
+    assert_eq!(treasury.received(&token_client.address), 3_475);
+    assert_eq!(token_client.balance(&owner), 96_525);
+}
+
+#[test]
+fn test_deposit_match_for_reputable_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let registry_id = env.register(mock_registry::MockRegistry, ());
+    let registry = mock_registry::MockRegistryClient::new(&env, &registry_id);
+    let match_pool = Address::generate(&env);
+    let low_rep_owner = Address::generate(&env);
+
+    StellarAssetClient::new(&env, &token_client.address).mint(&match_pool, &1_500);
+    // The pool lets the vault pull matches
+    token_client.approve(&match_pool, &client.address, &1_500, &1_000);
+    registry.set_reputation(&owner, &100);
+    registry.set_reputation(&low_rep_owner, &10);
+
+    client.initialize_with_config(&admin, &0, &0, &admin, &Some(registry_id));
+    // 10% match for owners with reputation above 50
+    client.set_deposit_match(&admin, &1_000, &match_pool, &50);
+
+    let matched_id = client.create_project(
+        &owner,
+        &symbol_short!("Matched"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &matched_id);
+    let unmatched_id = client.create_project(
+        &low_rep_owner,
+        &symbol_short!("Plain"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&low_rep_owner, &unmatched_id);
+
+    // Reputable owner: deposit is matched
+    client.deposit(&user, &matched_id, &10_000);
+    assert_eq!(client.get_balance(&matched_id), 11_000);
+    assert_eq!(client.get_contribution(&matched_id, &user), 10_000);
+    assert_eq!(token_client.balance(&match_pool), 500);
+
+    // Below the threshold: no match
+    client.deposit(&user, &unmatched_id, &10_000);
+    assert_eq!(client.get_balance(&unmatched_id), 10_000);
+
+    // Pool cannot cover the match: deposit still succeeds unmatched
+    client.deposit(&user, &matched_id, &10_000);
+    assert_eq!(client.get_balance(&matched_id), 21_000);
+    assert_eq!(token_client.balance(&match_pool), 500);
+
+    // A refund returns contributions and leaves no matched funds behind
+    let user_before = token_client.balance(&user);
+    client.cancel_project(&owner, &matched_id);
+    client.refund_contributors(&matched_id, &admin);
+    assert_eq!(token_client.balance(&user), user_before + 20_000);
+    assert_eq!(token_client.balance(&admin), 1_000);
+    assert_eq!(token_client.balance(&client.address), 10_000);
+}
+
+#[test]
+fn test_unfunded_deposit_is_not_matched() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, _, token_client) = setup_test(&env);
+    let registry_id = env.register(mock_registry::MockRegistry, ());
+    let registry = mock_registry::MockRegistryClient::new(&env, &registry_id);
+    let match_pool = Address::generate(&env);
+    let broke = Address::generate(&env);
+
+    StellarAssetClient::new(&env, &token_client.address).mint(&match_pool, &1_000);
+    token_client.approve(&match_pool, &client.address, &1_000, &1_000);
+    registry.set_reputation(&owner, &100);
+
+    client.initialize_with_config(&admin, &0, &0, &admin, &Some(registry_id));
+    client.set_deposit_match(&admin, &1_000, &match_pool, &50);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("Matched"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+
+    // A sponsor without funds cannot record a deposit or draw a match
+    let result = client.try_deposit(&broke, &project_id, &5_000);
+    assert_eq!(result, Err(Ok(CrowdfundError::TransferFailed)));
+    assert_eq!(client.get_balance(&project_id), 0);
+    assert_eq!(client.get_contribution(&project_id, &broke), 0);
+    assert_eq!(token_client.balance(&match_pool), 1_000);
+}
+
+#[test]
+fn test_time_to_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, _, token_client) = setup_test(&env);
+    client.initialize(&admin);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000,
+        &token_client.address,
+    );
+
+    // No deadline set
+    assert_eq!(client.time_to_deadline(&project_id), 0);
+
+    client.set_project_deadline(&owner, &project_id, &5_000);
+    assert_eq!(client.time_to_deadline(&project_id), 4_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 4_999);
+    assert_eq!(client.time_to_deadline(&project_id), 1);
+
+    // Past the deadline
+    env.ledger().with_mut(|li| li.timestamp = 6_000);
+    assert_eq!(client.time_to_deadline(&project_id), 0);
+
+    let result = client.try_time_to_deadline(&99);
+    assert_eq!(result, Err(Ok(CrowdfundError::ProjectNotFound)));
+}
+
+#[test]
+fn test_deposit_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let fee_recipient = Address::generate(&env);
+    // 1% deposit fee
+    client.initialize_with_config(&admin, &0, &100, &fee_recipient, &None);
+    assert_eq!(client.get_deposit_fee_bps(), 100);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+
+    let user_before = token_client.balance(&user);
+    client.deposit(&user, &project_id, &10_000);
+
+    // Project and contributor are credited the net, the fee reaches the recipient
+    assert_eq!(client.get_balance(&project_id), 9_900);
+    assert_eq!(client.get_contribution(&project_id, &user), 9_900);
+    assert_eq!(token_client.balance(&fee_recipient), 100);
+    assert_eq!(token_client.balance(&user), user_before - 10_000);
+
+    // Fees above the cap are rejected
+    let result = client.try_set_deposit_fee_bps(&admin, &1_001);
+    assert_eq!(result, Err(Ok(CrowdfundError::InvalidFee)));
+    let (client2, admin2, _, _, _) = setup_test(&env);
+    let result = client2.try_initialize_with_config(&admin2, &0, &1_001, &fee_recipient, &None);
+    assert_eq!(result, Err(Ok(CrowdfundError::InvalidFee)));
+}
+
+#[test]
+fn test_fees_collected_tallies_every_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let fee_recipient = Address::generate(&env);
+    // 5% withdrawal, 1% deposit and 2% refund fees
+    client.initialize_with_config(&admin, &500, &0, &fee_recipient, &None);
+    client.set_deposit_fee_bps(&admin, &100);
+    client.set_refund_fee_bps(&admin, &200);
+    assert_eq!(client.get_fees_collected(&token_client.address), 0);
+
+    let funded = client.create_project(
+        &owner,
+        &symbol_short!("Funded"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &funded);
+    client.deposit(&user, &funded, &10_000);
+    client.approve_milestone(&admin, &funded, &0);
+    client.withdraw(&funded, &0, &9_900);
+
+    let canceled = client.create_project(
+        &owner,
+        &symbol_short!("Canceled"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &canceled);
+    client.deposit(&user, &canceled, &10_000);
+    client.cancel_project(&owner, &canceled);
+    client.refund_contributors(&canceled, &owner);
+
+    // Two deposit fees of 100, a withdrawal fee of 495 and a refund fee of 198
+    assert_eq!(client.get_fees_collected(&token_client.address), 893);
+    assert_eq!(token_client.balance(&fee_recipient), 893);
+}
+
+#[test]
+fn test_milestone_release_caps_withdrawals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &10_000);
+
+    // 25% released: the owner may withdraw up to 2,500
+    client.set_milestone_release(&admin, &project_id, &0, &2_500);
+    assert_eq!(client.withdrawable_now(&project_id), 2_500);
+    let result = client.try_withdraw(&project_id, &0, &2_501);
+    assert_eq!(result, Err(Ok(CrowdfundError::InsufficientBalance)));
+    client.withdraw(&project_id, &0, &2_500);
+    assert_eq!(client.withdrawable_now(&project_id), 0);
+
+    // The release can only be raised
+    let result = client.try_set_milestone_release(&admin, &project_id, &0, &1_000);
+    assert_eq!(result, Err(Ok(CrowdfundError::CannotReduceRelease)));
+
+    // 60% released for milestone 1: another 3,500 becomes available
+    client.set_milestone_release(&admin, &project_id, &1, &6_000);
+    assert_eq!(client.get_milestone_release(&project_id), 6_000);
+    assert_eq!(client.withdrawable_now(&project_id), 3_500);
+    let result = client.try_withdraw(&project_id, &1, &3_501);
+    assert_eq!(result, Err(Ok(CrowdfundError::InsufficientBalance)));
+
+    // Withdrawals are booked against the released milestone only
+    let result = client.try_withdraw(&project_id, &0, &3_500);
+    assert_eq!(result, Err(Ok(CrowdfundError::MilestoneNotApproved)));
+    client.withdraw(&project_id, &1, &3_500);
+
+    assert_eq!(token_client.balance(&owner), 6_000);
+    assert_eq!(client.get_balance(&project_id), 4_000);
+}
+
+#[test]
+fn test_revoke_milestone() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &10_000);
+
+    // Revoking before any withdrawal succeeds
+    client.approve_milestone(&admin, &project_id, &0);
+    client.revoke_milestone(&admin, &project_id, &0);
+    assert!(!client.is_milestone_approved(&project_id, &0));
+    assert_eq!(client.available_to_withdraw(&project_id), 0);
+    let result = client.try_withdraw(&project_id, &0, &1_000);
+    assert_eq!(result, Err(Ok(CrowdfundError::MilestoneNotApproved)));
+
+    // Once funds were withdrawn against it the approval stands
+    client.approve_milestone(&admin, &project_id, &0);
+    client.withdraw(&project_id, &0, &1_000);
+    let result = client.try_revoke_milestone(&admin, &project_id, &0);
+    assert_eq!(result, Err(Ok(CrowdfundError::AlreadyWithdrawn)));
+    assert!(client.is_milestone_approved(&project_id, &0));
+}
+
+#[test]
+fn test_target_in_reference_unit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    // Created before an oracle is configured: target stays in raw tokens
+    let raw_id =
+        client.create_project(&owner, &symbol_short!("Raw"), &1_000, &token_client.address);
+    client.activate_project(&owner, &raw_id);
+    client.deposit(&user, &raw_id, &400);
+    assert!(!client.is_target_met(&raw_id));
+
+    // Target of 1000 USD
+    let oracle_id = env.register(mock_oracle::MockOracle, (25_000_000i128,));
+    client.set_price_oracle(&admin, &Some(oracle_id), &symbol_short!("USD"));
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("Priced"),
+        &1_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &400);
+
+    // At 2.5 USD per token, 400 tokens raise 1000 USD
+    assert!(client.is_target_met(&project_id));
+    assert!(!client.is_target_met(&raw_id));
+
+    // A broken oracle surfaces as an error rather than a panic
+    let broken_id = env.register(mock_oracle::MockOracle, (0i128,));
+    client.set_price_oracle(&admin, &Some(broken_id), &symbol_short!("USD"));
+    let result = client.try_is_target_met(&project_id);
+    assert_eq!(result, Err(Ok(CrowdfundError::OracleUnavailable)));
+
+    client.set_price_oracle(&admin, &None, &symbol_short!("USD"));
+    let result = client.try_is_target_met(&project_id);
+    assert_eq!(result, Err(Ok(CrowdfundError::OracleUnavailable)));
+    assert!(!client.is_target_met(&raw_id));
+}
+
+#[test]
+fn test_deadline_outcome_fixed_once_evaluated() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    // Target of 1000 USD at 2.5 USD per token
+    let oracle_id = env.register(mock_oracle::MockOracle, (25_000_000i128,));
+    client.set_price_oracle(&admin, &Some(oracle_id), &symbol_short!("USD"));
+    let now = env.ledger().timestamp();
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("Priced"),
+        &1_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.set_project_deadline(&owner, &project_id, &(now + 1000));
+    client.deposit(&user, &project_id, &400);
+
+    env.ledger().with_mut(|li| li.timestamp = now + 1000);
+    assert_eq!(
+        client.get_deadline_status(&project_id),
+        DeadlineStatus::Succeeded
+    );
+
+    // A later price drop or oracle outage does not flip the outcome
+    let cheap_id = env.register(mock_oracle::MockOracle, (1_000_000i128,));
+    client.set_price_oracle(&admin, &Some(cheap_id), &symbol_short!("USD"));
+    assert_eq!(
+        client.get_deadline_status(&project_id),
+        DeadlineStatus::Succeeded
+    );
+    assert!(client.is_target_met(&project_id));
+    client.set_price_oracle(&admin, &None, &symbol_short!("USD"));
+    assert_eq!(
+        client.get_deadline_status(&project_id),
+        DeadlineStatus::Succeeded
+    );
+    let result = client.try_refund_contributors(&project_id, &admin);
+    assert_eq!(result, Err(Ok(CrowdfundError::ProjectNotCancellable)));
+}
+
+#[test]
+fn test_deposit_analytics_counters() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let other = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_client.address).mint(&other, &1_000);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("Counted"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+
+    client.deposit(&user, &project_id, &100);
+    client.deposit(&user, &project_id, &200);
+    let project = client.get_project(&project_id);
+    assert_eq!(project.deposit_count, 2);
+    assert_eq!(project.unique_contributors, 1);
+
+    client.deposit(&other, &project_id, &300);
+    let project = client.get_project(&project_id);
+    assert_eq!(project.deposit_count, 3);
+    assert_eq!(project.unique_contributors, 2);
+}
+
+#[test]
+fn test_admin_actions_share_audit_topic() {
+    use crate::events::AdminActionEvent;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _, _) = setup_test(&env);
+    let fee_recipient = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    client.initialize_with_config(&admin, &0, &0, &fee_recipient, &None);
+
+    let assert_audited = |action: &str, admin: &Address| {
+        let expected = AdminActionEvent {
+            action: Symbol::new(&env, action),
+            admin: admin.clone(),
+        };
+        let topics = expected.topics(&env);
+        assert!(
+            env.events()
+                .all()
+                .iter()
+                .any(|(contract, emitted, _)| contract == client.address && emitted == topics),
+            "{action}"
+        );
+    };
+
+    client.pause(&admin);
+    assert_audited("pause", &admin);
+    client.unpause(&admin);
+    assert_audited("unpause", &admin);
+    client.pause_deposits(&admin);
+    assert_audited("pause_deposits", &admin);
+    client.resume_deposits(&admin);
+    assert_audited("resume_deposits", &admin);
+    client.set_deposit_fee_bps(&admin, &100);
+    assert_audited("set_deposit_fee_bps", &admin);
+    client.set_refund_fee_bps(&admin, &100);
+    assert_audited("set_refund_fee_bps", &admin);
+    client.set_fee_recipient_is_contract(&admin, &false);
+    assert_audited("set_fee_recipient_is_contract", &admin);
+    client.set_emit_events(&admin, &true);
+    assert_audited("set_emit_events", &admin);
+    client.set_min_target(&admin, &0);
+    assert_audited("set_min_target", &admin);
+    client.set_milestone_cooldown(&admin, &0);
+    assert_audited("set_milestone_cooldown", &admin);
+    client.allow_token(&admin, &fee_recipient);
+    assert_audited("allow_token", &admin);
+    client.disallow_token(&admin, &fee_recipient);
+    assert_audited("disallow_token", &admin);
+
+    client.migrate(&admin);
+    assert_audited("migrate", &admin);
+
+    client.set_admin(&admin, &new_admin);
+    assert_audited("set_admin", &admin);
+}
+
+#[test]
+fn test_migrate_runs_once_per_version() {
+    use crate::events::VersionBumpedEvent;
+    use crate::storage::{DataKey, ProjectDataV1};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    // Fresh deployments start at version 1 and migrate once
+    assert_eq!(client.get_version(), 1);
+    client.migrate(&admin);
+    assert_eq!(client.get_version(), 2);
+    let result = client.try_migrate(&admin);
+    assert_eq!(result, Err(Ok(CrowdfundError::AlreadyMigrated)));
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("Legacy"),
+        &1_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &400);
+
+    // Simulate storage written by a build that predates versioning
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        storage.set(
+            &DataKey::Project(project_id),
+            &ProjectDataV1 {
+                id: project_id,
+                owner: owner.clone(),
+                name: symbol_short!("Legacy"),
+                target_amount: 1_000,
+                token_address: token_client.address.clone(),
+                total_deposited: 400,
+                total_withdrawn: 0,
+                is_active: true,
+            },
+        );
+        storage.remove(&DataKey::OwnerProjects(owner.clone()));
+        storage.remove(&DataKey::ProjectByName(symbol_short!("Legacy")));
+        storage.remove(&DataKey::ContributorProjects(user.clone()));
+        env.storage().instance().remove(&DataKey::Version);
+    });
+    assert_eq!(client.get_version(), 1);
+
+    client.migrate(&admin);
+    let expected = VersionBumpedEvent {
+        old_version: 1,
+        new_version: 2,
+    };
+    let events = env.events().all();
+    let emitted = events
+        .iter()
+        .find(|(_, topics, _)| *topics == expected.topics(&env))
+        .unwrap();
+    assert_eq!(vec![&env, emitted.2], vec![&env, expected.data(&env)]);
+    assert_eq!(client.get_version(), 2);
+
+    // The project reads in the new layout and its indexes are rebuilt
+    let project = client.get_project(&project_id);
+    assert_eq!(project.total_deposited, 400);
+    assert_eq!(project.unique_contributors, 1);
+    assert_eq!(project.release_bps, 0);
+    assert_eq!(project.reference_denom, None);
+    assert_eq!(client.get_owner_projects(&owner), vec![&env, project_id]);
+    let result = client.try_create_project(
+        &owner,
+        &symbol_short!("Legacy"),
+        &1_000,
+        &token_client.address,
+    );
+    assert_eq!(result, Err(Ok(CrowdfundError::NameTaken)));
+
+    let result = client.try_migrate(&admin);
+    assert_eq!(result, Err(Ok(CrowdfundError::AlreadyMigrated)));
+}
+
+#[test]
+fn test_token_allowlist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, _, token_client) = setup_test(&env);
+    let (junk_token, _) = create_token_contract(&env, &admin);
+    client.initialize(&admin);
+
+    // An empty allowlist allows every token
+    assert!(client.is_token_allowed(&junk_token.address));
+
+    client.allow_token(&admin, &token_client.address);
+    assert!(!client.is_token_allowed(&junk_token.address));
+
+    let result =
+        client.try_create_project(&owner, &symbol_short!("Junk"), &1_000, &junk_token.address);
+    assert_eq!(result, Err(Ok(CrowdfundError::TokenNotAllowed)));
+
+    client.create_project(
+        &owner,
+        &symbol_short!("Listed"),
+        &1_000,
+        &token_client.address,
+    );
+
+    // Emptying the allowlist again restores allow-all
+    client.disallow_token(&admin, &token_client.address);
+    assert!(client.is_token_allowed(&junk_token.address));
+}
+
+#[test]
+fn test_projects_ending_before() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let now = env.ledger().timestamp();
+    let create = |name: Symbol, deadline: u64| {
+        let id = client.create_project(&owner, &name, &1_000, &token_client.address);
+        client.activate_project(&owner, &id);
+        client.set_project_deadline(&owner, &id, &deadline);
+        id
+    };
+    let soon = create(symbol_short!("Soon"), now + 100);
+    let funded = create(symbol_short!("Funded"), now + 100);
+    let later = create(symbol_short!("Later"), now + 5_000);
+
+    // Neither a project without a deadline nor a pending one qualifies
+    let open_ended = client.create_project(
+        &owner,
+        &symbol_short!("OpenEnd"),
+        &1_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &open_ended);
+    client.create_project(
+        &owner,
+        &symbol_short!("Pending"),
+        &1_000,
+        &token_client.address,
+    );
+
+    client.deposit(&user, &soon, &400);
+    client.deposit(&user, &funded, &1_000);
+
+    assert_eq!(
+        client.projects_ending_before(&(now + 1_000)),
+        vec![&env, soon]
+    );
+    assert_eq!(
+        client.projects_ending_before(&(now + 5_000)),
+        vec![&env, soon, later]
+    );
+    assert_eq!(client.projects_ending_before(&now), vec![&env]);
+}
+
+#[test]
+fn test_refund_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let fee_recipient = Address::generate(&env);
+    client.initialize_with_config(&admin, &0, &0, &fee_recipient, &None);
+
+    // 2% refund fee
+    client.set_refund_fee_bps(&admin, &200);
+    assert_eq!(client.get_refund_fee_bps(), 200);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+
+    let user_before = token_client.balance(&user);
+    client.deposit(&user, &project_id, &10_000);
+    client.cancel_project(&owner, &project_id);
+    client.refund_contributors(&project_id, &owner);
+
+    // The contributor receives the net and the fee reaches the recipient
+    assert_eq!(token_client.balance(&user), user_before - 200);
+    assert_eq!(token_client.balance(&fee_recipient), 200);
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    // Fees above the cap are rejected
+    let result = client.try_set_refund_fee_bps(&admin, &1_001);
+    assert_eq!(result, Err(Ok(CrowdfundError::InvalidFee)));
+}
+
+#[test]
+fn test_withdraw_event_carries_schema_version() {
+    use crate::events::WithdrawEvent;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &10_000);
+    client.approve_milestone(&admin, &project_id, &0);
+    client.withdraw(&project_id, &0, &4_000);
+
+    let expected = WithdrawEvent {
+        owner: owner.clone(),
+        project_id,
+        amount: 4_000,
+        schema_version: 1,
+    };
+    let events = env.events().all();
+    let emitted = events
+        .iter()
+        .find(|(contract, topics, _)| {
+            *contract == client.address && *topics == expected.topics(&env)
+        })
+        .unwrap();
+    assert_eq!(
+        vec![&env, emitted],
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                expected.topics(&env),
+                expected.data(&env)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_is_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, _, _) = setup_test(&env);
+    assert!(!client.is_admin(&admin));
+
+    client.initialize(&admin);
+    assert!(client.is_admin(&admin));
+    assert!(!client.is_admin(&owner));
+}
+
+#[test]
+fn test_approve_milestones_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, _, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let create = |name: Symbol| client.create_project(&owner, &name, &1_000, &token_client.address);
+    let first = create(symbol_short!("First"));
+    let second = create(symbol_short!("Second"));
+
+    // The second project already has milestone 0 approved
+    client.approve_milestone(&admin, &second, &0);
+
+    let approved = client.approve_milestones(&admin, &vec![&env, first, 99, second]);
+    assert_eq!(approved, vec![&env, first, second]);
+    assert!(client.is_milestone_approved(&first, &0));
+    assert!(!client.is_milestone_approved(&first, &1));
+    assert!(client.is_milestone_approved(&second, &1));
+
+    let result = client.try_approve_milestones(&owner, &vec![&env, first]);
+    assert_eq!(result, Err(Ok(CrowdfundError::Unauthorized)));
+}
+
+#[test]
+fn test_milestone_approval_cooldown() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, _, token_client) = setup_test(&env);
+    client.initialize(&admin);
+    client.set_milestone_cooldown(&admin, &3_600);
+    assert_eq!(client.get_milestone_cooldown(), 3_600);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+
+    client.approve_milestone(&admin, &project_id, &0);
+    let result = client.try_approve_milestone(&admin, &project_id, &1);
+    assert_eq!(result, Err(Ok(CrowdfundError::ApprovalCooldownActive)));
+    assert_eq!(
+        client.approve_milestones(&admin, &vec![&env, project_id]),
+        vec![&env]
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    client.approve_milestone(&admin, &project_id, &1);
+    assert!(client.is_milestone_approved(&project_id, &1));
+}
+
+#[test]
+fn test_vote_approval_respects_cooldown() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let user2 = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_milestone_cooldown(&admin, &3_600);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    token_client.transfer(&user, &user2, &100_000);
+    client.deposit(&user, &project_id, &600_000);
+    client.deposit(&user2, &project_id, &100_000);
+    client.approve_milestone(&admin, &project_id, &0);
+
+    // A majority vote inside the cooldown is counted but does not approve
+    client.start_milestone_vote(&project_id, &1, &7_200);
+    client.vote_milestone(&user, &project_id, &1, &true);
+    assert!(!client.is_milestone_approved(&project_id, &1));
+    let result = client.try_finalize_milestone_vote(&project_id, &1);
+    assert_eq!(result, Err(Ok(CrowdfundError::ApprovalCooldownActive)));
+
+    // Once the cooldown ends anyone can finalize the passed vote
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    client.finalize_milestone_vote(&project_id, &1);
+    assert!(client.is_milestone_approved(&project_id, &1));
+    let result = client.try_finalize_milestone_vote(&project_id, &1);
+    assert_eq!(result, Err(Ok(CrowdfundError::MilestoneAlreadyApproved)));
+
+    // A vote short of a majority cannot be finalized
+    client.start_milestone_vote(&project_id, &2, &7_200);
+    client.vote_milestone(&user2, &project_id, &2, &true);
+    let result = client.try_finalize_milestone_vote(&project_id, &2);
+    assert_eq!(result, Err(Ok(CrowdfundError::VoteNotPassed)));
+    let result = client.try_finalize_milestone_vote(&project_id, &3);
+    assert_eq!(result, Err(Ok(CrowdfundError::VotingWindowNotStarted)));
+}
+
+#[test]
+fn test_auto_archive_after_full_withdrawal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+    client.set_auto_archive(&admin, &true);
+
+    let create = |name: Symbol| client.create_project(&owner, &name, &1_000, &token_client.address);
+    let funded = create(symbol_short!("Funded"));
+    let other = create(symbol_short!("Other"));
+    client.activate_project(&owner, &funded);
+    client.activate_project(&owner, &other);
+    assert_eq!(client.get_active_projects(), vec![&env, funded, other]);
+
+    client.deposit(&user, &funded, &1_000);
+    client.approve_milestone(&admin, &funded, &0);
+
+    // A partial withdrawal leaves the project active
+    client.withdraw(&funded, &0, &400);
+    assert_eq!(
+        client.get_project_status(&funded),
+        Symbol::new(&env, "ACTIVE")
+    );
+
+    client.withdraw(&funded, &0, &600);
+    assert_eq!(
+        client.get_project_status(&funded),
+        Symbol::new(&env, "ARCHIVED")
+    );
+    assert!(!client.get_project(&funded).is_active);
+    assert_eq!(client.get_active_projects(), vec![&env, other]);
+}
+
+#[test]
+fn test_auto_archive_waits_for_every_milestone() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+    client.set_auto_archive(&admin, &true);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("Stages"),
+        &1_000,
+        &token_client.address,
+    );
+    client.set_project_milestones(&owner, &project_id, &2);
+    client.activate_project(&owner, &project_id);
+
+    // Draining a funded project with milestone 1 still open keeps it active
+    client.deposit(&user, &project_id, &1_000);
+    client.approve_milestone(&admin, &project_id, &0);
+    client.withdraw(&project_id, &0, &1_000);
+    assert_eq!(
+        client.get_project_status(&project_id),
+        Symbol::new(&env, "ACTIVE")
+    );
+
+    client.deposit(&user, &project_id, &200);
+    client.approve_milestone(&admin, &project_id, &1);
+    client.withdraw(&project_id, &1, &200);
+    assert_eq!(
+        client.get_project_status(&project_id),
+        Symbol::new(&env, "ARCHIVED")
+    );
+
+    // The milestone count is fixed once the project leaves pending
+    assert_eq!(
+        client.try_set_project_milestones(&owner, &project_id, &3),
+        Err(Ok(CrowdfundError::ProjectNotPending))
+    );
+}
+
+#[test]
+fn test_withdraw_with_royalty() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let fee_recipient = Address::generate(&env);
+    let originator = Address::generate(&env);
+
+    // 5% platform fee
+    client.initialize_with_config(&admin, &500, &0, &fee_recipient, &None);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+
+    // 20% royalty on what remains after the platform fee
+    client.set_project_royalty(&owner, &project_id, &2_000, &originator);
+    client.activate_project(&owner, &project_id);
+
+    client.deposit(&user, &project_id, &10_000);
+    client.approve_milestone(&admin, &project_id, &0);
+    client.withdraw(&project_id, &0, &10_000);
+
+    assert_eq!(token_client.balance(&fee_recipient), 500);
+    assert_eq!(token_client.balance(&originator), 1_900);
+    assert_eq!(token_client.balance(&owner), 7_600);
+
+    // Royalties are fixed once the project is active
+    let result = client.try_set_project_royalty(&owner, &project_id, &1_000, &originator);
+    assert_eq!(result, Err(Ok(CrowdfundError::ProjectNotPending)));
+
+    let pending = client.create_project(
+        &owner,
+        &symbol_short!("Pending"),
+        &1_000_000,
+        &token_client.address,
+    );
+    let result = client.try_set_project_royalty(&owner, &pending, &5_001, &originator);
+    assert_eq!(result, Err(Ok(CrowdfundError::InvalidRoyalty)));
+}
+
+#[test]
+fn test_deposit_with_memo() {
+    use soroban_sdk::String;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+
+    let memo = String::from_str(&env, "INV-2025-0042");
+    client.deposit_with_memo(&user, &project_id, &1_000, &memo);
+
+    let expected = DepositEvent {
+        user: user.clone(),
+        project_id,
+        amount: 1_000,
+        sponsor: user.clone(),
+        fee: 0,
+        memo: Some(memo),
+        schema_version: 2,
+    };
+    let events = env.events().all();
+    let emitted = events
+        .iter()
+        .find(|(contract, topics, _)| {
+            *contract == client.address && *topics == expected.topics(&env)
+        })
+        .unwrap();
+    assert_eq!(
+        vec![&env, emitted],
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                expected.topics(&env),
+                expected.data(&env)
+            )
+        ]
+    );
+
+    // Otherwise identical to a plain deposit
+    assert_eq!(client.get_balance(&project_id), 1_000);
+    assert_eq!(client.get_contribution(&project_id, &user), 1_000);
+    assert_eq!(client.get_project(&project_id).deposit_count, 1);
+
+    let too_long = String::from_str(&env, &"x".repeat(129));
+    let result = client.try_deposit_with_memo(&user, &project_id, &1_000, &too_long);
+    assert_eq!(result, Err(Ok(CrowdfundError::MemoTooLong)));
+}
+
+#[test]
+fn test_create_project_requires_registered_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, _, token_client) = setup_test(&env);
+    let registry_id = env.register(mock_registry::MockRegistry, ());
+    let registry = mock_registry::MockRegistryClient::new(&env, &registry_id);
+    client.initialize_with_config(&admin, &0, &0, &admin, &Some(registry_id));
+
+    let result = client.try_create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000,
+        &token_client.address,
+    );
+    assert_eq!(result, Err(Ok(CrowdfundError::OwnerNotRegistered)));
+
+    // Registered with zero reputation is enough
+    registry.set_reputation(&owner, &0);
+    client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000,
+        &token_client.address,
+    );
+}
+
+#[test]
+fn test_refundable_projects_lists_only_refundable() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let canceled = client.create_project(
+        &owner,
+        &symbol_short!("Canceled"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &canceled);
+    client.deposit(&user, &canceled, &3_000);
+    client.cancel_project(&owner, &canceled);
+
+    let approved = client.create_project(
+        &owner,
+        &symbol_short!("Approved"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &approved);
+    client.deposit(&user, &approved, &5_000);
+    client.approve_milestone(&admin, &approved, &0);
+
+    let refundable = client.refundable_projects(&user);
+    assert_eq!(refundable, vec![&env, (canceled, 3_000)]);
+
+    // Nothing is left to refund once the contributor has been paid back
+    client.refund_contributors(&canceled, &owner);
+    assert_eq!(client.refundable_projects(&user).len(), 0);
+}
+
+#[test]
+fn test_preview_refunds_matches_refund() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let other = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_client.address).mint(&other, &5_000);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &3_000);
+    client.deposit(&other, &project_id, &5_000);
+    client.deposit(&user, &project_id, &1_000);
+
+    let preview = client.preview_refunds(&project_id);
+    assert_eq!(
+        preview,
+        vec![&env, (user.clone(), 4_000), (other.clone(), 5_000)]
+    );
+    let total: i128 = preview.iter().map(|(_, amount)| amount).sum();
+    assert_eq!(total, client.get_balance(&project_id));
+
+    let user_before = token_client.balance(&user);
+    client.cancel_project(&owner, &project_id);
+    client.refund_contributors(&project_id, &owner);
+    assert_eq!(token_client.balance(&user), user_before + 4_000);
+    assert_eq!(token_client.balance(&other), 5_000);
+}
+
+#[test]
+fn test_preview_refunds_capped_by_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let other = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_client.address).mint(&other, &1_000);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &3_000);
+    client.deposit(&other, &project_id, &1_000);
+
+    // Half of the deposits leave before the project is canceled
+    client.set_milestone_release(&admin, &project_id, &0, &5_000);
+    client.withdraw(&project_id, &0, &2_000);
+
+    let preview = client.preview_refunds(&project_id);
+    assert_eq!(
+        preview,
+        vec![&env, (user.clone(), 1_500), (other.clone(), 500)]
+    );
+    let total: i128 = preview.iter().map(|(_, amount)| amount).sum();
+    assert_eq!(total, client.get_balance(&project_id));
+
+    let user_before = token_client.balance(&user);
+    client.cancel_project(&owner, &project_id);
+    client.refund_contributors(&project_id, &owner);
+    assert_eq!(token_client.balance(&user), user_before + 1_500);
+    assert_eq!(token_client.balance(&other), 500);
+}
+
+#[test]
+fn test_redeposit_after_emptied_position_is_not_relisted() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.deposit(&user, &project_id, &1_000);
+
+    // The user's contribution drops to zero but they stay listed
+    client.transfer_contribution(&user, &buyer, &project_id, &1_000);
+    assert_eq!(client.get_contribution(&project_id, &user), 0);
+    client.deposit(&user, &project_id, &2_000);
+
+    assert_eq!(client.get_contributor_count(&project_id), 2);
+    assert_eq!(client.get_project(&project_id).unique_contributors, 2);
+    assert_eq!(
+        client.preview_refunds(&project_id),
+        vec![&env, (user.clone(), 2_000), (buyer.clone(), 1_000)]
+    );
+}
+
+#[test]
+fn test_refund_chunk_covers_each_contributor_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("TestProj"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+
+    let token_admin = StellarAssetClient::new(&env, &token_client.address);
+    let mut contributors = vec![&env, user.clone()];
+    for _ in 0..4 {
+        contributors.push_back(Address::generate(&env));
+    }
+    for contributor in contributors.iter() {
+        if contributor != user {
+            token_admin.mint(&contributor, &1_000);
+        }
+        client.deposit(&contributor, &project_id, &1_000);
+    }
+    client.cancel_project(&owner, &project_id);
+
+    let user_before = token_client.balance(&user);
+    assert_eq!(client.refund_chunk(&admin, &project_id, &0, &3), 3);
+    // Overlapping the first chunk skips contributors already refunded
+    assert_eq!(client.refund_chunk(&admin, &project_id, &0, &10), 2);
+    assert_eq!(client.refund_chunk(&admin, &project_id, &0, &10), 0);
+
+    assert_eq!(token_client.balance(&user), user_before + 1_000);
+    for contributor in contributors.iter().skip(1) {
+        assert_eq!(token_client.balance(&contributor), 1_000);
+    }
+    assert_eq!(client.get_balance(&project_id), 0);
+
+    let stranger = Address::generate(&env);
+    let result = client.try_refund_chunk(&stranger, &project_id, &0, &10);
+    assert_eq!(result, Err(Ok(CrowdfundError::Unauthorized)));
+}
+
+#[test]
+fn test_private_project_allowlist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, owner, user, token_client) = setup_test(&env);
+    let stranger = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_client.address).mint(&stranger, &1_000);
+    client.initialize(&admin);
+
+    let project_id = client.create_project(
+        &owner,
+        &symbol_short!("Private"),
+        &1_000_000,
+        &token_client.address,
+    );
+    client.activate_project(&owner, &project_id);
+    client.set_project_private(&owner, &project_id, &true);
+    client.allow_depositor(&project_id, &user);
+
+    client.deposit(&user, &project_id, &1_000);
+    let result = client.try_deposit(&stranger, &project_id, &1_000);
+    assert_eq!(result, Err(Ok(CrowdfundError::NotWhitelisted)));
+
+    client.disallow_depositor(&project_id, &user);
+    let result = client.try_deposit(&user, &project_id, &1_000);
+    assert_eq!(result, Err(Ok(CrowdfundError::NotWhitelisted)));
+
+    // Public projects accept anyone
+    client.set_project_private(&owner, &project_id, &false);
+    client.deposit(&stranger, &project_id, &1_000);
+    assert_eq!(client.get_balance(&project_id), 2_000);
+}
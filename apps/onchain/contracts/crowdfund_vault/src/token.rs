@@ -1,9 +1,20 @@
+use crate::errors::CrowdfundError;
 use soroban_sdk::{Address, Env};
 
 /// Transfer tokens from one address to another
-pub fn transfer(env: &Env, token: &Address, from: &Address, to: &Address, amount: &i128) {
+/// A rejected transfer surfaces as `TransferFailed` rather than a host error
+pub fn transfer(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    to: &Address,
+    amount: &i128,
+) -> Result<(), CrowdfundError> {
     let token_client = soroban_sdk::token::Client::new(env, token);
-    token_client.transfer(from, to, amount);
+    match token_client.try_transfer(from, to, amount) {
+        Ok(Ok(())) => Ok(()),
+        _ => Err(CrowdfundError::TransferFailed),
+    }
 }
 
 /// Get the balance of an address for a given token
@@ -12,3 +23,26 @@ pub fn balance(env: &Env, token: &Address, address: &Address) -> i128 {
     let token_client = soroban_sdk::token::Client::new(env, token);
     token_client.balance(address)
 }
+
+/// Transfer tokens from `from` to `to` using the allowance granted to `spender`
+/// A rejected transfer surfaces as `TransferFailed` rather than a host error
+pub fn transfer_from(
+    env: &Env,
+    token: &Address,
+    spender: &Address,
+    from: &Address,
+    to: &Address,
+    amount: &i128,
+) -> Result<(), CrowdfundError> {
+    let token_client = soroban_sdk::token::Client::new(env, token);
+    match token_client.try_transfer_from(spender, from, to, amount) {
+        Ok(Ok(())) => Ok(()),
+        _ => Err(CrowdfundError::TransferFailed),
+    }
+}
+
+/// Get the amount `spender` is allowed to transfer on behalf of `from`
+pub fn allowance(env: &Env, token: &Address, from: &Address, spender: &Address) -> i128 {
+    let token_client = soroban_sdk::token::Client::new(env, token);
+    token_client.allowance(from, spender)
+}
@@ -0,0 +1,8 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// Interface of a treasury contract that accounts for the fees it receives
+#[contractclient(name = "FeeTreasuryClient")]
+pub trait FeeTreasury {
+    /// Called after `amount` of `token` has been transferred to the treasury
+    fn receive_fee(env: Env, token: Address, amount: i128);
+}
@@ -60,6 +60,7 @@ fn test_lumenpulse_protocol_e2e() {
         &5000i128,
         &token_id,
     );
+    vault_client.activate_project(&project_owner, &project_id);
 
     // Step D: Contributor deposits into the project
     vault_client.deposit(&contributor, &project_id, &3000i128);
@@ -126,6 +127,7 @@ fn test_notification_flow() {
         &5000i128,
         &token_id,
     );
+    vault_client.activate_project(&project_owner, &project_id);
 
     // Contributor deposits into the project
     vault_client.deposit(&contributor, &project_id, &1000i128);
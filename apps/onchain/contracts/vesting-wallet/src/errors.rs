@@ -13,4 +13,24 @@ pub enum VestingError {
     InvalidStartTime = 7,
     NothingToClaim = 8,
     InsufficientBalance = 9,
+    ScheduleFrozen = 10,
+    SameAdmin = 11,
+    ScheduleStillVesting = 12,
+    InvalidToken = 13,
+    NoSurplus = 14,
+    RecipientNotAllowed = 15,
+    InvalidUnlockPoints = 16,
+    InvalidFee = 17,
+    InvalidSplit = 18,
+    ClaimNotExpired = 19,
+    AlreadyMigrated = 20,
+    NotAccepted = 21,
+    LabelTooLong = 22,
+    DurationTooShort = 23,
+    InvalidCurve = 24,
+    ScheduleHeld = 25,
+    NotHeld = 26,
+    StakingNotConfigured = 27,
+    ScheduleStarted = 28,
+    MigrationIncomplete = 29,
 }
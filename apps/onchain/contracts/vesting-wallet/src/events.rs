@@ -1,4 +1,28 @@
-use soroban_sdk::{contractevent, Address, BytesN};
+use soroban_sdk::{contractevent, Address, BytesN, Env, String, Symbol};
+
+/// Emitted by every privileged entry point. All such events share the
+/// `admin_action` topic so an indexer can follow admin activity with one
+/// filter; `action` names the entry point.
+#[contractevent(topics = ["admin_action"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminActionEvent {
+    #[topic]
+    pub action: Symbol,
+    #[topic]
+    pub admin: Address,
+}
+
+/// Publish an [`AdminActionEvent`] for a privileged call
+pub fn admin_action(env: &Env, action: &str, admin: &Address) {
+    AdminActionEvent {
+        action: Symbol::new(env, action),
+        admin: admin.clone(),
+    }
+    .publish(env);
+}
+
+/// Schema version carried by versioned events; bump when their fields change
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
 
 #[contractevent]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -17,6 +41,80 @@ pub struct TokensClaimedEvent {
     pub beneficiary: Address,
     pub amount_claimed: i128,
     pub remaining: i128,
+    pub fee: i128,
+    pub schema_version: u32,
+}
+
+/// Emitted when a beneficiary's schedule is frozen or unfrozen by the admin.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleFrozenEvent {
+    #[topic]
+    pub beneficiary: Address,
+    pub frozen: bool,
+}
+
+/// Emitted when the admin places or releases a hold on a beneficiary's claims.
+/// `reason` is the reason the hold was placed with.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleHeldEvent {
+    #[topic]
+    pub beneficiary: Address,
+    pub held: bool,
+    pub reason: String,
+}
+
+/// Emitted when a beneficiary accepts a schedule that required acceptance.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingAcceptedEvent {
+    #[topic]
+    pub beneficiary: Address,
+    pub start_time: u64,
+}
+
+/// Emitted when the admin cancels a schedule, with the vested amount paid to
+/// the beneficiary, the unvested amount returned to the admin, and whether the
+/// beneficiary authorized the cancellation too.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingCanceledEvent {
+    #[topic]
+    pub beneficiary: Address,
+    pub vested_paid: i128,
+    pub unvested_returned: i128,
+    pub mutual: bool,
+}
+
+/// Emitted when the admin reclaims the unclaimed remainder of an expired
+/// schedule.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingExpiredEvent {
+    #[topic]
+    pub beneficiary: Address,
+    pub reclaimed: i128,
+}
+
+/// Emitted when unallocated tokens are recovered by the admin.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SurplusRecoveredEvent {
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+}
+
+/// Emitted when the vesting token is switched by `migrate_token`. `swept` is
+/// the old token balance returned to the admin.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMigratedEvent {
+    #[topic]
+    pub old_token: Address,
+    pub new_token: Address,
+    pub swept: i128,
 }
 
 #[contractevent]
@@ -35,3 +133,11 @@ pub struct AdminChangedEvent {
     pub old_admin: Address,
     pub new_admin: Address,
 }
+
+/// Emitted when `migrate` moves storage to a new layout version.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VersionBumpedEvent {
+    pub old_version: u32,
+    pub new_version: u32,
+}
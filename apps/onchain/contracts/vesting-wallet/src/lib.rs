@@ -2,38 +2,188 @@
 
 mod errors;
 mod events;
+pub mod receiver;
+pub mod registry;
+pub mod staking;
 mod storage;
 mod token;
 
 use errors::VestingError;
-use events::{AdminChangedEvent, UpgradedEvent};
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env};
-use storage::{DataKey, VestingData};
-use token::transfer;
+use events::{
+    AdminChangedEvent, ScheduleFrozenEvent, ScheduleHeldEvent, SurplusRecoveredEvent,
+    TokenMigratedEvent, UpgradedEvent, VersionBumpedEvent, VestingAcceptedEvent,
+    VestingCanceledEvent, VestingExpiredEvent,
+};
+use receiver::TokenReceiverClient;
+use registry::ContributorRegistryClient;
+use soroban_sdk::{
+    contract, contractimpl, vec, Address, BytesN, Env, Executable, Map, String, Symbol, Val, Vec,
+};
+use staking::StakingRewardsClient;
+use storage::{
+    DataKey, VestingConfig, VestingCurve, VestingData, VestingDataV1, VestingState,
+    VESTING_V1_FIELDS,
+};
+use token::{balance, decimals, transfer};
+
+/// Storage layout version written by this build; bump alongside a `migrate` step
+const STORAGE_VERSION: u32 = 2;
+
+/// Maximum claim fee in basis points (10%)
+const MAX_CLAIM_FEE_BPS: u32 = 1_000;
+
+/// Basis point denominator (100%)
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Maximum length of a schedule label
+const MAX_LABEL_LEN: u32 = 64;
+
+/// Largest exponent accepted for a front-loaded curve
+const MAX_CURVE_EXPONENT: u32 = 4;
+
+/// Fixed-point scale used when evaluating front-loaded curves
+const CURVE_SCALE: u128 = 1_000_000_000;
+
+/// Number of claim checkpoints kept for `claims_since`; older ones are overwritten
+const MAX_CLAIM_CHECKPOINTS: u32 = 1_024;
 
 #[contract]
 pub struct VestingWalletContract;
 
 #[contractimpl]
 impl VestingWalletContract {
-    /// Helper function to calculate claimable amount for a vesting schedule
-    /// This is used by both get_claimable and claim to ensure consistency
-    fn calculate_claimable_amount(current_time: u64, vesting: &VestingData) -> i128 {
-        if current_time < vesting.start_time {
+    /// Whether a schedule's clock is still waiting for the beneficiary to accept
+    fn awaiting_start(vesting: &VestingData) -> bool {
+        vesting.pending_acceptance && vesting.start_on_acceptance
+    }
+
+    /// Helper function to calculate the gross amount vested for a schedule,
+    /// including anything already claimed
+    fn calculate_vested_amount(current_time: u64, vesting: &VestingData) -> i128 {
+        if Self::awaiting_start(vesting) || current_time < vesting.start_time {
             // Vesting hasn't started yet
             0
         } else if current_time >= vesting.start_time + vesting.duration {
-            // Vesting period has ended, all tokens are available
-            vesting.total_amount - vesting.claimed_amount
+            // Vesting period has ended, all tokens are vested
+            vesting.total_amount
+        } else if !vesting.unlock_points.is_empty() {
+            // Custom curve: hold the cumulative amount of the last point reached
+            let time_elapsed = current_time - vesting.start_time;
+            let mut total_vested = 0;
+            for (offset, cumulative) in vesting.unlock_points.iter() {
+                if offset > time_elapsed {
+                    break;
+                }
+                total_vested = cumulative;
+            }
+            total_vested
+        } else if let VestingCurve::FrontLoaded(exponent) = vesting.curve {
+            let time_elapsed = current_time - vesting.start_time;
+            Self::front_loaded_amount(
+                vesting.total_amount,
+                time_elapsed,
+                vesting.duration,
+                exponent,
+            )
         } else {
             // Calculate linearly vested amount
             let time_elapsed = current_time - vesting.start_time;
-            let total_vested = (vesting.total_amount as u128)
+            (vesting.total_amount as u128)
                 .checked_mul(time_elapsed as u128)
                 .and_then(|x| x.checked_div(vesting.duration as u128))
-                .unwrap_or(0) as i128;
-            total_vested - vesting.claimed_amount
+                .unwrap_or(0) as i128
+        }
+    }
+
+    /// Amount unlocked by a front-loaded curve: `total * (elapsed / duration)^(1 / exponent)`
+    ///
+    /// The fraction is taken in `CURVE_SCALE` fixed point and its root found by
+    /// binary search, so every intermediate fits in a `u128` for exponents up
+    /// to `MAX_CURVE_EXPONENT`. The result rounds down.
+    fn front_loaded_amount(total: i128, elapsed: u64, duration: u64, exponent: u32) -> i128 {
+        let fraction = CURVE_SCALE * elapsed as u128 / duration as u128;
+        let target = fraction * CURVE_SCALE.pow(exponent - 1);
+        let (mut low, mut high) = (0u128, CURVE_SCALE);
+        while low < high {
+            let mid = (low + high).div_ceil(2);
+            if mid.pow(exponent) <= target {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        let total = total as u128;
+        (total / CURVE_SCALE * low + total % CURVE_SCALE * low / CURVE_SCALE) as i128
+    }
+
+    /// Helper function to calculate claimable amount for a vesting schedule
+    /// This is used by both get_claimable and claim to ensure consistency
+    ///
+    /// With `round_to` set, the vested amount is floored to a multiple of it
+    /// until the schedule is fully vested, so only the final claim carries dust.
+    fn calculate_claimable_amount(current_time: u64, vesting: &VestingData) -> i128 {
+        let mut vested = Self::calculate_vested_amount(current_time, vesting);
+        if vesting.round_to > 1 && vested < vesting.total_amount {
+            vested -= vested % vesting.round_to;
+        }
+        (vested - vesting.claimed_amount).max(0)
+    }
+
+    /// Helper function to verify admin authorization
+    fn verify_admin(env: &Env, caller: &Address) -> Result<(), VestingError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(VestingError::NotInitialized)?;
+
+        if caller != &stored_admin {
+            return Err(VestingError::Unauthorized);
+        }
+
+        caller.require_auth();
+        Ok(())
+    }
+
+    /// Set the frozen flag on a beneficiary's schedule (admin only)
+    fn set_frozen(
+        env: &Env,
+        admin: &Address,
+        beneficiary: Address,
+        frozen: bool,
+    ) -> Result<(), VestingError> {
+        Self::verify_admin(env, admin)?;
+
+        let mut vesting: VestingData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Vesting(beneficiary.clone()))
+            .ok_or(VestingError::VestingNotFound)?;
+
+        vesting.frozen = frozen;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Vesting(beneficiary.clone()), &vesting);
+
+        ScheduleFrozenEvent {
+            beneficiary,
+            frozen,
         }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Apply a delta to the running total of tokens backing schedules
+    fn adjust_outstanding(env: &Env, delta: i128) {
+        let outstanding: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalOutstanding)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalOutstanding, &(outstanding + delta));
     }
 
     /// Initialize the contract with an admin address and token address
@@ -46,9 +196,19 @@ impl VestingWalletContract {
         // Require admin authorization
         admin.require_auth();
 
+        // Catch a misconfigured token address early; the decimals are cached
+        // so clients can format amounts without a separate token call
+        let token_decimals = decimals(&env, &token).ok_or(VestingError::InvalidToken)?;
+
         // Store admin address and token address
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::Token, &token);
+        env.storage()
+            .instance()
+            .set(&DataKey::TokenDecimals, &token_decimals);
+        env.storage()
+            .instance()
+            .set(&DataKey::Version, &STORAGE_VERSION);
 
         Ok(())
     }
@@ -61,6 +221,73 @@ impl VestingWalletContract {
         amount: i128,
         start_time: u64,
         duration: u64,
+    ) -> Result<(), VestingError> {
+        Self::do_create_vesting(
+            &env,
+            &admin,
+            &beneficiary,
+            amount,
+            start_time,
+            duration,
+            vec![&env],
+        )?;
+        events::admin_action(&env, "create_vesting", &admin);
+        Ok(())
+    }
+
+    /// Create a vesting schedule that unlocks along a custom curve
+    ///
+    /// `unlock_points` are `(offset_from_start, cumulative_unlocked)` pairs with
+    /// strictly increasing offsets and non-decreasing cumulative amounts; the
+    /// last point must unlock the full `amount`. Between points the unlocked
+    /// amount holds at the previous point.
+    pub fn create_custom_vesting(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        amount: i128,
+        start_time: u64,
+        unlock_points: Vec<(u64, i128)>,
+    ) -> Result<(), VestingError> {
+        let mut previous: Option<(u64, i128)> = None;
+        for (offset, cumulative) in unlock_points.iter() {
+            if cumulative < 0 || cumulative > amount {
+                return Err(VestingError::InvalidUnlockPoints);
+            }
+            if let Some((prev_offset, prev_cumulative)) = previous {
+                if offset <= prev_offset || cumulative < prev_cumulative {
+                    return Err(VestingError::InvalidUnlockPoints);
+                }
+            }
+            previous = Some((offset, cumulative));
+        }
+
+        let (duration, final_cumulative) = previous.ok_or(VestingError::InvalidUnlockPoints)?;
+        if final_cumulative != amount {
+            return Err(VestingError::InvalidUnlockPoints);
+        }
+
+        Self::do_create_vesting(
+            &env,
+            &admin,
+            &beneficiary,
+            amount,
+            start_time,
+            duration,
+            unlock_points,
+        )?;
+        events::admin_action(&env, "create_custom_vesting", &admin);
+        Ok(())
+    }
+
+    fn do_create_vesting(
+        env: &Env,
+        admin: &Address,
+        beneficiary: &Address,
+        amount: i128,
+        start_time: u64,
+        duration: u64,
+        unlock_points: Vec<(u64, i128)>,
     ) -> Result<(), VestingError> {
         // Check if contract is initialized
         let stored_admin: Address = env
@@ -70,7 +297,7 @@ impl VestingWalletContract {
             .ok_or(VestingError::NotInitialized)?;
 
         // Verify admin identity
-        if admin != stored_admin {
+        if *admin != stored_admin {
             return Err(VestingError::Unauthorized);
         }
 
@@ -86,6 +313,14 @@ impl VestingWalletContract {
         if duration == 0 {
             return Err(VestingError::InvalidDuration);
         }
+        let min_duration: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinDuration)
+            .unwrap_or(0);
+        if duration < min_duration {
+            return Err(VestingError::DurationTooShort);
+        }
 
         // Validate start time (should be in the future or current time)
         let current_time = env.ledger().timestamp();
@@ -102,21 +337,23 @@ impl VestingWalletContract {
 
         let contract_address = env.current_contract_address();
 
-        // If vesting already exists, return remaining tokens to admin
-        // (total_amount - claimed_amount)
+        // If vesting already exists, pay the beneficiary what has already vested
+        // and return only the unvested remainder to the admin. Vested funds the
+        // beneficiary cannot claim yet keep the old schedule in place.
         if let Some(existing_vesting) = env
             .storage()
             .persistent()
             .get::<_, VestingData>(&DataKey::Vesting(beneficiary.clone()))
         {
-            let remaining = existing_vesting.total_amount - existing_vesting.claimed_amount;
-            if remaining > 0 {
-                transfer(&env, &token, &contract_address, &admin, &remaining);
+            if Self::must_escrow(env, &existing_vesting) {
+                Self::check_claimable(env, &existing_vesting)?;
             }
+            Self::settle_schedule(env, &token, admin, &existing_vesting);
         }
 
         // Transfer tokens from admin to contract
-        transfer(&env, &token, &admin, &contract_address, &amount);
+        transfer(env, &token, admin, &contract_address, &amount);
+        Self::adjust_outstanding(env, amount);
 
         // Create vesting data
         let vesting = VestingData {
@@ -125,12 +362,29 @@ impl VestingWalletContract {
             start_time,
             duration,
             claimed_amount: 0,
+            frozen: false,
+            unlock_points,
+            claim_expiry: 0,
+            pending_acceptance: false,
+            start_on_acceptance: false,
+            label: String::from_str(env, ""),
+            round_to: 0,
+            curve: VestingCurve::Linear,
         };
 
         // Store vesting data
         env.storage()
             .persistent()
-            .set(&DataKey::Vesting(beneficiary), &vesting);
+            .set(&DataKey::Vesting(beneficiary.clone()), &vesting);
+
+        // Track the beneficiary for contract-wide queries
+        let mut beneficiaries = Self::get_beneficiaries(env.clone());
+        if !beneficiaries.contains(beneficiary) {
+            beneficiaries.push_back(beneficiary.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::Beneficiaries, &beneficiaries);
+        }
 
         // Emit VestingCreated event
         events::VestingCreatedEvent {
@@ -139,13 +393,102 @@ impl VestingWalletContract {
             start_time: vesting.start_time,
             duration: vesting.duration,
         }
-        .publish(&env);
+        .publish(env);
 
         Ok(())
     }
 
     /// Claim available tokens based on linear vesting schedule
+    ///
+    /// If the beneficiary configured a claim split, the tokens are distributed
+    /// across the split recipients.
     pub fn claim(env: Env, beneficiary: Address) -> Result<i128, VestingError> {
+        Self::do_claim(&env, &beneficiary, None, false)
+    }
+
+    /// Pull accrued staking rewards into the contract, claim as `claim` does,
+    /// and pay the pulled rewards to the beneficiary
+    ///
+    /// Requires a configured staking contract. The rewards are measured by the
+    /// contract's token balance before and after the pull, and the returned
+    /// amount is the vested claim plus the rewards.
+    pub fn claim_with_rewards(env: Env, beneficiary: Address) -> Result<i128, VestingError> {
+        let staking: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::StakingContract)
+            .ok_or(VestingError::StakingNotConfigured)?;
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(VestingError::NotInitialized)?;
+        let contract_address = env.current_contract_address();
+
+        let before = balance(&env, &token, &contract_address);
+        StakingRewardsClient::new(&env, &staking).claim_rewards(&contract_address);
+        let reward = (balance(&env, &token, &contract_address) - before).max(0);
+
+        let claimed = Self::do_claim(&env, &beneficiary, None, false)?;
+        if reward > 0 {
+            transfer(&env, &token, &contract_address, &beneficiary, &reward);
+        }
+
+        Ok(claimed + reward)
+    }
+
+    /// Claim for several beneficiaries in one call, each to their own address
+    ///
+    /// Every beneficiary must authorize their claim. Returns the claimed amount
+    /// per beneficiary, with 0 where nothing was claimable; other errors abort
+    /// the batch.
+    pub fn claim_batch(env: Env, beneficiaries: Vec<Address>) -> Result<Vec<i128>, VestingError> {
+        let mut claimed = vec![&env];
+        for beneficiary in beneficiaries.iter() {
+            match Self::do_claim(&env, &beneficiary, None, false) {
+                Ok(amount) => claimed.push_back(amount),
+                Err(VestingError::NothingToClaim) => claimed.push_back(0),
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(claimed)
+    }
+
+    /// Claim available tokens and deliver them to `recipient`
+    ///
+    /// If the admin configured a recipient allowlist for the beneficiary, the
+    /// recipient must be on it. With `notify` set and a contract recipient, the
+    /// recipient's `on_tokens_received(token, amount)` is called after the
+    /// transfer; accounts always receive a plain transfer.
+    pub fn claim_to(
+        env: Env,
+        beneficiary: Address,
+        recipient: Address,
+        notify: bool,
+    ) -> Result<i128, VestingError> {
+        let allowed: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RecipientAllowlist(beneficiary.clone()))
+            .unwrap_or(vec![&env]);
+        if !allowed.is_empty() && !allowed.contains(&recipient) {
+            return Err(VestingError::RecipientNotAllowed);
+        }
+
+        Self::do_claim(&env, &beneficiary, Some(&recipient), notify)
+    }
+
+    /// Internal claim logic shared by `claim` and `claim_to`
+    ///
+    /// Without an explicit `recipient` the tokens follow the beneficiary's claim
+    /// split, or go to the beneficiary when none is set. `notify` calls back a
+    /// contract `recipient` once the claim is settled.
+    fn do_claim(
+        env: &Env,
+        beneficiary: &Address,
+        recipient: Option<&Address>,
+        notify: bool,
+    ) -> Result<i128, VestingError> {
         // Check if contract is initialized
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(VestingError::NotInitialized);
@@ -161,6 +504,9 @@ impl VestingWalletContract {
             .get(&DataKey::Vesting(beneficiary.clone()))
             .ok_or(VestingError::VestingNotFound)?;
 
+        // Frozen schedules keep accruing but cannot be claimed
+        Self::check_claimable(env, &vesting)?;
+
         // Get current time
         let current_time = env.ledger().timestamp();
 
@@ -179,21 +525,76 @@ impl VestingWalletContract {
             .get(&DataKey::Token)
             .ok_or(VestingError::NotInitialized)?;
 
-        // Transfer tokens from contract to beneficiary
+        let splits: Vec<(Address, u32)> = match recipient {
+            Some(_) => vec![env],
+            None => env
+                .storage()
+                .persistent()
+                .get(&DataKey::ClaimSplit(beneficiary.clone()))
+                .unwrap_or(vec![env]),
+        };
+
+        // The allowlist may have changed since the split was set
+        if !splits.is_empty() {
+            let allowed: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::RecipientAllowlist(beneficiary.clone()))
+                .unwrap_or(vec![env]);
+            if !allowed.is_empty()
+                && splits
+                    .iter()
+                    .any(|(split_recipient, _)| !allowed.contains(&split_recipient))
+            {
+                return Err(VestingError::RecipientNotAllowed);
+            }
+        }
+
+        // Skim the claim fee, if configured, and transfer the rest to the recipient
         let contract_address = env.current_contract_address();
-        transfer(
-            &env,
-            &token,
-            &contract_address,
-            &beneficiary,
-            &available_amount,
-        );
+        let fee_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ClaimFeeBps)
+            .unwrap_or(0);
+        let fee = available_amount * fee_bps as i128 / BPS_DENOMINATOR;
+        if fee > 0 {
+            let fee_recipient: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::FeeRecipient)
+                .ok_or(VestingError::InvalidFee)?;
+            transfer(env, &token, &contract_address, &fee_recipient, &fee);
+        }
+        let payout = available_amount - fee;
+        if splits.is_empty() {
+            let recipient = recipient.unwrap_or(beneficiary);
+            transfer(env, &token, &contract_address, recipient, &payout);
+        } else {
+            // The last recipient receives the rounding remainder
+            let mut distributed = 0;
+            let last = splits.len() - 1;
+            for (i, (split_recipient, bps)) in splits.iter().enumerate() {
+                let share = if i as u32 == last {
+                    payout - distributed
+                } else {
+                    payout * bps as i128 / BPS_DENOMINATOR
+                };
+                if share > 0 {
+                    transfer(env, &token, &contract_address, &split_recipient, &share);
+                }
+                distributed += share;
+            }
+        }
 
         // Update claimed amount
         vesting.claimed_amount += available_amount;
         env.storage()
             .persistent()
-            .set(&DataKey::Vesting(beneficiary), &vesting);
+            .set(&DataKey::Vesting(beneficiary.clone()), &vesting);
+
+        Self::adjust_outstanding(env, -available_amount);
+        Self::record_claimed(env, beneficiary, available_amount);
 
         // Emit TokensClaimed event
         let remaining = vesting.total_amount - vesting.claimed_amount;
@@ -201,92 +602,1299 @@ impl VestingWalletContract {
             beneficiary: vesting.beneficiary.clone(),
             amount_claimed: available_amount,
             remaining,
+            fee,
+            schema_version: events::EVENT_SCHEMA_VERSION,
+        }
+        .publish(env);
+
+        // Reward the beneficiary for completing the grant
+        if remaining == 0 {
+            Self::award_completion_bonus(env, beneficiary);
+        }
+
+        // Notify a contract recipient only after all state is settled
+        if let Some(recipient) = recipient.filter(|_| notify) {
+            if let Some(Executable::Wasm(_)) = recipient.executable() {
+                TokenReceiverClient::new(env, recipient).on_tokens_received(&token, &payout);
+            }
         }
-        .publish(&env);
 
         Ok(available_amount)
     }
 
-    /// Get the claimable amount for a beneficiary without modifying state
-    /// This is a pure view method that returns how much a beneficiary could claim at the current time
-    pub fn get_claimable(env: Env, beneficiary: Address) -> Result<i128, VestingError> {
-        // Get vesting data
-        let vesting: VestingData = env
+    /// Award the configured reputation bonus through the contributor registry
+    ///
+    /// Skipped when no registry is configured; failures such as an unregistered
+    /// beneficiary never block the claim.
+    fn award_completion_bonus(env: &Env, beneficiary: &Address) {
+        let Some(registry) = env
             .storage()
-            .persistent()
-            .get(&DataKey::Vesting(beneficiary))
-            .ok_or(VestingError::VestingNotFound)?;
+            .instance()
+            .get::<_, Address>(&DataKey::Registry)
+        else {
+            return;
+        };
+        let bonus: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::CompletionBonus)
+            .unwrap_or(0);
+        if bonus == 0 {
+            return;
+        }
 
-        // Get current time
-        let current_time = env.ledger().timestamp();
+        let _ = ContributorRegistryClient::new(env, &registry).try_award_reputation(
+            &env.current_contract_address(),
+            beneficiary,
+            &bonus,
+        );
+    }
 
-        // Calculate claimable amount using the helper function
-        let claimable_amount = Self::calculate_claimable_amount(current_time, &vesting);
+    /// Re-lock the claimable amount into a fresh schedule instead of transferring it.
+    ///
+    /// The new schedule starts now and keeps the tokens in the contract. Since
+    /// each beneficiary holds a single schedule, a relock while still vesting
+    /// folds the unvested remainder in too, vesting both linearly over the
+    /// longer of `relock_duration` and the time the old schedule had left, so
+    /// nothing unlocks sooner than it would have. Schedules with custom unlock
+    /// points must finish vesting first. Returns the relocked claimable amount.
+    pub fn claim_and_relock(
+        env: Env,
+        beneficiary: Address,
+        relock_duration: u64,
+    ) -> Result<i128, VestingError> {
+        // Check if contract is initialized
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(VestingError::NotInitialized);
+        }
 
-        Ok(claimable_amount)
-    }
+        // Require beneficiary authorization
+        beneficiary.require_auth();
 
-    /// Get vesting data for a beneficiary
-    pub fn get_vesting(env: Env, beneficiary: Address) -> Result<VestingData, VestingError> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Vesting(beneficiary))
-            .ok_or(VestingError::VestingNotFound)
-    }
+        // Validate duration
+        if relock_duration == 0 {
+            return Err(VestingError::InvalidDuration);
+        }
+        let min_duration: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinDuration)
+            .unwrap_or(0);
+        if relock_duration < min_duration {
+            return Err(VestingError::DurationTooShort);
+        }
 
-    /// Get the available amount that can be claimed by a beneficiary
-    pub fn get_available_amount(env: Env, beneficiary: Address) -> Result<i128, VestingError> {
         // Get vesting data
         let vesting: VestingData = env
             .storage()
             .persistent()
-            .get(&DataKey::Vesting(beneficiary))
+            .get(&DataKey::Vesting(beneficiary.clone()))
             .ok_or(VestingError::VestingNotFound)?;
 
-        // Get current time
-        let current_time = env.ledger().timestamp();
+        Self::check_claimable(&env, &vesting)?;
 
-        // Calculate available amount using the helper function
-        let available_amount = Self::calculate_claimable_amount(current_time, &vesting);
+        let current_time = env.ledger().timestamp();
+        let vesting_end = vesting.start_time + vesting.duration;
+        if current_time < vesting_end && !vesting.unlock_points.is_empty() {
+            return Err(VestingError::ScheduleStillVesting);
+        }
 
-        Ok(available_amount)
-    }
+        let relock_amount = Self::calculate_claimable_amount(current_time, &vesting);
+        if relock_amount <= 0 {
+            return Err(VestingError::NothingToClaim);
+        }
 
-    /// Get admin address
-    pub fn get_admin(env: Env) -> Result<Address, VestingError> {
+        // Replace the schedule with the relocked amount plus any unvested remainder
+        let relocked = VestingData {
+            beneficiary: beneficiary.clone(),
+            total_amount: vesting.total_amount - vesting.claimed_amount,
+            start_time: current_time,
+            duration: relock_duration.max(vesting_end.saturating_sub(current_time)),
+            claimed_amount: 0,
+            frozen: false,
+            unlock_points: vec![&env],
+            claim_expiry: vesting.claim_expiry,
+            pending_acceptance: false,
+            start_on_acceptance: false,
+            label: vesting.label,
+            round_to: vesting.round_to,
+            curve: VestingCurve::Linear,
+        };
         env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(VestingError::NotInitialized)
+            .persistent()
+            .set(&DataKey::Vesting(beneficiary), &relocked);
+
+        events::VestingCreatedEvent {
+            beneficiary: relocked.beneficiary,
+            amount: relocked.total_amount,
+            start_time: relocked.start_time,
+            duration: relocked.duration,
+        }
+        .publish(&env);
+
+        Ok(relock_amount)
     }
 
-    /// Get token address
-    pub fn get_token(env: Env) -> Result<Address, VestingError> {
+    /// Link a contributor registry that rewards completed grants (admin only)
+    ///
+    /// When a claim completes a schedule, the beneficiary is awarded `bonus`
+    /// reputation. The registry must allow this contract as a reputation source.
+    pub fn set_completion_bonus(
+        env: Env,
+        admin: Address,
+        registry: Address,
+        bonus: u64,
+    ) -> Result<(), VestingError> {
+        Self::verify_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::Registry, &registry);
         env.storage()
             .instance()
-            .get(&DataKey::Token)
-            .ok_or(VestingError::NotInitialized)
+            .set(&DataKey::CompletionBonus, &bonus);
+        events::admin_action(&env, "set_completion_bonus", &admin);
+        Ok(())
     }
 
-    /// Upgrade the contract WASM to a new hash.
-    ///
-    /// Only the stored admin may call this. Emits [`UpgradedEvent`] on success.
-    pub fn upgrade(
+    /// Set the staking contract `claim_with_rewards` pulls rewards from (admin only)
+    /// Passing `None` disables `claim_with_rewards`
+    pub fn set_staking_contract(
         env: Env,
-        caller: Address,
-        new_wasm_hash: BytesN<32>,
+        admin: Address,
+        staking: Option<Address>,
     ) -> Result<(), VestingError> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(VestingError::NotInitialized)?;
-        if caller != admin {
-            return Err(VestingError::Unauthorized);
+        Self::verify_admin(&env, &admin)?;
+        match staking {
+            Some(staking) => env
+                .storage()
+                .instance()
+                .set(&DataKey::StakingContract, &staking),
+            None => env.storage().instance().remove(&DataKey::StakingContract),
+        }
+        events::admin_action(&env, "set_staking_contract", &admin);
+        Ok(())
+    }
+
+    /// Get the configured staking contract, if any
+    pub fn get_staking_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::StakingContract)
+    }
+
+    /// Set the shortest duration new schedules may use (admin only, 0 disables)
+    pub fn set_min_duration(
+        env: Env,
+        admin: Address,
+        min_duration: u64,
+    ) -> Result<(), VestingError> {
+        Self::verify_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::MinDuration, &min_duration);
+        events::admin_action(&env, "set_min_duration", &admin);
+        Ok(())
+    }
+
+    /// Get the minimum schedule duration (0 if not configured)
+    pub fn get_min_duration(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinDuration)
+            .unwrap_or(0)
+    }
+
+    /// Configure the fee skimmed from each claim (admin only)
+    ///
+    /// `fee_bps` is capped at 10%; a fee of 0 disables it.
+    pub fn set_claim_fee(
+        env: Env,
+        admin: Address,
+        fee_bps: u32,
+        fee_recipient: Address,
+    ) -> Result<(), VestingError> {
+        Self::verify_admin(&env, &admin)?;
+
+        if fee_bps > MAX_CLAIM_FEE_BPS {
+            return Err(VestingError::InvalidFee);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ClaimFeeBps, &fee_bps);
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeRecipient, &fee_recipient);
+
+        events::admin_action(&env, "set_claim_fee", &admin);
+        Ok(())
+    }
+
+    /// Get the claim fee in basis points (0 if not configured)
+    pub fn get_claim_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ClaimFeeBps)
+            .unwrap_or(0)
+    }
+
+    /// Get the claim fee recipient, if configured
+    pub fn get_fee_recipient(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::FeeRecipient)
+    }
+
+    /// Restrict the addresses a beneficiary may claim to (admin only).
+    ///
+    /// An empty list allows any recipient.
+    pub fn set_recipient_allowlist(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        allowed: Vec<Address>,
+    ) -> Result<(), VestingError> {
+        Self::verify_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::RecipientAllowlist(beneficiary), &allowed);
+        events::admin_action(&env, "set_recipient_allowlist", &admin);
+        Ok(())
+    }
+
+    /// Split every `claim` of a beneficiary across several recipients.
+    ///
+    /// `splits` pairs each recipient with a share in basis points; the shares
+    /// must be non-zero, name each recipient once and sum to 10000. Recipients
+    /// must be on the beneficiary's recipient allowlist, if one is set, both
+    /// now and when each claim is paid. An empty list removes the split.
+    pub fn set_claim_split(
+        env: Env,
+        beneficiary: Address,
+        splits: Vec<(Address, u32)>,
+    ) -> Result<(), VestingError> {
+        beneficiary.require_auth();
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Vesting(beneficiary.clone()))
+        {
+            return Err(VestingError::VestingNotFound);
+        }
+
+        let key = DataKey::ClaimSplit(beneficiary.clone());
+        if splits.is_empty() {
+            env.storage().persistent().remove(&key);
+            return Ok(());
+        }
+
+        // Split recipients are bound by the beneficiary's recipient allowlist
+        let allowed: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RecipientAllowlist(beneficiary))
+            .unwrap_or(vec![&env]);
+
+        let mut seen: Vec<Address> = Vec::new(&env);
+        let mut total_bps: u32 = 0;
+        for (recipient, bps) in splits.iter() {
+            if bps == 0 || seen.contains(&recipient) {
+                return Err(VestingError::InvalidSplit);
+            }
+            if !allowed.is_empty() && !allowed.contains(&recipient) {
+                return Err(VestingError::RecipientNotAllowed);
+            }
+            seen.push_back(recipient);
+            total_bps = total_bps
+                .checked_add(bps)
+                .ok_or(VestingError::InvalidSplit)?;
+        }
+        if total_bps as i128 != BPS_DENOMINATOR {
+            return Err(VestingError::InvalidSplit);
+        }
+
+        env.storage().persistent().set(&key, &splits);
+        Ok(())
+    }
+
+    /// Require the beneficiary to accept a schedule before claiming (admin only)
+    ///
+    /// With `start_on_acceptance`, nothing vests until acceptance: `start_time`
+    /// is cleared and set to the acceptance timestamp by `accept_vesting`.
+    /// Only schedules that have not started vesting can require acceptance.
+    pub fn require_acceptance(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        start_on_acceptance: bool,
+    ) -> Result<(), VestingError> {
+        Self::verify_admin(&env, &admin)?;
+
+        let key = DataKey::Vesting(beneficiary);
+        let mut vesting: VestingData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(VestingError::VestingNotFound)?;
+        if Self::awaiting_start(&vesting) {
+            return Err(VestingError::NotAccepted);
+        }
+        // Acceptance cannot be imposed on a schedule that is already vesting
+        if vesting.claimed_amount > 0 || vesting.start_time <= env.ledger().timestamp() {
+            return Err(VestingError::ScheduleStarted);
+        }
+        vesting.pending_acceptance = true;
+        vesting.start_on_acceptance = start_on_acceptance;
+        if start_on_acceptance {
+            vesting.start_time = 0;
+        }
+        env.storage().persistent().set(&key, &vesting);
+        events::admin_action(&env, "require_acceptance", &admin);
+        Ok(())
+    }
+
+    /// Set the human-readable label of a schedule (admin only)
+    pub fn set_label(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        label: String,
+    ) -> Result<(), VestingError> {
+        Self::verify_admin(&env, &admin)?;
+
+        if label.len() > MAX_LABEL_LEN {
+            return Err(VestingError::LabelTooLong);
+        }
+
+        let key = DataKey::Vesting(beneficiary);
+        let mut vesting: VestingData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(VestingError::VestingNotFound)?;
+        vesting.label = label;
+        env.storage().persistent().set(&key, &vesting);
+        events::admin_action(&env, "set_label", &admin);
+        Ok(())
+    }
+
+    /// Set the unit claims of a schedule are rounded down to (admin only, 0 disables)
+    pub fn set_round_to(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        round_to: i128,
+    ) -> Result<(), VestingError> {
+        Self::verify_admin(&env, &admin)?;
+
+        if round_to < 0 {
+            return Err(VestingError::InvalidAmount);
+        }
+
+        let key = DataKey::Vesting(beneficiary);
+        let mut vesting: VestingData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(VestingError::VestingNotFound)?;
+        vesting.round_to = round_to;
+        env.storage().persistent().set(&key, &vesting);
+        events::admin_action(&env, "set_round_to", &admin);
+        Ok(())
+    }
+
+    /// Set the curve a schedule vests along (admin only)
+    ///
+    /// Front-loaded exponents must be within `1..=MAX_CURVE_EXPONENT`; schedules
+    /// with custom unlock points keep following those points and are rejected.
+    pub fn set_vesting_curve(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        curve: VestingCurve,
+    ) -> Result<(), VestingError> {
+        Self::verify_admin(&env, &admin)?;
+
+        if let VestingCurve::FrontLoaded(exponent) = curve {
+            if exponent == 0 || exponent > MAX_CURVE_EXPONENT {
+                return Err(VestingError::InvalidCurve);
+            }
+        }
+
+        let key = DataKey::Vesting(beneficiary);
+        let mut vesting: VestingData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(VestingError::VestingNotFound)?;
+        if !vesting.unlock_points.is_empty() {
+            return Err(VestingError::InvalidCurve);
+        }
+        vesting.curve = curve;
+        env.storage().persistent().set(&key, &vesting);
+        events::admin_action(&env, "set_vesting_curve", &admin);
+        Ok(())
+    }
+
+    /// Accept a schedule that requires acceptance, enabling claims
+    ///
+    /// Emits [`VestingAcceptedEvent`] with the effective start time.
+    pub fn accept_vesting(env: Env, beneficiary: Address) -> Result<(), VestingError> {
+        beneficiary.require_auth();
+
+        let key = DataKey::Vesting(beneficiary.clone());
+        let mut vesting: VestingData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(VestingError::VestingNotFound)?;
+        if !vesting.pending_acceptance {
+            return Ok(());
+        }
+
+        if vesting.start_on_acceptance {
+            vesting.start_time = env.ledger().timestamp();
+        }
+        vesting.pending_acceptance = false;
+        env.storage().persistent().set(&key, &vesting);
+
+        VestingAcceptedEvent {
+            beneficiary,
+            start_time: vesting.start_time,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Freeze a beneficiary's schedule so claims are blocked (admin only).
+    ///
+    /// Vesting continues to accrue while frozen. Emits [`ScheduleFrozenEvent`].
+    pub fn freeze_schedule(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+    ) -> Result<(), VestingError> {
+        Self::set_frozen(&env, &admin, beneficiary, true)?;
+        events::admin_action(&env, "freeze_schedule", &admin);
+        Ok(())
+    }
+
+    /// Unfreeze a beneficiary's schedule so claims resume (admin only).
+    ///
+    /// Emits [`ScheduleFrozenEvent`].
+    pub fn unfreeze_schedule(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+    ) -> Result<(), VestingError> {
+        Self::set_frozen(&env, &admin, beneficiary, false)?;
+        events::admin_action(&env, "unfreeze_schedule", &admin);
+        Ok(())
+    }
+
+    /// Place a legal hold on a beneficiary's claims, recording why (admin only).
+    ///
+    /// Like a freeze, vesting keeps accruing while claims are blocked; the
+    /// reason is stored and emitted in [`ScheduleHeldEvent`].
+    pub fn hold_schedule(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        reason: String,
+    ) -> Result<(), VestingError> {
+        Self::verify_admin(&env, &admin)?;
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Vesting(beneficiary.clone()))
+        {
+            return Err(VestingError::VestingNotFound);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Hold(beneficiary.clone()), &reason);
+
+        ScheduleHeldEvent {
+            beneficiary,
+            held: true,
+            reason,
+        }
+        .publish(&env);
+
+        events::admin_action(&env, "hold_schedule", &admin);
+        Ok(())
+    }
+
+    /// Release a beneficiary's legal hold so claims resume (admin only).
+    ///
+    /// Emits [`ScheduleHeldEvent`] carrying the reason the hold was placed with.
+    pub fn release_hold(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+    ) -> Result<(), VestingError> {
+        Self::verify_admin(&env, &admin)?;
+
+        let key = DataKey::Hold(beneficiary.clone());
+        let reason: String = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(VestingError::NotHeld)?;
+        env.storage().persistent().remove(&key);
+
+        ScheduleHeldEvent {
+            beneficiary,
+            held: false,
+            reason,
+        }
+        .publish(&env);
+
+        events::admin_action(&env, "release_hold", &admin);
+        Ok(())
+    }
+
+    /// Get the reason a beneficiary's claims are on hold, if they are
+    pub fn get_hold(env: Env, beneficiary: Address) -> Option<String> {
+        env.storage().persistent().get(&DataKey::Hold(beneficiary))
+    }
+
+    /// Whether a beneficiary's claims are on legal hold
+    fn is_held(env: &Env, beneficiary: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Hold(beneficiary.clone()))
+    }
+
+    /// Check that a schedule can pay out: not frozen, on hold or awaiting acceptance
+    fn check_claimable(env: &Env, vesting: &VestingData) -> Result<(), VestingError> {
+        if vesting.frozen {
+            return Err(VestingError::ScheduleFrozen);
+        }
+        if Self::is_held(env, &vesting.beneficiary) {
+            return Err(VestingError::ScheduleHeld);
+        }
+        if vesting.pending_acceptance {
+            return Err(VestingError::NotAccepted);
+        }
+        Ok(())
+    }
+
+    /// Recover vesting tokens sent to the contract outside of `create_vesting` (admin only).
+    ///
+    /// Only the surplus above the amount backing schedules is transferred to `to`.
+    /// Rejects with `MigrationIncomplete` while legacy schedules await `migrate`.
+    /// Emits [`SurplusRecoveredEvent`].
+    pub fn recover_surplus(env: Env, admin: Address, to: Address) -> Result<i128, VestingError> {
+        Self::verify_admin(&env, &admin)?;
+
+        // Schedules not yet migrated are missing from the outstanding total
+        let version: u32 = env.storage().instance().get(&DataKey::Version).unwrap_or(0);
+        if version < STORAGE_VERSION {
+            return Err(VestingError::MigrationIncomplete);
+        }
+
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(VestingError::NotInitialized)?;
+
+        let contract_address = env.current_contract_address();
+        let surplus =
+            balance(&env, &token, &contract_address) - Self::total_outstanding(env.clone());
+        if surplus <= 0 {
+            return Err(VestingError::NoSurplus);
+        }
+
+        transfer(&env, &token, &contract_address, &to, &surplus);
+
+        SurplusRecoveredEvent {
+            to,
+            amount: surplus,
+        }
+        .publish(&env);
+
+        events::admin_action(&env, "recover_surplus", &admin);
+        Ok(surplus)
+    }
+
+    /// Switch every schedule to a new token (admin only)
+    ///
+    /// The admin funds the contract with `new_token` beforehand; the migration
+    /// is rejected unless that balance covers the total outstanding. Schedules
+    /// are paid in `new_token` from then on, so the whole old token balance is
+    /// sent back to the admin and the swept amount returned. Emits
+    /// [`TokenMigratedEvent`].
+    pub fn migrate_token(
+        env: Env,
+        admin: Address,
+        new_token: Address,
+    ) -> Result<i128, VestingError> {
+        Self::verify_admin(&env, &admin)?;
+
+        let old_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(VestingError::NotInitialized)?;
+        if new_token == old_token {
+            return Err(VestingError::InvalidToken);
+        }
+        let token_decimals = decimals(&env, &new_token).ok_or(VestingError::InvalidToken)?;
+
+        let contract_address = env.current_contract_address();
+        let outstanding = Self::total_outstanding(env.clone());
+        if balance(&env, &new_token, &contract_address) < outstanding {
+            return Err(VestingError::InsufficientBalance);
+        }
+
+        // Nothing is owed in the old token any more
+        let swept = balance(&env, &old_token, &contract_address);
+        if swept > 0 {
+            transfer(&env, &old_token, &contract_address, &admin, &swept);
+        }
+
+        env.storage().instance().set(&DataKey::Token, &new_token);
+        env.storage()
+            .instance()
+            .set(&DataKey::TokenDecimals, &token_decimals);
+
+        TokenMigratedEvent {
+            old_token,
+            new_token,
+            swept,
+        }
+        .publish(&env);
+
+        events::admin_action(&env, "migrate_token", &admin);
+        Ok(swept)
+    }
+
+    /// Cancel a beneficiary's schedule (admin only).
+    ///
+    /// Everything vested but unclaimed is paid to the beneficiary, the unvested
+    /// remainder returns to the admin, and the schedule is deleted. If the
+    /// beneficiary cannot claim right now, the vested amount stays in escrow as
+    /// a fully vested schedule instead. With `mutual` set the beneficiary must
+    /// authorize the cancellation as well. Emits [`VestingCanceledEvent`].
+    pub fn cancel_vesting(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        mutual: bool,
+    ) -> Result<(i128, i128), VestingError> {
+        Self::verify_admin(&env, &admin)?;
+        if mutual {
+            beneficiary.require_auth();
+        }
+
+        let vesting: VestingData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Vesting(beneficiary.clone()))
+            .ok_or(VestingError::VestingNotFound)?;
+
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(VestingError::NotInitialized)?;
+
+        let escrowed = Self::must_escrow(&env, &vesting);
+        let (vested_unclaimed, unvested) = Self::settle_schedule(&env, &token, &admin, &vesting);
+        if !escrowed {
+            Self::remove_schedule(&env, &beneficiary);
+        }
+
+        VestingCanceledEvent {
+            beneficiary,
+            vested_paid: vested_unclaimed,
+            unvested_returned: unvested,
+            mutual,
+        }
+        .publish(&env);
+
+        events::admin_action(&env, "cancel_vesting", &admin);
+        Ok((vested_unclaimed, unvested))
+    }
+
+    /// Set how long after full vesting a schedule may go unclaimed (admin only)
+    ///
+    /// A `claim_expiry` of 0 disables expiry for the schedule.
+    pub fn set_claim_expiry(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        claim_expiry: u64,
+    ) -> Result<(), VestingError> {
+        Self::verify_admin(&env, &admin)?;
+
+        let key = DataKey::Vesting(beneficiary);
+        let mut vesting: VestingData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(VestingError::VestingNotFound)?;
+        vesting.claim_expiry = claim_expiry;
+        env.storage().persistent().set(&key, &vesting);
+        events::admin_action(&env, "set_claim_expiry", &admin);
+        Ok(())
+    }
+
+    /// Reclaim the unclaimed remainder of an expired schedule (admin only)
+    ///
+    /// Allowed once `start_time + duration + claim_expiry` has passed. The
+    /// remainder returns to the admin and the schedule is deleted.
+    /// Emits [`VestingExpiredEvent`].
+    pub fn reclaim_expired(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+    ) -> Result<i128, VestingError> {
+        Self::verify_admin(&env, &admin)?;
+
+        let vesting: VestingData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Vesting(beneficiary.clone()))
+            .ok_or(VestingError::VestingNotFound)?;
+
+        let expires_at = vesting.start_time + vesting.duration + vesting.claim_expiry;
+        if vesting.claim_expiry == 0
+            || Self::awaiting_start(&vesting)
+            || env.ledger().timestamp() < expires_at
+        {
+            return Err(VestingError::ClaimNotExpired);
+        }
+
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(VestingError::NotInitialized)?;
+
+        let remaining = vesting.total_amount - vesting.claimed_amount;
+        if remaining > 0 {
+            transfer(
+                &env,
+                &token,
+                &env.current_contract_address(),
+                &admin,
+                &remaining,
+            );
+            Self::adjust_outstanding(&env, -remaining);
+        }
+
+        Self::remove_schedule(&env, &beneficiary);
+
+        VestingExpiredEvent {
+            beneficiary,
+            reclaimed: remaining,
+        }
+        .publish(&env);
+
+        events::admin_action(&env, "reclaim_expired", &admin);
+        Ok(remaining)
+    }
+
+    /// Pay out a schedule being ended early: the vested but unclaimed amount
+    /// goes to the beneficiary and the unvested remainder to the admin
+    ///
+    /// While the schedule is frozen, on hold or awaiting acceptance, the vested
+    /// amount stays in escrow: the schedule is cut down to it, fully vested,
+    /// and kept until it can be claimed. Returns the amount paid to the
+    /// beneficiary and the amount returned.
+    fn settle_schedule(
+        env: &Env,
+        token: &Address,
+        admin: &Address,
+        vesting: &VestingData,
+    ) -> (i128, i128) {
+        let now = env.ledger().timestamp();
+        let vested_unclaimed = Self::calculate_claimable_amount(now, vesting);
+        let unvested = vesting.total_amount - vesting.claimed_amount - vested_unclaimed;
+
+        let contract_address = env.current_contract_address();
+        if unvested > 0 {
+            transfer(env, token, &contract_address, admin, &unvested);
+            Self::adjust_outstanding(env, -unvested);
+        }
+
+        if Self::must_escrow(env, vesting) {
+            let mut escrowed = vesting.clone();
+            escrowed.total_amount = vesting.claimed_amount + vested_unclaimed;
+            escrowed.duration = now.saturating_sub(vesting.start_time);
+            escrowed.unlock_points = Vec::new(env);
+            escrowed.curve = VestingCurve::Linear;
+            escrowed.round_to = 0;
+            escrowed.claim_expiry = 0;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Vesting(vesting.beneficiary.clone()), &escrowed);
+            return (0, unvested);
+        }
+
+        if vested_unclaimed > 0 {
+            transfer(
+                env,
+                token,
+                &contract_address,
+                &vesting.beneficiary,
+                &vested_unclaimed,
+            );
+            Self::record_claimed(env, &vesting.beneficiary, vested_unclaimed);
+            Self::adjust_outstanding(env, -vested_unclaimed);
+        }
+
+        (vested_unclaimed, unvested)
+    }
+
+    /// Whether settling a schedule now would leave vested funds the
+    /// beneficiary cannot claim yet
+    fn must_escrow(env: &Env, vesting: &VestingData) -> bool {
+        Self::check_claimable(env, vesting).is_err()
+            && Self::calculate_claimable_amount(env.ledger().timestamp(), vesting) > 0
+    }
+
+    /// Add a payout to the global and the beneficiary's lifetime claimed totals
+    fn record_claimed(env: &Env, beneficiary: &Address, amount: i128) {
+        let total_claimed: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalClaimed)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalClaimed, &(total_claimed + amount));
+
+        // Claims sharing a ledger timestamp share one checkpoint, and the
+        // checkpoints form a ring that overwrites the oldest once full
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ClaimCheckpointCount)
+            .unwrap_or(0);
+        let now = env.ledger().timestamp();
+        if count == 0 || Self::claim_checkpoint(env, count - 1).0 != now {
+            env.storage().persistent().set(
+                &DataKey::ClaimCheckpoint(count % MAX_CLAIM_CHECKPOINTS),
+                &(now, total_claimed),
+            );
+            env.storage()
+                .instance()
+                .set(&DataKey::ClaimCheckpointCount, &(count + 1));
+        }
+
+        let lifetime_key = DataKey::LifetimeClaimed(beneficiary.clone());
+        let lifetime: i128 = env.storage().persistent().get(&lifetime_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&lifetime_key, &(lifetime + amount));
+    }
+
+    /// Get the `(timestamp, total claimed before it)` checkpoint written as
+    /// the `index`-th one, which must still be within the ring
+    fn claim_checkpoint(env: &Env, index: u32) -> (u64, i128) {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ClaimCheckpoint(index % MAX_CLAIM_CHECKPOINTS))
+            .unwrap_or((0, 0))
+    }
+
+    /// Delete a beneficiary's schedule along with its split, recipient
+    /// allowlist, hold and list entry
+    fn remove_schedule(env: &Env, beneficiary: &Address) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Vesting(beneficiary.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ClaimSplit(beneficiary.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RecipientAllowlist(beneficiary.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Hold(beneficiary.clone()));
+
+        let mut beneficiaries = Self::get_beneficiaries(env.clone());
+        if let Some(index) = beneficiaries.first_index_of(beneficiary) {
+            beneficiaries.remove(index);
+            env.storage()
+                .persistent()
+                .set(&DataKey::Beneficiaries, &beneficiaries);
+        }
+    }
+
+    /// Get the sum claimable across all schedules at the current time
+    ///
+    /// The worst-case amount the treasury could be asked to pay out right now.
+    pub fn total_claimable_now(env: Env) -> i128 {
+        let current_time = env.ledger().timestamp();
+        let mut total = 0;
+        for beneficiary in Self::get_beneficiaries(env.clone()).iter() {
+            if let Some(vesting) = env
+                .storage()
+                .persistent()
+                .get::<_, VestingData>(&DataKey::Vesting(beneficiary))
+            {
+                total += Self::calculate_claimable_amount(current_time, &vesting);
+            }
+        }
+        total
+    }
+
+    /// Get the total amount allocated to schedules but not yet claimed
+    pub fn total_outstanding(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalOutstanding)
+            .unwrap_or(0)
+    }
+
+    /// Get the claimable amount for a beneficiary without modifying state
+    /// This is a pure view method that returns how much a beneficiary could claim at the current time
+    pub fn get_claimable(env: Env, beneficiary: Address) -> Result<i128, VestingError> {
+        // Get vesting data
+        let vesting: VestingData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Vesting(beneficiary))
+            .ok_or(VestingError::VestingNotFound)?;
+
+        // Get current time
+        let current_time = env.ledger().timestamp();
+
+        // Calculate claimable amount using the helper function
+        let claimable_amount = Self::calculate_claimable_amount(current_time, &vesting);
+
+        Ok(claimable_amount)
+    }
+
+    /// Get the gross amount vested so far, including already-claimed tokens
+    ///
+    /// Unlike `get_claimable`, this ignores `claimed_amount`.
+    pub fn get_vested_total(env: Env, beneficiary: Address) -> Result<i128, VestingError> {
+        let vesting: VestingData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Vesting(beneficiary))
+            .ok_or(VestingError::VestingNotFound)?;
+
+        Ok(Self::calculate_vested_amount(
+            env.ledger().timestamp(),
+            &vesting,
+        ))
+    }
+
+    /// Check whether `claim` would currently succeed for a beneficiary
+    ///
+    /// True when the contract is initialized, the schedule exists and is not
+    /// frozen or held, and a positive amount has vested but not been claimed.
+    pub fn can_claim(env: Env, beneficiary: Address) -> bool {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return false;
+        }
+
+        match env
+            .storage()
+            .persistent()
+            .get::<_, VestingData>(&DataKey::Vesting(beneficiary.clone()))
+        {
+            Some(vesting) => {
+                Self::check_claimable(&env, &vesting).is_ok()
+                    && Self::calculate_claimable_amount(env.ledger().timestamp(), &vesting) > 0
+            }
+            None => false,
+        }
+    }
+
+    /// Get the amount a beneficiary could claim at an arbitrary `timestamp`
+    /// This is a pure view method used to project future unlocks; timestamps
+    /// before the schedule start naturally return 0
+    pub fn get_claimable_at(
+        env: Env,
+        beneficiary: Address,
+        timestamp: u64,
+    ) -> Result<i128, VestingError> {
+        // Get vesting data
+        let vesting: VestingData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Vesting(beneficiary))
+            .ok_or(VestingError::VestingNotFound)?;
+
+        // Calculate claimable amount at the supplied time
+        Ok(Self::calculate_claimable_amount(timestamp, &vesting))
+    }
+
+    /// Get the number of seconds until a beneficiary's schedule is fully vested
+    /// Returns 0 once the vesting period has ended
+    pub fn time_to_full_vest(env: Env, beneficiary: Address) -> Result<u64, VestingError> {
+        let vesting: VestingData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Vesting(beneficiary))
+            .ok_or(VestingError::VestingNotFound)?;
+
+        if Self::awaiting_start(&vesting) {
+            return Ok(vesting.duration);
+        }
+        let end_time = vesting.start_time + vesting.duration;
+        Ok(end_time.saturating_sub(env.ledger().timestamp()))
+    }
+
+    /// Check the contract's accounting invariants
+    ///
+    /// Every schedule must satisfy `0 <= claimed_amount <= total_amount`, and the
+    /// contract's token balance must cover the total outstanding allocation.
+    /// Meant for property tests and monitoring.
+    pub fn check_invariants(env: Env) -> bool {
+        let mut outstanding = 0i128;
+        for beneficiary in Self::get_beneficiaries(env.clone()).iter() {
+            if let Some(vesting) = env
+                .storage()
+                .persistent()
+                .get::<_, VestingData>(&DataKey::Vesting(beneficiary))
+            {
+                if vesting.claimed_amount < 0 || vesting.claimed_amount > vesting.total_amount {
+                    return false;
+                }
+                outstanding += vesting.total_amount - vesting.claimed_amount;
+            }
+        }
+
+        let total_outstanding = Self::total_outstanding(env.clone());
+        if outstanding != total_outstanding {
+            return false;
+        }
+
+        match env.storage().instance().get::<_, Address>(&DataKey::Token) {
+            Some(token) => {
+                balance(&env, &token, &env.current_contract_address()) >= total_outstanding
+            }
+            None => total_outstanding == 0,
+        }
+    }
+
+    /// Get every beneficiary that currently holds a schedule
+    pub fn get_beneficiaries(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Beneficiaries)
+            .unwrap_or(vec![&env])
+    }
+
+    /// Get the number of schedules a beneficiary holds
+    ///
+    /// Each beneficiary holds at most one schedule, so this is 0 or 1.
+    pub fn schedule_count(env: Env, beneficiary: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Vesting(beneficiary)) as u32
+    }
+
+    /// Get the beneficiaries whose schedule is fully vested by `timestamp`,
+    /// paired with the time their schedule ends
+    pub fn schedules_ending_before(env: Env, timestamp: u64) -> Vec<(Address, u64)> {
+        let mut ending = vec![&env];
+        for beneficiary in Self::get_beneficiaries(env.clone()).iter() {
+            if let Some(vesting) = env
+                .storage()
+                .persistent()
+                .get::<_, VestingData>(&DataKey::Vesting(beneficiary.clone()))
+            {
+                let end_time = vesting.start_time + vesting.duration;
+                if !Self::awaiting_start(&vesting) && end_time <= timestamp {
+                    ending.push_back((beneficiary, end_time));
+                }
+            }
+        }
+        ending
+    }
+
+    /// Get the beneficiaries whose schedule has fully vested but still holds
+    /// an unclaimed remainder between 1 and `threshold`
+    ///
+    /// Lets maintenance tooling find schedules left with rounding dust.
+    pub fn schedules_with_dust(env: Env, threshold: i128) -> Vec<Address> {
+        let now = env.ledger().timestamp();
+        let mut dusty = vec![&env];
+        for beneficiary in Self::get_beneficiaries(env.clone()).iter() {
+            if let Some(vesting) = env
+                .storage()
+                .persistent()
+                .get::<_, VestingData>(&DataKey::Vesting(beneficiary.clone()))
+            {
+                let remaining = vesting.total_amount - vesting.claimed_amount;
+                let ended =
+                    !Self::awaiting_start(&vesting) && now >= vesting.start_time + vesting.duration;
+                if ended && remaining >= 1 && remaining <= threshold {
+                    dusty.push_back(beneficiary);
+                }
+            }
+        }
+        dusty
+    }
+
+    /// Get vesting data for a beneficiary
+    pub fn get_vesting(env: Env, beneficiary: Address) -> Result<VestingData, VestingError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Vesting(beneficiary))
+            .ok_or(VestingError::VestingNotFound)
+    }
+
+    /// Get the available amount that can be claimed by a beneficiary
+    pub fn get_available_amount(env: Env, beneficiary: Address) -> Result<i128, VestingError> {
+        // Get vesting data
+        let vesting: VestingData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Vesting(beneficiary))
+            .ok_or(VestingError::VestingNotFound)?;
+
+        // Get current time
+        let current_time = env.ledger().timestamp();
+
+        // Calculate available amount using the helper function
+        let available_amount = Self::calculate_claimable_amount(current_time, &vesting);
+
+        Ok(available_amount)
+    }
+
+    /// Get the total amount claimed across all beneficiaries
+    pub fn total_claimed(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalClaimed)
+            .unwrap_or(0)
+    }
+
+    /// Get the total a beneficiary has ever been paid, across every schedule
+    /// they have held, including ones since canceled or deleted
+    pub fn lifetime_claimed(env: Env, beneficiary: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LifetimeClaimed(beneficiary))
+            .unwrap_or(0)
+    }
+
+    /// Get the total claimed across all schedules at or after `since`
+    ///
+    /// Each distinct claim timestamp stores a checkpoint of the running total
+    /// before it, found by a binary search over the checkpoints. Only the
+    /// latest `MAX_CLAIM_CHECKPOINTS` (1,024) are kept, overwriting the oldest,
+    /// so storage is bounded at that many small persistent entries. The result
+    /// is exact for windows starting within the retained checkpoints; a window
+    /// reaching further back is counted from the oldest checkpoint kept.
+    pub fn claims_since(env: Env, since: u64) -> i128 {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ClaimCheckpointCount)
+            .unwrap_or(0);
+
+        // First retained checkpoint at or after `since`
+        let (mut low, mut high) = (count.saturating_sub(MAX_CLAIM_CHECKPOINTS), count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if Self::claim_checkpoint(&env, mid).0 < since {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        if low == count {
+            return 0;
+        }
+        let (_, claimed_before) = Self::claim_checkpoint(&env, low);
+        Self::total_claimed(env) - claimed_before
+    }
+
+    /// Get a compact snapshot of the contract's global state
+    pub fn get_state(env: Env) -> Result<VestingState, VestingError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(VestingError::NotInitialized)?;
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(VestingError::NotInitialized)?;
+
+        Ok(VestingState {
+            admin,
+            token,
+            total_claimed: Self::total_claimed(env),
+        })
+    }
+
+    /// Get the contract's configuration in a single call
+    pub fn get_config(env: Env) -> Result<VestingConfig, VestingError> {
+        Ok(VestingConfig {
+            admin: Self::get_admin(env.clone())?,
+            token: Self::get_token(env.clone())?,
+            token_decimals: Self::get_decimals(env.clone()),
+            claim_fee_bps: Self::get_claim_fee_bps(env.clone()),
+            fee_recipient: Self::get_fee_recipient(env.clone()),
+            min_duration: Self::get_min_duration(env),
+        })
+    }
+
+    /// Get admin address
+    pub fn get_admin(env: Env) -> Result<Address, VestingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(VestingError::NotInitialized)
+    }
+
+    /// Check whether `address` is the stored admin (false before initialization)
+    pub fn is_admin(env: Env, address: Address) -> bool {
+        env.storage()
+            .instance()
+            .get::<_, Address>(&DataKey::Admin)
+            .is_some_and(|admin| admin == address)
+    }
+
+    /// Get token address
+    pub fn get_token(env: Env) -> Result<Address, VestingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(VestingError::NotInitialized)
+    }
+
+    /// Get the vesting token's decimals, cached at initialization
+    pub fn get_decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TokenDecimals)
+            .unwrap_or(0)
+    }
+
+    /// Upgrade the contract WASM to a new hash.
+    ///
+    /// Only the stored admin may call this. Emits [`UpgradedEvent`] on success.
+    pub fn upgrade(
+        env: Env,
+        caller: Address,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), VestingError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(VestingError::NotInitialized)?;
+        if caller != admin {
+            return Err(VestingError::Unauthorized);
         }
         caller.require_auth();
         env.deployer()
             .update_current_contract_wasm(new_wasm_hash.clone());
+        events::admin_action(&env, "upgrade", &caller);
         UpgradedEvent {
             admin: caller,
             new_wasm_hash,
@@ -295,9 +1903,140 @@ impl VestingWalletContract {
         Ok(())
     }
 
+    /// Migrate storage to the current layout after an upgrade (admin only)
+    ///
+    /// Call after `upgrade` installs WASM with a newer storage version.
+    /// Storage before version 2 kept no index of schedules, so the admin
+    /// declares `legacy_count`, the number of schedules in the old layout, and
+    /// lists their beneficiaries in `beneficiaries`, in one call or in batches;
+    /// only the first batch records `legacy_count`. Listed schedules are
+    /// rewritten in the current layout and indexed, and the total outstanding
+    /// is recomputed from the indexed schedules. The version is bumped once
+    /// every legacy schedule is migrated; until then `recover_surplus` rejects.
+    /// Rejects with `AlreadyMigrated` once storage is at [`STORAGE_VERSION`].
+    pub fn migrate(
+        env: Env,
+        admin: Address,
+        legacy_count: u32,
+        beneficiaries: Vec<Address>,
+    ) -> Result<(), VestingError> {
+        Self::verify_admin(&env, &admin)?;
+
+        let version: u32 = env.storage().instance().get(&DataKey::Version).unwrap_or(0);
+        if version >= STORAGE_VERSION {
+            return Err(VestingError::AlreadyMigrated);
+        }
+
+        // Version 2 extended `VestingData` and added the beneficiary index,
+        // the outstanding total and the cached token decimals
+        if version < 2 {
+            let remaining: u32 = env
+                .storage()
+                .instance()
+                .get(&DataKey::MigrationRemaining)
+                .unwrap_or(legacy_count);
+            let migrated = Self::migrate_schedules(&env, &beneficiaries)?;
+            let remaining = remaining.saturating_sub(migrated);
+            if remaining > 0 {
+                env.storage()
+                    .instance()
+                    .set(&DataKey::MigrationRemaining, &remaining);
+                events::admin_action(&env, "migrate", &admin);
+                return Ok(());
+            }
+            env.storage()
+                .instance()
+                .remove(&DataKey::MigrationRemaining);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Version, &STORAGE_VERSION);
+
+        VersionBumpedEvent {
+            old_version: version,
+            new_version: STORAGE_VERSION,
+        }
+        .publish(&env);
+        events::admin_action(&env, "migrate", &admin);
+        Ok(())
+    }
+
+    /// Rewrite the listed schedules still stored as `VestingDataV1` in the
+    /// current layout, index them, and recompute the total outstanding
+    /// Returns how many schedules were rewritten
+    fn migrate_schedules(env: &Env, beneficiaries: &Vec<Address>) -> Result<u32, VestingError> {
+        let mut migrated = 0;
+        let mut indexed = Self::get_beneficiaries(env.clone());
+        for beneficiary in beneficiaries.iter() {
+            let key = DataKey::Vesting(beneficiary.clone());
+            let Some(stored) = env.storage().persistent().get::<_, Map<Symbol, Val>>(&key) else {
+                return Err(VestingError::VestingNotFound);
+            };
+            if stored.len() == VESTING_V1_FIELDS {
+                let legacy: VestingDataV1 = env.storage().persistent().get(&key).unwrap();
+                let vesting = VestingData {
+                    beneficiary: legacy.beneficiary,
+                    total_amount: legacy.total_amount,
+                    start_time: legacy.start_time,
+                    duration: legacy.duration,
+                    claimed_amount: legacy.claimed_amount,
+                    frozen: false,
+                    unlock_points: Vec::new(env),
+                    claim_expiry: 0,
+                    pending_acceptance: false,
+                    start_on_acceptance: false,
+                    label: String::from_str(env, ""),
+                    round_to: 0,
+                    curve: VestingCurve::Linear,
+                };
+                env.storage().persistent().set(&key, &vesting);
+                migrated += 1;
+            }
+            if !indexed.contains(&beneficiary) {
+                indexed.push_back(beneficiary);
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Beneficiaries, &indexed);
+
+        // Everything still owed to a schedule backs it, so none of it is surplus
+        let mut outstanding = 0;
+        for beneficiary in indexed.iter() {
+            let vesting: VestingData = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Vesting(beneficiary))
+                .ok_or(VestingError::VestingNotFound)?;
+            outstanding += vesting.total_amount - vesting.claimed_amount;
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalOutstanding, &outstanding);
+
+        if !env.storage().instance().has(&DataKey::TokenDecimals) {
+            let token: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::Token)
+                .ok_or(VestingError::NotInitialized)?;
+            let token_decimals = decimals(env, &token).ok_or(VestingError::InvalidToken)?;
+            env.storage()
+                .instance()
+                .set(&DataKey::TokenDecimals, &token_decimals);
+        }
+        Ok(migrated)
+    }
+
+    /// Get the storage layout version (0 for storage that predates versioning)
+    pub fn get_version(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Version).unwrap_or(0)
+    }
+
     /// Transfer the admin role to `new_admin`.
     ///
-    /// Requires authorization from the current admin. Emits [`AdminChangedEvent`].
+    /// Requires authorization from the current admin. Rejects a no-op transfer
+    /// to the current admin. Emits [`AdminChangedEvent`].
     pub fn set_admin(
         env: Env,
         current_admin: Address,
@@ -311,8 +2050,12 @@ impl VestingWalletContract {
         if current_admin != stored_admin {
             return Err(VestingError::Unauthorized);
         }
+        if new_admin == current_admin {
+            return Err(VestingError::SameAdmin);
+        }
         current_admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &new_admin);
+        events::admin_action(&env, "set_admin", &current_admin);
         AdminChangedEvent {
             old_admin: current_admin,
             new_admin,
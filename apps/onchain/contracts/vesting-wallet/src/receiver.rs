@@ -0,0 +1,7 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// Callback a contract recipient implements to be notified of a `claim_to` payout
+#[contractclient(name = "TokenReceiverClient")]
+pub trait TokenReceiver {
+    fn on_tokens_received(env: Env, token: Address, amount: i128);
+}
@@ -0,0 +1,7 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// The part of the contributor registry interface the vesting wallet relies on
+#[contractclient(name = "ContributorRegistryClient")]
+pub trait ContributorRegistry {
+    fn award_reputation(env: Env, source: Address, contributor: Address, amount: u64);
+}
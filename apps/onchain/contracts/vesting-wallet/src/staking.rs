@@ -0,0 +1,8 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// The part of a staking contract's interface used to pull accrued rewards
+#[contractclient(name = "StakingRewardsClient")]
+pub trait StakingRewards {
+    /// Transfer the rewards accrued to `recipient` and return the amount paid
+    fn claim_rewards(env: Env, recipient: Address) -> i128;
+}
@@ -1,11 +1,29 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, String, Vec};
 
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
-    Admin,            // -> Address
-    Token,            // -> Address
-    Vesting(Address), // beneficiary -> VestingData
+    Admin,                       // -> Address
+    Token,                       // -> Address
+    Vesting(Address),            // beneficiary -> VestingData
+    TotalClaimed,                // -> i128
+    TotalOutstanding,            // -> i128 (allocated to schedules but not yet claimed)
+    RecipientAllowlist(Address), // beneficiary -> Vec<Address>
+    ClaimFeeBps,                 // -> u32 (claim fee in basis points)
+    FeeRecipient,                // -> Address
+    ClaimSplit(Address),         // beneficiary -> Vec<(Address, u32)>
+    Beneficiaries,               // -> Vec<Address>
+    Registry,                    // -> Address (contributor registry)
+    CompletionBonus,             // -> u64 (reputation awarded on completion)
+    Version,                     // -> u32 (storage layout version)
+    TokenDecimals,               // -> u32 (cached from the token at initialize)
+    MinDuration,                 // -> u64 (0 = no minimum)
+    LifetimeClaimed(Address),    // beneficiary -> i128 (kept after schedules are deleted)
+    Hold(Address),               // beneficiary -> String (reason claims are on legal hold)
+    ClaimCheckpointCount,        // -> u32 (checkpoints ever written)
+    ClaimCheckpoint(u32), // ring slot -> (u64, i128) (claim timestamp, total claimed before it)
+    StakingContract,      // -> Address (pays staking rewards on the held tokens)
+    MigrationRemaining,   // -> u32 (legacy schedules not yet migrated)
 }
 
 #[contracttype]
@@ -16,4 +34,63 @@ pub struct VestingData {
     pub start_time: u64,
     pub duration: u64,
     pub claimed_amount: i128,
+    pub frozen: bool,
+    /// `(offset_from_start, cumulative_unlocked)` points of a custom curve;
+    /// empty for a linear schedule
+    pub unlock_points: Vec<(u64, i128)>,
+    /// Seconds after full vesting before the admin may reclaim the unclaimed
+    /// remainder; 0 means the schedule never expires
+    pub claim_expiry: u64,
+    /// Claims are blocked until the beneficiary accepts the grant
+    pub pending_acceptance: bool,
+    /// Vesting starts at the acceptance timestamp; `start_time` is 0 until then
+    pub start_on_acceptance: bool,
+    /// Human-readable description of the grant
+    pub label: String,
+    /// Claims are floored to a multiple of this unit until the final claim (0 = off)
+    pub round_to: i128,
+    /// Shape of the schedule when no `unlock_points` are set
+    pub curve: VestingCurve,
+}
+
+/// `VestingData` as stored before storage version 2, migrated by `migrate`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingDataV1 {
+    pub beneficiary: Address,
+    pub total_amount: i128,
+    pub start_time: u64,
+    pub duration: u64,
+    pub claimed_amount: i128,
+}
+
+/// Number of fields in a stored `VestingDataV1`
+pub const VESTING_V1_FIELDS: u32 = 5;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VestingCurve {
+    Linear,
+    /// Unlocks `(elapsed / duration)^(1 / exponent)` of the total, so most
+    /// tokens vest early and the rate tapers off
+    FrontLoaded(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingState {
+    pub admin: Address,
+    pub token: Address,
+    pub total_claimed: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingConfig {
+    pub admin: Address,
+    pub token: Address,
+    pub token_decimals: u32,
+    pub claim_fee_bps: u32,
+    pub fee_recipient: Option<Address>,
+    pub min_duration: u64,
 }
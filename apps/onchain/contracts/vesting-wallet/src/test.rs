@@ -1,11 +1,77 @@
 use crate::errors::VestingError;
 use crate::{VestingWalletContract, VestingWalletContractClient};
+use contributor_registry::{ContributorRegistryContract, ContributorRegistryContractClient};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
+    testutils::{Address as _, Events, Ledger},
     token::{StellarAssetClient, TokenClient},
-    Address, Env,
+    vec, Address, Env, Event, String,
 };
 
+mod mock_receiver {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    /// Recipient contract that records the last `on_tokens_received` callback
+    #[contract]
+    pub struct MockReceiver;
+
+    #[contractimpl]
+    impl MockReceiver {
+        pub fn on_tokens_received(env: Env, token: Address, amount: i128) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("received"), &(token, amount));
+        }
+
+        pub fn received(env: Env) -> Option<(Address, i128)> {
+            env.storage().instance().get(&symbol_short!("received"))
+        }
+    }
+}
+
+mod mock_staking {
+    use soroban_sdk::{contract, contractimpl, symbol_short, token::TokenClient, Address, Env};
+
+    /// Staking contract that pays a fixed reward, recording the recipient's
+    /// balance at the time it is called
+    #[contract]
+    pub struct MockStaking;
+
+    #[contractimpl]
+    impl MockStaking {
+        pub fn __constructor(env: Env, token: Address, reward: i128) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("token"), &token);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("reward"), &reward);
+        }
+
+        pub fn claim_rewards(env: Env, recipient: Address) -> i128 {
+            let token: Address = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("token"))
+                .unwrap();
+            let reward: i128 = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("reward"))
+                .unwrap();
+            let token = TokenClient::new(&env, &token);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("seen"), &token.balance(&recipient));
+            token.transfer(&env.current_contract_address(), &recipient, &reward);
+            reward
+        }
+
+        pub fn seen_balance(env: Env) -> Option<i128> {
+            env.storage().instance().get(&symbol_short!("seen"))
+        }
+    }
+}
+
 fn create_token_contract<'a>(
     env: &Env,
     admin: &Address,
@@ -72,6 +138,24 @@ fn test_double_initialization_fails() {
     assert_eq!(result, Err(Ok(VestingError::AlreadyInitialized)));
 }
 
+#[test]
+fn test_initialize_rejects_non_token_address() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, _, _) = setup_test(&env);
+
+    // A plain account address is not a token contract
+    let result = client.try_initialize(&admin, &beneficiary);
+    assert_eq!(result, Err(Ok(VestingError::InvalidToken)));
+
+    // Contract stays uninitialized
+    assert_eq!(
+        client.try_get_admin(),
+        Err(Ok(VestingError::NotInitialized))
+    );
+}
+
 #[test]
 fn test_create_vesting() {
     let env = Env::default();
@@ -573,55 +657,1805 @@ fn test_get_claimable_consistency_with_claim() {
     assert_eq!(claimable_after, 0);
 }
 
-// ---------------------------------------------------------------------------
-// Upgradeability tests
-// ---------------------------------------------------------------------------
+#[test]
+fn test_get_claimable_at_projects_future_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+
+    // Initialize contract
+    client.initialize(&admin, &token_client.address);
+
+    let current_time = env.ledger().timestamp();
+    let start_time = current_time + 100;
+    let duration = 10_000;
+    let amount: i128 = 1_000_000;
+
+    client.create_vesting(&admin, &beneficiary, &amount, &start_time, &duration);
+
+    // Before the start the projection is 0
+    assert_eq!(client.get_claimable_at(&beneficiary, &current_time), 0);
+
+    // Project the amount at 40% and after the end
+    let future = start_time + (duration * 4 / 10);
+    let projected = client.get_claimable_at(&beneficiary, &future);
+    assert_eq!(projected, (amount * 4) / 10);
+    assert_eq!(
+        client.get_claimable_at(&beneficiary, &(start_time + duration + 1)),
+        amount
+    );
+
+    // Projection does not modify state
+    assert_eq!(client.get_vesting(&beneficiary).claimed_amount, 0);
+
+    // Moving the ledger there yields exactly the projected claim
+    env.ledger().set_timestamp(future);
+    assert_eq!(client.claim(&beneficiary), projected);
+}
 
 #[test]
-fn test_set_admin_transfers_role() {
+fn test_get_claimable_at_not_found() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, admin, _, token_client, _) = setup_test(&env);
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
     client.initialize(&admin, &token_client.address);
 
-    let new_admin = Address::generate(&env);
-    client.set_admin(&admin, &new_admin);
+    let result = client.try_get_claimable_at(&beneficiary, &1_000);
+    assert_eq!(result, Err(Ok(VestingError::VestingNotFound)));
+}
+
+#[test]
+fn test_time_to_full_vest() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+
+    // Initialize contract
+    client.initialize(&admin, &token_client.address);
+
+    let current_time = env.ledger().timestamp();
+    let start_time = current_time + 100;
+    let duration = 10_000;
+
+    client.create_vesting(&admin, &beneficiary, &1_000_000, &start_time, &duration);
+
+    // Mid-vesting returns the remaining seconds
+    env.ledger().set_timestamp(start_time + duration / 4);
+    assert_eq!(client.time_to_full_vest(&beneficiary), duration * 3 / 4);
+
+    // After the end it returns 0
+    env.ledger().set_timestamp(start_time + duration + 500);
+    assert_eq!(client.time_to_full_vest(&beneficiary), 0);
+
+    // Missing schedules are reported
+    let unknown = Address::generate(&env);
+    let result = client.try_time_to_full_vest(&unknown);
+    assert_eq!(result, Err(Ok(VestingError::VestingNotFound)));
+}
+
+#[test]
+fn test_claim_and_relock() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, contract_id) = setup_test(&env);
+
+    // Initialize contract
+    client.initialize(&admin, &token_client.address);
+
+    let current_time = env.ledger().timestamp();
+    let start_time = current_time + 100;
+    let duration = 10_000;
+    let amount: i128 = 1_000_000;
+
+    client.create_vesting(&admin, &beneficiary, &amount, &start_time, &duration);
+
+    // Claim part of the schedule normally
+    env.ledger().set_timestamp(start_time + duration / 4);
+    client.claim(&beneficiary);
+
+    // The relock duration honours the configured minimum
+    client.set_min_duration(&admin, &1_000);
+    let result = client.try_claim_and_relock(&beneficiary, &999);
+    assert_eq!(result, Err(Ok(VestingError::DurationTooShort)));
+
+    // Once fully vested, the remainder is relocked instead of transferred
+    let relock_time = start_time + duration + 10;
+    env.ledger().set_timestamp(relock_time);
+    let relocked = client.claim_and_relock(&beneficiary, &1_000);
+    assert_eq!(relocked, amount * 3 / 4);
+
+    // Tokens stay in the contract
+    assert_eq!(token_client.balance(&beneficiary), amount / 4);
+    assert_eq!(token_client.balance(&contract_id), amount * 3 / 4);
+
+    // A fresh schedule exists for the relocked amount
+    let vesting = client.get_vesting(&beneficiary);
+    assert_eq!(vesting.total_amount, relocked);
+    assert_eq!(vesting.claimed_amount, 0);
+    assert_eq!(vesting.start_time, relock_time);
+    assert_eq!(vesting.duration, 1_000);
 
+    // And it vests over the relock duration
+    env.ledger().set_timestamp(relock_time + 500);
+    assert_eq!(client.claim(&beneficiary), relocked / 2);
+}
+
+#[test]
+fn test_claim_and_relock_mid_vesting() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, contract_id) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp();
+    let duration = 10_000;
+    let amount: i128 = 1_000_000;
+    client.create_vesting(&admin, &beneficiary, &amount, &start_time, &duration);
+
+    // A quarter is claimable; it is relocked together with the unvested rest
+    let relock_time = start_time + duration / 4;
+    env.ledger().set_timestamp(relock_time);
+    assert_eq!(client.claim_and_relock(&beneficiary, &1_000), amount / 4);
+    assert_eq!(token_client.balance(&contract_id), amount);
+    assert_eq!(client.total_outstanding(), amount);
+
+    // The merged schedule runs as long as the original had left
+    let vesting = client.get_vesting(&beneficiary);
+    assert_eq!(vesting.total_amount, amount);
+    assert_eq!(vesting.start_time, relock_time);
+    assert_eq!(vesting.duration, duration * 3 / 4);
+    assert_eq!(client.get_claimable(&beneficiary), 0);
+
+    env.ledger().set_timestamp(start_time + duration);
+    assert_eq!(client.claim(&beneficiary), amount);
+
+    // Custom unlock points cannot be flattened before they finish
+    let other = Address::generate(&env);
+    let points = vec![&env, (100u64, 0i128), (1_000u64, 1_000i128)];
+    client.create_custom_vesting(&admin, &other, &1_000, &(start_time + duration), &points);
+    env.ledger().set_timestamp(start_time + duration + 500);
+    let result = client.try_claim_and_relock(&other, &1_000);
+    assert_eq!(result, Err(Ok(VestingError::ScheduleStillVesting)));
+}
+
+#[test]
+fn test_migrate_token_then_claim_in_new_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, contract_id) = setup_test(&env);
+    let (new_token_client, new_token_admin) = create_token_contract(&env, &admin);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp();
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+
+    env.ledger().set_timestamp(start_time + 250);
+    client.claim(&beneficiary);
+    assert_eq!(client.total_outstanding(), 7_500);
+
+    // The new token must already back everything outstanding
+    new_token_admin.mint(&contract_id, &7_499);
+    let result = client.try_migrate_token(&admin, &new_token_client.address);
+    assert_eq!(result, Err(Ok(VestingError::InsufficientBalance)));
+
+    // Migrating to the current token is rejected
+    let result = client.try_migrate_token(&admin, &token_client.address);
+    assert_eq!(result, Err(Ok(VestingError::InvalidToken)));
+
+    // The whole old-token balance, surplus included, goes back to the admin
+    StellarAssetClient::new(&env, &token_client.address).mint(&contract_id, &1_000);
+    new_token_admin.mint(&contract_id, &1);
+    let admin_before = token_client.balance(&admin);
     assert_eq!(
-        client.get_admin(),
-        new_admin,
-        "admin must be updated after set_admin"
+        client.migrate_token(&admin, &new_token_client.address),
+        8_500
     );
+    assert_eq!(client.get_token(), new_token_client.address);
+    assert_eq!(token_client.balance(&contract_id), 0);
+    assert_eq!(token_client.balance(&admin), admin_before + 8_500);
+
+    env.ledger().set_timestamp(start_time + 1000);
+    assert_eq!(client.claim(&beneficiary), 7_500);
+    assert_eq!(new_token_client.balance(&beneficiary), 7_500);
+    assert_eq!(token_client.balance(&beneficiary), 2_500);
 }
 
 #[test]
-fn test_only_admin_can_upgrade() {
+fn test_recover_surplus() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, admin, _, token_client, _) = setup_test(&env);
+    let (client, admin, beneficiary, token_client, contract_id) = setup_test(&env);
+    let treasury = Address::generate(&env);
+
+    // Initialize contract
     client.initialize(&admin, &token_client.address);
 
+    let current_time = env.ledger().timestamp();
+    let start_time = current_time + 100;
+    let duration = 10_000;
+    let amount: i128 = 1_000_000;
+
+    client.create_vesting(&admin, &beneficiary, &amount, &start_time, &duration);
+
+    // No surplus while the balance exactly backs the schedule
+    let result = client.try_recover_surplus(&admin, &treasury);
+    assert_eq!(result, Err(Ok(VestingError::NoSurplus)));
+
+    // Partial claim reduces both balance and outstanding
+    env.ledger().set_timestamp(start_time + duration / 4);
+    client.claim(&beneficiary);
+    assert_eq!(client.total_outstanding(), amount * 3 / 4);
+
+    // Someone sends tokens directly to the contract
+    token_client.transfer(&admin, &contract_id, &50_000);
+
+    // Only the non-admin is rejected
     let non_admin = Address::generate(&env);
-    let dummy = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
+    let result = client.try_recover_surplus(&non_admin, &treasury);
+    assert_eq!(result, Err(Ok(VestingError::Unauthorized)));
 
-    let result = client.try_upgrade(&non_admin, &dummy);
-    assert_eq!(result, Err(Ok(crate::errors::VestingError::Unauthorized)));
+    // Exactly the surplus is recovered
+    assert_eq!(client.recover_surplus(&admin, &treasury), 50_000);
+    assert_eq!(token_client.balance(&treasury), 50_000);
+    assert_eq!(token_client.balance(&contract_id), amount * 3 / 4);
+
+    // Schedule backing stays intact
+    env.ledger().set_timestamp(start_time + duration);
+    assert_eq!(client.claim(&beneficiary), amount * 3 / 4);
+    assert_eq!(token_client.balance(&contract_id), 0);
 }
 
 #[test]
-fn test_old_admin_cannot_upgrade_after_rotation() {
+fn test_claim_to_respects_recipient_allowlist() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, admin, _, token_client, _) = setup_test(&env);
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    let allowed = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    // Initialize contract
     client.initialize(&admin, &token_client.address);
 
-    let new_admin = Address::generate(&env);
-    client.set_admin(&admin, &new_admin);
+    let current_time = env.ledger().timestamp();
+    let start_time = current_time + 100;
+    let duration = 10_000;
+    let amount: i128 = 1_000_000;
 
-    let dummy = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
-    let result = client.try_upgrade(&admin, &dummy);
-    assert_eq!(result, Err(Ok(crate::errors::VestingError::Unauthorized)));
+    client.create_vesting(&admin, &beneficiary, &amount, &start_time, &duration);
+
+    // Without an allowlist any recipient works
+    env.ledger().set_timestamp(start_time + duration / 4);
+    assert_eq!(client.claim_to(&beneficiary, &other, &false), amount / 4);
+    assert_eq!(token_client.balance(&other), amount / 4);
+
+    client.set_recipient_allowlist(
+        &admin,
+        &beneficiary,
+        &soroban_sdk::vec![&env, allowed.clone()],
+    );
+
+    // Non-listed recipient is rejected
+    env.ledger().set_timestamp(start_time + duration / 2);
+    let result = client.try_claim_to(&beneficiary, &other, &false);
+    assert_eq!(result, Err(Ok(VestingError::RecipientNotAllowed)));
+
+    // Listed recipient receives the claim
+    assert_eq!(client.claim_to(&beneficiary, &allowed, &false), amount / 4);
+    assert_eq!(token_client.balance(&allowed), amount / 4);
+    assert_eq!(token_client.balance(&beneficiary), 0);
+}
+
+#[test]
+fn test_claim_with_rewards_pulls_rewards_first() {
+    use mock_staking::{MockStaking, MockStakingClient};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, contract_id) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let duration = 10_000;
+    let amount: i128 = 1_000_000;
+    client.create_vesting(&admin, &beneficiary, &amount, &start_time, &duration);
+    env.ledger().set_timestamp(start_time + duration / 2);
+
+    let result = client.try_claim_with_rewards(&beneficiary);
+    assert_eq!(result, Err(Ok(VestingError::StakingNotConfigured)));
+
+    let reward: i128 = 5_000;
+    let staking = MockStakingClient::new(
+        &env,
+        &env.register(MockStaking, (token_client.address.clone(), reward)),
+    );
+    token_client.transfer(&admin, &staking.address, &reward);
+    client.set_staking_contract(&admin, &Some(staking.address.clone()));
+    assert_eq!(client.get_staking_contract(), Some(staking.address.clone()));
+
+    assert_eq!(client.claim_with_rewards(&beneficiary), amount / 2 + reward);
+
+    // Rewards arrived while the contract still held the full grant and were
+    // passed on to the beneficiary with the vested tokens
+    assert_eq!(staking.seen_balance(), Some(amount));
+    assert_eq!(token_client.balance(&beneficiary), amount / 2 + reward);
+    assert_eq!(token_client.balance(&contract_id), amount / 2);
+
+    // Nothing is left behind as surplus
+    let treasury = Address::generate(&env);
+    assert_eq!(
+        client.try_recover_surplus(&admin, &treasury),
+        Err(Ok(VestingError::NoSurplus))
+    );
+}
+
+#[test]
+fn test_claim_to_notifies_contract_recipient() {
+    use mock_receiver::{MockReceiver, MockReceiverClient};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+    let receiver = MockReceiverClient::new(&env, &env.register(MockReceiver, ()));
+    let account = Address::generate(&env);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let duration = 10_000;
+    let amount: i128 = 1_000_000;
+    client.create_vesting(&admin, &beneficiary, &amount, &start_time, &duration);
+
+    // Without `notify` the contract only receives a plain transfer
+    env.ledger().set_timestamp(start_time + duration / 4);
+    client.claim_to(&beneficiary, &receiver.address, &false);
+    assert_eq!(token_client.balance(&receiver.address), amount / 4);
+    assert_eq!(receiver.received(), None);
+
+    // With `notify` the contract is called back with the amount delivered
+    env.ledger().set_timestamp(start_time + duration / 2);
+    client.claim_to(&beneficiary, &receiver.address, &true);
+    assert_eq!(token_client.balance(&receiver.address), amount / 2);
+    assert_eq!(
+        receiver.received(),
+        Some((token_client.address.clone(), amount / 4))
+    );
+
+    // Accounts fall back to a plain transfer even with `notify`
+    env.ledger().set_timestamp(start_time + duration * 3 / 4);
+    client.claim_to(&beneficiary, &account, &true);
+    assert_eq!(token_client.balance(&account), amount / 4);
+}
+
+#[test]
+fn test_total_claimed_across_beneficiaries() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary1, token_client, _) = setup_test(&env);
+    let beneficiary2 = Address::generate(&env);
+
+    // Initialize contract
+    client.initialize(&admin, &token_client.address);
+
+    let current_time = env.ledger().timestamp();
+    let start_time = current_time + 100;
+    let duration = 10_000;
+
+    client.create_vesting(&admin, &beneficiary1, &1_000_000, &start_time, &duration);
+    client.create_vesting(&admin, &beneficiary2, &2_000_000, &start_time, &duration);
+
+    // Nothing claimed yet
+    assert_eq!(client.total_claimed(), 0);
+
+    // Partial claims at 25%
+    env.ledger().set_timestamp(start_time + duration / 4);
+    client.claim(&beneficiary1);
+    client.claim(&beneficiary2);
+
+    // Another partial claim at 60% for the first beneficiary only
+    env.ledger().set_timestamp(start_time + (duration * 6 / 10));
+    client.claim(&beneficiary1);
+
+    let claimed1 = client.get_vesting(&beneficiary1).claimed_amount;
+    let claimed2 = client.get_vesting(&beneficiary2).claimed_amount;
+    assert_eq!(claimed1, 600_000);
+    assert_eq!(claimed2, 500_000);
+
+    // Running total equals the sum of individual claimed amounts
+    assert_eq!(client.total_claimed(), claimed1 + claimed2);
+}
+
+#[test]
+fn test_claims_since_respects_boundary() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary1, token_client, _) = setup_test(&env);
+    let beneficiary2 = Address::generate(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let duration = 10_000;
+    let amount: i128 = 1_000_000;
+    client.create_vesting(&admin, &beneficiary1, &amount, &start_time, &duration);
+    client.create_vesting(&admin, &beneficiary2, &amount, &start_time, &duration);
+
+    let first = start_time + duration / 4;
+    env.ledger().set_timestamp(first);
+    client.claim(&beneficiary1);
+
+    let second = start_time + duration / 2;
+    env.ledger().set_timestamp(second);
+    let claimed1 = client.claim(&beneficiary1);
+    let claimed2 = client.claim(&beneficiary2);
+
+    assert_eq!(client.claims_since(&0), client.total_claimed());
+    assert_eq!(client.claims_since(&first), client.total_claimed());
+    assert_eq!(client.claims_since(&(first + 1)), claimed1 + claimed2);
+    assert_eq!(client.claims_since(&second), claimed1 + claimed2);
+    assert_eq!(client.claims_since(&(second + 1)), 0);
+}
+
+#[test]
+fn test_claims_since_covers_long_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let duration = 10_000;
+    client.create_vesting(&admin, &beneficiary, &1_000_000, &start_time, &duration);
+
+    // More distinct claim timestamps than a bounded log would keep
+    for step in 1..=250 {
+        env.ledger().set_timestamp(start_time + step * 10);
+        client.claim(&beneficiary);
+    }
+
+    assert_eq!(
+        client.claims_since(&(start_time + 10)),
+        client.total_claimed()
+    );
+    assert_eq!(client.claims_since(&(start_time + 2_500)), 1_000);
+    assert_eq!(client.claims_since(&(start_time + 2_501)), 0);
+}
+
+#[test]
+fn test_claims_since_keeps_bounded_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp();
+    let steps: u64 = 1_100;
+    client.create_vesting(&admin, &beneficiary, &(steps as i128), &start_time, &steps);
+
+    // One unit vests and is claimed at each of more timestamps than are kept
+    for step in 1..=steps {
+        env.ledger().set_timestamp(start_time + step);
+        client.claim(&beneficiary);
+    }
+
+    // Windows within the retained checkpoints stay exact
+    let oldest_kept = steps - 1_024 + 1;
+    assert_eq!(client.claims_since(&(start_time + oldest_kept)), 1_024);
+    assert_eq!(client.claims_since(&(start_time + 1_000)), 101);
+
+    // Older windows are counted from the oldest checkpoint kept
+    assert_eq!(client.claims_since(&start_time), 1_024);
+    assert_eq!(client.total_claimed(), steps as i128);
+}
+
+#[test]
+fn test_lifetime_claimed_survives_schedule_deletion() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp();
+    client.create_vesting(&admin, &beneficiary, &1_000, &start_time, &1_000);
+
+    env.ledger().set_timestamp(start_time + 400);
+    client.claim(&beneficiary);
+
+    // Canceling pays out the vested remainder and deletes the schedule
+    env.ledger().set_timestamp(start_time + 750);
+    client.cancel_vesting(&admin, &beneficiary, &false);
+    assert_eq!(client.lifetime_claimed(&beneficiary), 750);
+
+    // A second schedule keeps accumulating on top
+    let second_start = env.ledger().timestamp();
+    client.create_vesting(&admin, &beneficiary, &2_000, &second_start, &1_000);
+    env.ledger().set_timestamp(second_start + 1_000);
+    client.claim(&beneficiary);
+
+    assert_eq!(client.lifetime_claimed(&beneficiary), 2_750);
+    assert_eq!(token_client.balance(&beneficiary), 2_750);
+}
+
+#[test]
+fn test_freeze_schedule_blocks_only_that_beneficiary() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary1, token_client, _) = setup_test(&env);
+    let beneficiary2 = Address::generate(&env);
+
+    // Initialize contract
+    client.initialize(&admin, &token_client.address);
+
+    let current_time = env.ledger().timestamp();
+    let start_time = current_time + 100;
+    let duration = 10_000;
+    let amount: i128 = 1_000_000;
+
+    client.create_vesting(&admin, &beneficiary1, &amount, &start_time, &duration);
+    client.create_vesting(&admin, &beneficiary2, &amount, &start_time, &duration);
+
+    // Freeze the first beneficiary's schedule
+    client.freeze_schedule(&admin, &beneficiary1);
+    assert!(client.get_vesting(&beneficiary1).frozen);
+
+    env.ledger().set_timestamp(start_time + duration / 4);
+
+    // Frozen beneficiary cannot claim
+    let result = client.try_claim(&beneficiary1);
+    assert_eq!(result, Err(Ok(VestingError::ScheduleFrozen)));
+
+    // The other beneficiary claims normally
+    assert_eq!(client.claim(&beneficiary2), amount / 4);
+
+    // Vesting keeps accruing during the freeze
+    env.ledger().set_timestamp(start_time + duration / 2);
+    assert_eq!(client.get_claimable(&beneficiary1), amount / 2);
+
+    // Unfreeze and claim everything accrued so far
+    client.unfreeze_schedule(&admin, &beneficiary1);
+    assert!(!client.get_vesting(&beneficiary1).frozen);
+    assert_eq!(client.claim(&beneficiary1), amount / 2);
+}
+
+#[test]
+fn test_cancel_keeps_held_vested_funds_in_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, contract_id) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let amount: i128 = 10_000;
+    client.create_vesting(&admin, &beneficiary, &amount, &start_time, &1000);
+    client.hold_schedule(&admin, &beneficiary, &String::from_str(&env, "dispute"));
+
+    // Half has vested: it cannot be replaced or paid out while held
+    env.ledger().set_timestamp(start_time + 500);
+    let result =
+        client.try_create_vesting(&admin, &beneficiary, &amount, &(start_time + 500), &1000);
+    assert_eq!(result, Err(Ok(VestingError::ScheduleHeld)));
+
+    let admin_before = token_client.balance(&admin);
+    assert_eq!(
+        client.cancel_vesting(&admin, &beneficiary, &false),
+        (0, 5_000)
+    );
+
+    // Without `mutual` the beneficiary is not asked to authorize
+    assert!(!env.auths().iter().any(|(signer, _)| *signer == beneficiary));
+    assert_eq!(token_client.balance(&admin), admin_before + 5_000);
+    assert_eq!(token_client.balance(&beneficiary), 0);
+    assert_eq!(token_client.balance(&contract_id), 5_000);
+    assert_eq!(client.total_outstanding(), 5_000);
+
+    // The escrowed remainder is claimable in full once the hold is released
+    let result = client.try_claim(&beneficiary);
+    assert_eq!(result, Err(Ok(VestingError::ScheduleHeld)));
+    client.release_hold(&admin, &beneficiary);
+    env.ledger().set_timestamp(start_time + 600);
+    assert_eq!(client.claim(&beneficiary), 5_000);
+    assert_eq!(client.total_outstanding(), 0);
+}
+
+#[test]
+fn test_cancel_clears_hold_and_allowlist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    let allowed = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+    client.set_recipient_allowlist(&admin, &beneficiary, &vec![&env, allowed]);
+    client.hold_schedule(&admin, &beneficiary, &String::from_str(&env, "dispute"));
+
+    // Nothing vested yet, so the schedule is deleted outright
+    client.cancel_vesting(&admin, &beneficiary, &false);
+    assert_eq!(client.get_hold(&beneficiary), None);
+
+    // A new schedule starts without the old restrictions
+    let now = env.ledger().timestamp();
+    client.create_vesting(&admin, &beneficiary, &10_000, &now, &1000);
+    env.ledger().set_timestamp(now + 1000);
+    assert_eq!(client.claim_to(&beneficiary, &other, &false), 10_000);
+}
+
+#[test]
+fn test_hold_schedule_blocks_claims_with_reason() {
+    use crate::events::ScheduleHeldEvent;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let duration = 10_000;
+    let amount: i128 = 1_000_000;
+    client.create_vesting(&admin, &beneficiary, &amount, &start_time, &duration);
+
+    let reason = String::from_str(&env, "court order 2026-117");
+    client.hold_schedule(&admin, &beneficiary, &reason);
+    let expected = ScheduleHeldEvent {
+        beneficiary: beneficiary.clone(),
+        held: true,
+        reason: reason.clone(),
+    };
+    let events = env.events().all();
+    let emitted = events
+        .iter()
+        .find(|(_, topics, _)| *topics == expected.topics(&env))
+        .unwrap();
+    assert_eq!(vec![&env, emitted.2], vec![&env, expected.data(&env)]);
+    assert_eq!(client.get_hold(&beneficiary), Some(reason));
+
+    env.ledger().set_timestamp(start_time + duration / 2);
+    assert!(!client.can_claim(&beneficiary));
+    let result = client.try_claim(&beneficiary);
+    assert_eq!(result, Err(Ok(VestingError::ScheduleHeld)));
+
+    // Vesting kept accruing during the hold
+    client.release_hold(&admin, &beneficiary);
+    assert_eq!(client.get_hold(&beneficiary), None);
+    assert_eq!(client.claim(&beneficiary), amount / 2);
+
+    let result = client.try_release_hold(&admin, &beneficiary);
+    assert_eq!(result, Err(Ok(VestingError::NotHeld)));
+}
+
+#[test]
+fn test_freeze_schedule_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+
+    // Initialize contract
+    client.initialize(&admin, &token_client.address);
+
+    let current_time = env.ledger().timestamp();
+    client.create_vesting(
+        &admin,
+        &beneficiary,
+        &1_000_000,
+        &(current_time + 100),
+        &10_000,
+    );
+
+    let non_admin = Address::generate(&env);
+    let result = client.try_freeze_schedule(&non_admin, &beneficiary);
+    assert_eq!(result, Err(Ok(VestingError::Unauthorized)));
+
+    // Freezing a missing schedule fails
+    let unknown = Address::generate(&env);
+    let result = client.try_freeze_schedule(&admin, &unknown);
+    assert_eq!(result, Err(Ok(VestingError::VestingNotFound)));
+}
+
+#[test]
+fn test_get_state_snapshot() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+
+    // Uninitialized contract has no state
+    assert_eq!(
+        client.try_get_state(),
+        Err(Ok(VestingError::NotInitialized))
+    );
+
+    client.initialize(&admin, &token_client.address);
+
+    let state = client.get_state();
+    assert_eq!(state.admin, admin);
+    assert_eq!(state.token, token_client.address);
+    assert_eq!(state.total_claimed, 0);
+
+    // Mutate: create, claim and rotate admin
+    let current_time = env.ledger().timestamp();
+    let start_time = current_time + 100;
+    let duration = 10_000;
+    client.create_vesting(&admin, &beneficiary, &1_000_000, &start_time, &duration);
+    env.ledger().set_timestamp(start_time + duration / 2);
+    let claimed = client.claim(&beneficiary);
+    let new_admin = Address::generate(&env);
+    client.set_admin(&admin, &new_admin);
+
+    let state = client.get_state();
+    assert_eq!(state.admin, new_admin);
+    assert_eq!(state.total_claimed, claimed);
+}
+
+// ---------------------------------------------------------------------------
+// Upgradeability tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_set_admin_transfers_role() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let new_admin = Address::generate(&env);
+    client.set_admin(&admin, &new_admin);
+
+    assert_eq!(
+        client.get_admin(),
+        new_admin,
+        "admin must be updated after set_admin"
+    );
+}
+
+#[test]
+fn test_set_admin_to_self_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let result = client.try_set_admin(&admin, &admin);
+    assert_eq!(result, Err(Ok(crate::errors::VestingError::SameAdmin)));
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_only_admin_can_upgrade() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let non_admin = Address::generate(&env);
+    let dummy = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
+
+    let result = client.try_upgrade(&non_admin, &dummy);
+    assert_eq!(result, Err(Ok(crate::errors::VestingError::Unauthorized)));
+}
+
+#[test]
+fn test_old_admin_cannot_upgrade_after_rotation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let new_admin = Address::generate(&env);
+    client.set_admin(&admin, &new_admin);
+
+    let dummy = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
+    let result = client.try_upgrade(&admin, &dummy);
+    assert_eq!(result, Err(Ok(crate::errors::VestingError::Unauthorized)));
+}
+
+#[test]
+fn test_custom_vesting_curve() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    // 10% after 1000s, nothing more until 7000s, then the rest at 10000s
+    let points = vec![&env, (1000u64, 100i128), (7000, 100), (10000, 1000)];
+    client.create_custom_vesting(&admin, &beneficiary, &1000, &start_time, &points);
+
+    let vesting = client.get_vesting(&beneficiary);
+    assert_eq!(vesting.duration, 10000);
+    assert_eq!(vesting.unlock_points, points);
+
+    // Before the first point nothing is unlocked
+    assert_eq!(
+        client.get_claimable_at(&beneficiary, &(start_time + 999)),
+        0
+    );
+
+    // At the first point
+    env.ledger().with_mut(|li| li.timestamp = start_time + 1000);
+    assert_eq!(client.claim(&beneficiary), 100);
+
+    // Between points the amount holds
+    env.ledger().with_mut(|li| li.timestamp = start_time + 5000);
+    assert_eq!(client.get_claimable(&beneficiary), 0);
+    assert_eq!(
+        client.get_claimable_at(&beneficiary, &(start_time + 9999)),
+        0
+    );
+
+    // At the final point the rest unlocks
+    env.ledger()
+        .with_mut(|li| li.timestamp = start_time + 10000);
+    assert_eq!(client.claim(&beneficiary), 900);
+    assert_eq!(token_client.balance(&beneficiary), 1000);
+}
+
+#[test]
+fn test_custom_vesting_invalid_points() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+    let start_time = env.ledger().timestamp() + 100;
+
+    let invalid = [
+        // Empty
+        vec![&env],
+        // Unsorted offsets
+        vec![&env, (2000u64, 500i128), (1000, 1000)],
+        // Decreasing cumulative amount
+        vec![&env, (1000u64, 600i128), (2000, 500), (3000, 1000)],
+        // Final cumulative below total
+        vec![&env, (1000u64, 500i128), (2000, 900)],
+    ];
+    for points in invalid {
+        let result =
+            client.try_create_custom_vesting(&admin, &beneficiary, &1000, &start_time, &points);
+        assert_eq!(result, Err(Ok(VestingError::InvalidUnlockPoints)));
+    }
+}
+
+#[test]
+fn test_claim_fee_split() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    let fee_recipient = Address::generate(&env);
+    client.initialize(&admin, &token_client.address);
+
+    // 1.5% claim fee
+    client.set_claim_fee(&admin, &150, &fee_recipient);
+    assert_eq!(client.get_claim_fee_bps(), 150);
+    assert_eq!(client.get_fee_recipient(), Some(fee_recipient.clone()));
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = start_time + 500);
+    let claimable = client.get_claimable(&beneficiary);
+    assert_eq!(client.claim(&beneficiary), claimable);
+
+    let fee = token_client.balance(&fee_recipient);
+    assert_eq!(fee, 75);
+    assert_eq!(token_client.balance(&beneficiary) + fee, claimable);
+}
+
+#[test]
+fn test_claim_fee_over_cap_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let result = client.try_set_claim_fee(&admin, &1_001, &admin);
+    assert_eq!(result, Err(Ok(VestingError::InvalidFee)));
+}
+
+#[test]
+fn test_claim_split() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    let member_a = Address::generate(&env);
+    let member_b = Address::generate(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+
+    // Shares must sum to 100%
+    let result = client.try_set_claim_split(
+        &beneficiary,
+        &vec![&env, (member_a.clone(), 6000u32), (member_b.clone(), 3000)],
+    );
+    assert_eq!(result, Err(Ok(VestingError::InvalidSplit)));
+
+    // Duplicate and zero-share entries are rejected
+    let result = client.try_set_claim_split(
+        &beneficiary,
+        &vec![&env, (member_a.clone(), 5000u32), (member_a.clone(), 5000)],
+    );
+    assert_eq!(result, Err(Ok(VestingError::InvalidSplit)));
+    let result = client.try_set_claim_split(
+        &beneficiary,
+        &vec![&env, (member_a.clone(), 10_000u32), (member_b.clone(), 0)],
+    );
+    assert_eq!(result, Err(Ok(VestingError::InvalidSplit)));
+
+    // Recipients must be on the beneficiary's allowlist when one is set
+    client.set_recipient_allowlist(&admin, &beneficiary, &vec![&env, member_a.clone()]);
+    let result = client.try_set_claim_split(
+        &beneficiary,
+        &vec![&env, (member_a.clone(), 6000u32), (member_b.clone(), 4000)],
+    );
+    assert_eq!(result, Err(Ok(VestingError::RecipientNotAllowed)));
+    client.set_recipient_allowlist(
+        &admin,
+        &beneficiary,
+        &vec![&env, member_a.clone(), member_b.clone()],
+    );
+
+    client.set_claim_split(
+        &beneficiary,
+        &vec![&env, (member_a.clone(), 6000u32), (member_b.clone(), 4000)],
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = start_time + 500);
+    assert_eq!(client.claim(&beneficiary), 5_000);
+
+    assert_eq!(token_client.balance(&member_a), 3_000);
+    assert_eq!(token_client.balance(&member_b), 2_000);
+    assert_eq!(token_client.balance(&beneficiary), 0);
+
+    // Dropping a split recipient from the allowlist blocks later claims
+    client.set_recipient_allowlist(&admin, &beneficiary, &vec![&env, member_a.clone()]);
+    env.ledger().with_mut(|li| li.timestamp = start_time + 1000);
+    let result = client.try_claim(&beneficiary);
+    assert_eq!(result, Err(Ok(VestingError::RecipientNotAllowed)));
+    assert_eq!(token_client.balance(&member_a), 3_000);
+}
+
+#[test]
+fn test_cancel_vesting_splits_between_parties() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, contract_id) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+
+    // Claim a part, then cancel later on
+    env.ledger().with_mut(|li| li.timestamp = start_time + 200);
+    client.claim(&beneficiary);
+    env.ledger().with_mut(|li| li.timestamp = start_time + 600);
+
+    let admin_before = token_client.balance(&admin);
+    assert_eq!(
+        client.cancel_vesting(&admin, &beneficiary, &true),
+        (4_000, 4_000)
+    );
+
+    // A mutual cancellation is authorized by both parties
+    let signers = env.auths();
+    assert!(signers.iter().any(|(signer, _)| *signer == admin));
+    assert!(signers.iter().any(|(signer, _)| *signer == beneficiary));
+
+    assert_eq!(token_client.balance(&beneficiary), 6_000);
+    assert_eq!(token_client.balance(&admin), admin_before + 4_000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+    assert_eq!(client.total_outstanding(), 0);
+
+    let result = client.try_get_vesting(&beneficiary);
+    assert_eq!(result, Err(Ok(VestingError::VestingNotFound)));
+}
+
+#[test]
+fn test_can_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    // No schedule yet
+    assert!(!client.can_claim(&beneficiary));
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+
+    // Nothing vested before the start
+    assert!(!client.can_claim(&beneficiary));
+
+    env.ledger().with_mut(|li| li.timestamp = start_time + 100);
+    assert!(client.can_claim(&beneficiary));
+
+    // Frozen schedules cannot be claimed
+    client.freeze_schedule(&admin, &beneficiary);
+    assert!(!client.can_claim(&beneficiary));
+    client.unfreeze_schedule(&admin, &beneficiary);
+
+    // Nothing left right after claiming
+    client.claim(&beneficiary);
+    assert!(!client.can_claim(&beneficiary));
+}
+
+#[test]
+fn test_front_loaded_curve_unlocks_early() {
+    use crate::storage::VestingCurve;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let duration = 10_000;
+    let amount: i128 = 1_000_000;
+    client.create_vesting(&admin, &beneficiary, &amount, &start_time, &duration);
+
+    let result = client.try_set_vesting_curve(&admin, &beneficiary, &VestingCurve::FrontLoaded(0));
+    assert_eq!(result, Err(Ok(VestingError::InvalidCurve)));
+    let result = client.try_set_vesting_curve(&admin, &beneficiary, &VestingCurve::FrontLoaded(5));
+    assert_eq!(result, Err(Ok(VestingError::InvalidCurve)));
+
+    // A square-root curve unlocks sqrt(0.25) = 50% at a quarter of the way
+    client.set_vesting_curve(&admin, &beneficiary, &VestingCurve::FrontLoaded(2));
+    env.ledger().set_timestamp(start_time + duration / 4);
+    assert_eq!(client.get_claimable(&beneficiary), amount / 2);
+
+    // More than half is unlocked at the halfway point
+    env.ledger().set_timestamp(start_time + duration / 2);
+    let claimable = client.get_claimable(&beneficiary);
+    assert!(claimable > amount / 2);
+    assert_eq!(client.claim(&beneficiary), claimable);
+
+    env.ledger().set_timestamp(start_time + duration);
+    assert_eq!(client.claim(&beneficiary), amount - claimable);
+    assert_eq!(token_client.balance(&beneficiary), amount);
+}
+
+#[test]
+fn test_round_to_floors_claims_until_final() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp();
+    client.create_vesting(&admin, &beneficiary, &10_005, &start_time, &1000);
+    client.set_round_to(&admin, &beneficiary, &1_000);
+
+    // 2_501 vested, floored to 2_000
+    env.ledger().set_timestamp(start_time + 250);
+    assert_eq!(client.claim(&beneficiary), 2_000);
+
+    // 7_503 vested, 5_000 more claimable
+    env.ledger().set_timestamp(start_time + 750);
+    assert_eq!(client.get_claimable(&beneficiary), 5_000);
+    assert_eq!(client.claim(&beneficiary), 5_000);
+
+    // The final claim releases the exact remainder
+    env.ledger().set_timestamp(start_time + 1000);
+    assert_eq!(client.claim(&beneficiary), 3_005);
+    assert_eq!(token_client.balance(&beneficiary), 10_005);
+
+    let result = client.try_set_round_to(&admin, &beneficiary, &-1);
+    assert_eq!(result, Err(Ok(VestingError::InvalidAmount)));
+}
+
+#[test]
+fn test_schedule_count() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    assert_eq!(client.schedule_count(&beneficiary), 0);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &1_000, &start_time, &1000);
+    assert_eq!(client.schedule_count(&beneficiary), 1);
+
+    // A second grant replaces the first rather than adding another schedule
+    client.create_vesting(&admin, &beneficiary, &2_000, &start_time, &1000);
+    assert_eq!(client.schedule_count(&beneficiary), 1);
+
+    client.cancel_vesting(&admin, &beneficiary, &false);
+    assert_eq!(client.schedule_count(&beneficiary), 0);
+    assert_eq!(client.schedule_count(&Address::generate(&env)), 0);
+}
+
+#[test]
+fn test_schedules_with_dust() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, dusty, token_client, _) = setup_test(&env);
+    let unclaimed = Address::generate(&env);
+    let fresh = Address::generate(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let duration = 10_000;
+    let amount: i128 = 1_000_000;
+    client.create_vesting(&admin, &dusty, &amount, &start_time, &duration);
+    client.create_vesting(&admin, &unclaimed, &amount, &start_time, &duration);
+
+    // Claiming one second before the end leaves 100 behind
+    env.ledger().set_timestamp(start_time + duration - 1);
+    client.claim(&dusty);
+    assert_eq!(client.schedules_with_dust(&100).len(), 0);
+
+    env.ledger().set_timestamp(start_time + duration);
+    client.create_vesting(
+        &admin,
+        &fresh,
+        &amount,
+        &(start_time + duration + 100),
+        &duration,
+    );
+
+    assert_eq!(client.schedules_with_dust(&100), vec![&env, dusty.clone()]);
+    assert_eq!(client.schedules_with_dust(&99).len(), 0);
+}
+
+#[test]
+fn test_schedules_ending_before() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &1_000, &start_time, &1_000);
+    client.create_vesting(&admin, &second, &1_000, &start_time, &2_000);
+    client.create_vesting(&admin, &third, &1_000, &start_time, &5_000);
+
+    let ending = client.schedules_ending_before(&(start_time + 2_000));
+    assert_eq!(
+        ending,
+        vec![
+            &env,
+            (beneficiary.clone(), start_time + 1_000),
+            (second.clone(), start_time + 2_000)
+        ]
+    );
+
+    // Canceled schedules drop out
+    client.cancel_vesting(&admin, &beneficiary, &false);
+    assert_eq!(client.get_beneficiaries(), vec![&env, second, third]);
+}
+
+#[test]
+fn test_claim_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    let later = Address::generate(&env);
+    let finished = Address::generate(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &1_000, &start_time, &1_000);
+    client.create_vesting(&admin, &later, &1_000, &(start_time + 5_000), &1_000);
+    client.create_vesting(&admin, &finished, &1_000, &start_time, &100);
+
+    env.ledger().with_mut(|li| li.timestamp = start_time + 500);
+    client.claim(&finished);
+
+    let claimed = client.claim_batch(&vec![
+        &env,
+        beneficiary.clone(),
+        later.clone(),
+        finished.clone(),
+    ]);
+    assert_eq!(claimed, vec![&env, 500, 0, 0]);
+
+    assert_eq!(token_client.balance(&beneficiary), 500);
+    assert_eq!(token_client.balance(&later), 0);
+    assert_eq!(token_client.balance(&finished), 1_000);
+}
+
+#[test]
+fn test_completed_grant_awards_reputation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, contract_id) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    // Registry that lets the vesting wallet award reputation
+    let registry_id = env.register(ContributorRegistryContract, ());
+    let registry = ContributorRegistryContractClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.register_contributor(&beneficiary, &String::from_str(&env, "grantee"));
+    registry.set_reputation_source(&admin, &contract_id, &true);
+
+    client.set_completion_bonus(&admin, &registry_id, &25);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &1_000, &start_time, &1_000);
+
+    // Partial claim: no bonus yet
+    env.ledger().with_mut(|li| li.timestamp = start_time + 500);
+    client.claim(&beneficiary);
+    assert_eq!(registry.get_reputation(&beneficiary), 0);
+
+    // Completing the grant awards the bonus
+    env.ledger()
+        .with_mut(|li| li.timestamp = start_time + 1_000);
+    client.claim(&beneficiary);
+    assert_eq!(registry.get_reputation(&beneficiary), 25);
+
+    // Unregistered beneficiaries still complete their claim
+    let stranger = Address::generate(&env);
+    client.create_vesting(&admin, &stranger, &1_000, &(start_time + 1_000), &1);
+    env.ledger()
+        .with_mut(|li| li.timestamp = start_time + 1_001);
+    assert_eq!(client.claim(&stranger), 1_000);
+}
+
+#[test]
+fn test_invariants_hold_under_random_claims() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+    assert!(client.check_invariants());
+
+    let start_time = env.ledger().timestamp() + 10;
+    let beneficiaries = [
+        beneficiary,
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for (i, b) in beneficiaries.iter().enumerate() {
+        let i = i as u64;
+        client.create_vesting(
+            &admin,
+            b,
+            &(100_003 * (i as i128 + 1)),
+            &(start_time + i * 50),
+            &(997 + i * 311),
+        );
+    }
+    assert!(client.check_invariants());
+
+    // Deterministic pseudo-random sequence of time steps and claims
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed
+    };
+    for _ in 0..60 {
+        let step = next() % 60;
+        env.ledger().with_mut(|li| li.timestamp += step);
+
+        let b = &beneficiaries[(next() % 3) as usize];
+        let _ = client.try_claim(b);
+        assert!(client.check_invariants());
+    }
+
+    // Drain everything at the end
+    env.ledger()
+        .with_mut(|li| li.timestamp = start_time + 10_000);
+    for b in beneficiaries.iter() {
+        let _ = client.try_claim(b);
+    }
+    assert!(client.check_invariants());
+    assert_eq!(client.total_outstanding(), 0);
+}
+
+#[test]
+fn test_reclaim_expired() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, contract_id) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+    client.set_claim_expiry(&admin, &beneficiary, &500);
+
+    env.ledger().with_mut(|li| li.timestamp = start_time + 200);
+    client.claim(&beneficiary);
+
+    // Fully vested but still inside the claim window
+    env.ledger().with_mut(|li| li.timestamp = start_time + 1499);
+    let result = client.try_reclaim_expired(&admin, &beneficiary);
+    assert_eq!(result, Err(Ok(VestingError::ClaimNotExpired)));
+
+    env.ledger().with_mut(|li| li.timestamp = start_time + 1500);
+    let admin_before = token_client.balance(&admin);
+    assert_eq!(client.reclaim_expired(&admin, &beneficiary), 8_000);
+
+    assert_eq!(token_client.balance(&admin), admin_before + 8_000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+    assert_eq!(client.total_outstanding(), 0);
+    assert_eq!(client.get_beneficiaries().len(), 0);
+
+    let result = client.try_get_vesting(&beneficiary);
+    assert_eq!(result, Err(Ok(VestingError::VestingNotFound)));
+}
+
+#[test]
+fn test_reclaim_without_expiry_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = start_time + 100_000);
+    let result = client.try_reclaim_expired(&admin, &beneficiary);
+    assert_eq!(result, Err(Ok(VestingError::ClaimNotExpired)));
+}
+
+#[test]
+fn test_admin_actions_share_audit_topic() {
+    use crate::events::AdminActionEvent;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+    let fee_recipient = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+
+    let assert_audited = |action: &str| {
+        let expected = AdminActionEvent {
+            action: soroban_sdk::Symbol::new(&env, action),
+            admin: admin.clone(),
+        };
+        assert!(
+            env.events()
+                .all()
+                .iter()
+                .any(|(_, topics, _)| topics == expected.topics(&env)),
+            "{action}"
+        );
+    };
+
+    client.set_claim_fee(&admin, &100, &fee_recipient);
+    assert_audited("set_claim_fee");
+    client.set_min_duration(&admin, &0);
+    assert_audited("set_min_duration");
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &1_000, &start_time, &1_000);
+    assert_audited("create_vesting");
+    client.freeze_schedule(&admin, &beneficiary);
+    assert_audited("freeze_schedule");
+    client.unfreeze_schedule(&admin, &beneficiary);
+    assert_audited("unfreeze_schedule");
+    client.hold_schedule(&admin, &beneficiary, &String::from_str(&env, "audit"));
+    assert_audited("hold_schedule");
+    client.release_hold(&admin, &beneficiary);
+    assert_audited("release_hold");
+    client.cancel_vesting(&admin, &beneficiary, &false);
+    assert_audited("cancel_vesting");
+
+    client.set_admin(&admin, &new_admin);
+    assert_audited("set_admin");
+}
+
+#[test]
+fn test_migrate_runs_once_per_version() {
+    use crate::events::VersionBumpedEvent;
+    use crate::storage::{DataKey, VestingDataV1};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, contract_id) = setup_test(&env);
+    let treasury = Address::generate(&env);
+    client.initialize(&admin, &token_client.address);
+
+    assert_eq!(client.get_version(), 2);
+
+    let other = Address::generate(&env);
+    let start_time = env.ledger().timestamp();
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+    client.create_vesting(&admin, &other, &4_000, &start_time, &1000);
+    env.ledger().set_timestamp(start_time + 250);
+    client.claim(&beneficiary);
+
+    // Simulate storage written by a build that predates versioning
+    env.as_contract(&contract_id, || {
+        for (address, total_amount, claimed_amount) in
+            [(&beneficiary, 10_000, 2_500), (&other, 4_000, 0)]
+        {
+            env.storage().persistent().set(
+                &DataKey::Vesting(address.clone()),
+                &VestingDataV1 {
+                    beneficiary: address.clone(),
+                    total_amount,
+                    start_time,
+                    duration: 1000,
+                    claimed_amount,
+                },
+            );
+        }
+        env.storage().persistent().remove(&DataKey::Beneficiaries);
+        env.storage().instance().remove(&DataKey::TotalOutstanding);
+        env.storage().instance().remove(&DataKey::Version);
+    });
+
+    // Listed beneficiaries must have a schedule
+    let result = client.try_migrate(&admin, &2, &vec![&env, Address::generate(&env)]);
+    assert_eq!(result, Err(Ok(VestingError::VestingNotFound)));
+
+    // The version holds until every legacy schedule is migrated
+    client.migrate(&admin, &2, &vec![&env, other.clone()]);
+    assert_eq!(client.get_version(), 0);
+    let result = client.try_recover_surplus(&admin, &treasury);
+    assert_eq!(result, Err(Ok(VestingError::MigrationIncomplete)));
+
+    client.migrate(&admin, &2, &vec![&env, beneficiary.clone()]);
+    let expected = VersionBumpedEvent {
+        old_version: 0,
+        new_version: 2,
+    };
+    let events = env.events().all();
+    let emitted = events
+        .iter()
+        .find(|(_, topics, _)| *topics == expected.topics(&env))
+        .unwrap();
+    assert_eq!(vec![&env, emitted.2], vec![&env, expected.data(&env)]);
+    assert_eq!(client.get_version(), 2);
+
+    // The schedules read in the new layout and still back their tokens
+    assert_eq!(client.get_vesting(&beneficiary).claimed_amount, 2_500);
+    assert_eq!(
+        client.get_beneficiaries(),
+        vec![&env, other.clone(), beneficiary.clone()]
+    );
+    assert_eq!(client.total_outstanding(), 11_500);
+    let result = client.try_recover_surplus(&admin, &treasury);
+    assert_eq!(result, Err(Ok(VestingError::NoSurplus)));
+
+    let result = client.try_migrate(&admin, &0, &vec![&env]);
+    assert_eq!(result, Err(Ok(VestingError::AlreadyMigrated)));
+}
+
+#[test]
+fn test_get_vested_total_ignores_claims() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = start_time + 500);
+    client.claim(&beneficiary);
+
+    assert_eq!(client.get_vested_total(&beneficiary), 5_000);
+    assert_eq!(client.get_claimable(&beneficiary), 0);
+
+    let result = client.try_get_vested_total(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(VestingError::VestingNotFound)));
+}
+
+#[test]
+fn test_claim_requires_acceptance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+    client.require_acceptance(&admin, &beneficiary, &false);
+
+    env.ledger().with_mut(|li| li.timestamp = start_time + 500);
+    assert!(!client.can_claim(&beneficiary));
+    let result = client.try_claim(&beneficiary);
+    assert_eq!(result, Err(Ok(VestingError::NotAccepted)));
+
+    client.accept_vesting(&beneficiary);
+    assert_eq!(client.claim(&beneficiary), 5_000);
+
+    // A schedule that already started cannot be put back behind acceptance
+    let result = client.try_require_acceptance(&admin, &beneficiary, &false);
+    assert_eq!(result, Err(Ok(VestingError::ScheduleStarted)));
+    let other = Address::generate(&env);
+    let now = env.ledger().timestamp();
+    client.create_vesting(&admin, &other, &10_000, &now, &1000);
+    let result = client.try_require_acceptance(&admin, &other, &true);
+    assert_eq!(result, Err(Ok(VestingError::ScheduleStarted)));
+}
+
+#[test]
+fn test_acceptance_can_restart_vesting() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+    client.require_acceptance(&admin, &beneficiary, &true);
+
+    // Vesting starts from the acceptance time rather than the original start
+    env.ledger().with_mut(|li| li.timestamp = start_time + 500);
+    client.accept_vesting(&beneficiary);
+    assert_eq!(
+        client.get_vesting(&beneficiary).start_time,
+        start_time + 500
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = start_time + 750);
+    assert_eq!(client.claim(&beneficiary), 2_500);
+}
+
+#[test]
+fn test_start_on_acceptance_holds_clock_until_accepted() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let created_at = env.ledger().timestamp();
+    client.create_vesting(&admin, &beneficiary, &10_000, &(created_at + 100), &1000);
+    client.require_acceptance(&admin, &beneficiary, &true);
+    assert_eq!(client.get_vesting(&beneficiary).start_time, 0);
+
+    // Nothing accrues before acceptance, even well past the full duration
+    env.ledger().with_mut(|li| li.timestamp = created_at + 5000);
+    assert_eq!(client.get_claimable(&beneficiary), 0);
+    assert_eq!(client.time_to_full_vest(&beneficiary), 1000);
+
+    client.accept_vesting(&beneficiary);
+    assert_eq!(
+        client.get_vesting(&beneficiary).start_time,
+        created_at + 5000
+    );
+    assert_eq!(client.get_claimable(&beneficiary), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = created_at + 5250);
+    assert_eq!(client.get_claimable(&beneficiary), 2_500);
+}
+
+#[test]
+fn test_get_decimals_matches_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    assert_eq!(client.get_decimals(), token_client.decimals());
+}
+
+#[test]
+fn test_total_claimable_now() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, first, token_client, _) = setup_test(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &first, &10_000, &start_time, &1000);
+    client.create_vesting(&admin, &second, &4_000, &start_time, &2000);
+    client.create_vesting(&admin, &third, &1_000, &(start_time + 1000), &1000);
+    assert_eq!(client.total_claimable_now(), 0);
+
+    // 50% of the first, 25% of the second and none of the third
+    env.ledger().with_mut(|li| li.timestamp = start_time + 500);
+    assert_eq!(client.total_claimable_now(), 5_000 + 1_000);
+
+    // Claims are excluded from the total
+    client.claim(&first);
+    assert_eq!(client.total_claimable_now(), 1_000);
+}
+
+#[test]
+fn test_claim_event_carries_schema_version() {
+    use crate::events::TokensClaimedEvent;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, contract_id) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+
+    env.ledger().with_mut(|li| li.timestamp = start_time + 500);
+    client.claim(&beneficiary);
+
+    let expected = TokensClaimedEvent {
+        beneficiary: beneficiary.clone(),
+        amount_claimed: 5_000,
+        remaining: 5_000,
+        fee: 0,
+        schema_version: 1,
+    };
+    let events = env.events().all();
+    let emitted = events
+        .iter()
+        .find(|(contract, topics, _)| *contract == contract_id && *topics == expected.topics(&env))
+        .unwrap();
+    assert_eq!(
+        vec![&env, emitted],
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                expected.topics(&env),
+                expected.data(&env)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_is_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    assert!(!client.is_admin(&admin));
+
+    client.initialize(&admin, &token_client.address);
+    assert!(client.is_admin(&admin));
+    assert!(!client.is_admin(&beneficiary));
+}
+
+#[test]
+fn test_schedule_label() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+    assert_eq!(
+        client.get_vesting(&beneficiary).label,
+        String::from_str(&env, "")
+    );
+
+    let label = String::from_str(&env, "Q3 2025 advisor grant");
+    client.set_label(&admin, &beneficiary, &label);
+    assert_eq!(client.get_vesting(&beneficiary).label, label);
+
+    let renamed = String::from_str(&env, "Q4 2025 advisor grant");
+    client.set_label(&admin, &beneficiary, &renamed);
+    assert_eq!(client.get_vesting(&beneficiary).label, renamed);
+
+    let result = client.try_set_label(&beneficiary, &beneficiary, &label);
+    assert_eq!(result, Err(Ok(VestingError::Unauthorized)));
+
+    let too_long = String::from_str(&env, &"x".repeat(65));
+    let result = client.try_set_label(&admin, &beneficiary, &too_long);
+    assert_eq!(result, Err(Ok(VestingError::LabelTooLong)));
+}
+
+#[test]
+fn test_min_duration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, _) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+    client.set_min_duration(&admin, &1000);
+    assert_eq!(client.get_min_duration(), 1000);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let result = client.try_create_vesting(&admin, &beneficiary, &10_000, &start_time, &999);
+    assert_eq!(result, Err(Ok(VestingError::DurationTooShort)));
+
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+    assert_eq!(client.get_vesting(&beneficiary).duration, 1000);
+}
+
+#[test]
+fn test_get_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, token_client, _) = setup_test(&env);
+    let result = client.try_get_config();
+    assert_eq!(result, Err(Ok(VestingError::NotInitialized)));
+
+    client.initialize(&admin, &token_client.address);
+    let config = client.get_config();
+    assert_eq!(config.admin, admin);
+    assert_eq!(config.token, token_client.address);
+    assert_eq!(config.token_decimals, token_client.decimals());
+    assert_eq!(config.claim_fee_bps, 0);
+    assert_eq!(config.fee_recipient, None);
+    assert_eq!(config.min_duration, 0);
+
+    let fee_recipient = Address::generate(&env);
+    client.set_claim_fee(&admin, &250, &fee_recipient);
+    client.set_min_duration(&admin, &3600);
+    let config = client.get_config();
+    assert_eq!(config.claim_fee_bps, 250);
+    assert_eq!(config.fee_recipient, Some(fee_recipient));
+    assert_eq!(config.min_duration, 3600);
+}
+
+#[test]
+fn test_overwrite_pays_vested_tokens_to_beneficiary() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client, contract_id) = setup_test(&env);
+    client.initialize(&admin, &token_client.address);
+
+    let start_time = env.ledger().timestamp() + 100;
+    client.create_vesting(&admin, &beneficiary, &10_000, &start_time, &1000);
+
+    // Overwrite at 40% vested with 1,000 already claimed
+    env.ledger().with_mut(|li| li.timestamp = start_time + 100);
+    client.claim(&beneficiary);
+    env.ledger().with_mut(|li| li.timestamp = start_time + 400);
+
+    let admin_before = token_client.balance(&admin);
+    let new_start = start_time + 500;
+    client.create_vesting(&admin, &beneficiary, &2_000, &new_start, &1000);
+
+    assert_eq!(token_client.balance(&beneficiary), 4_000);
+    assert_eq!(token_client.balance(&admin), admin_before + 6_000 - 2_000);
+    assert_eq!(token_client.balance(&contract_id), 2_000);
+    assert_eq!(client.total_outstanding(), 2_000);
+    assert_eq!(client.total_claimed(), 4_000);
 }
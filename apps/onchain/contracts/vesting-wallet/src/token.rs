@@ -6,8 +6,16 @@ pub fn transfer(env: &Env, token: &Address, from: &Address, to: &Address, amount
     token_client.transfer(from, to, amount);
 }
 
+/// Read a token's decimals, or `None` if the address is not a token contract
+pub fn decimals(env: &Env, token: &Address) -> Option<u32> {
+    let token_client = soroban_sdk::token::Client::new(env, token);
+    match token_client.try_decimals() {
+        Ok(Ok(decimals)) => Some(decimals),
+        _ => None,
+    }
+}
+
 /// Get the balance of an address for a given token
-#[allow(dead_code)]
 pub fn balance(env: &Env, token: &Address, address: &Address) -> i128 {
     let token_client = soroban_sdk::token::Client::new(env, token);
     token_client.balance(address)